@@ -0,0 +1,300 @@
+use crate::*;
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use bevy::ecs::system::EntityCommands;
+use bevy::utils::{HashMap, HashSet};
+use lunex_engine::NodeDataTrait;
+use thiserror::Error;
+
+
+// #=============#
+// #=== ASSET ===#
+
+/// Declarative description of [`UiTree`] nodes, loaded from `.lunex.ron` files.
+/// Nodes are described by their path and are spawned in the order they are written.
+/// Optional values are written without `Some(...)`.
+/// ## 🛠️ Example
+/// ```ron
+/// (
+///     nodes: [
+///         (
+///             path: "Root",
+///             base: Window((size: (rl: (100.0, 100.0)))),
+///         ),
+///         (
+///             path: "Root/Button",
+///             base: Window((pos: (rl: (10.0, 10.0)), size: (rl: (30.0, 10.0)))),
+///             hover: Window((pos: (rl: (12.0, 10.0)), size: (rl: (30.0, 10.0)))),
+///             depth_bias: 10.0,
+///             font_size: (em: 1.5),
+///         ),
+///     ],
+/// )
+/// ```
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UiLayoutAsset {
+    /// All nodes of the tree
+    pub nodes: Vec<UiNodeAsset>,
+}
+impl UiLayoutAsset {
+    /// Parses the layout from RON, optional values don't need to be wrapped in `Some(...)`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ron::error::SpannedError> {
+        ron::Options::default().with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME).from_bytes(bytes)
+    }
+}
+
+/// Single node of [`UiLayoutAsset`]
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct UiNodeAsset {
+    /// Path of the node, for example `Root/Menu/Button`
+    pub path: String,
+    /// Layout in [`Base`] state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<Layout>,
+    /// Layout in [`Hover`] state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hover: Option<Layout>,
+    /// Layout in [`Clicked`] state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clicked: Option<Layout>,
+    /// Layout in [`Selected`] state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected: Option<Layout>,
+    /// Layout in [`Intro`] state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intro: Option<Layout>,
+    /// Layout in [`Outro`] state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outro: Option<Layout>,
+    /// Layout in [`Droppable`] state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub droppable: Option<Layout>,
+    /// Optional [`UiStack`] component
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack: Option<UiStack>,
+    /// Optional [`UiDepthBias`] component
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_bias: Option<f32>,
+    /// Optional [`UiFontSize`] component
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<UiValue<f32>>,
+}
+impl UiNodeAsset {
+    /// Inserts the components of this node into the entity and removes the ones that are not set.
+    fn apply(&self, entity: &mut EntityCommands) {
+        entity.insert(UiLayout::<Base>::from(self.base.unwrap_or_default()));
+        apply_layout::<Hover>(entity, self.hover);
+        apply_layout::<Clicked>(entity, self.clicked);
+        apply_layout::<Selected>(entity, self.selected);
+        apply_layout::<Intro>(entity, self.intro);
+        apply_layout::<Outro>(entity, self.outro);
+        apply_layout::<Droppable>(entity, self.droppable);
+
+        match &self.stack {
+            Some(stack) => entity.insert(stack.clone()),
+            None => entity.remove::<UiStack>(),
+        };
+        match self.depth_bias {
+            Some(bias) => entity.insert(UiDepthBias(bias)),
+            None => entity.remove::<UiDepthBias>(),
+        };
+        match self.font_size {
+            Some(size) => entity.insert(UiFontSize(size)),
+            None => entity.remove::<UiFontSize>(),
+        };
+        if [self.hover, self.clicked, self.selected, self.intro, self.outro, self.droppable].iter().any(Option::is_some) {
+            entity.insert(UiLayoutController::default());
+        }
+    }
+    /// Resets the node data the removed components would leave behind.
+    fn clear<N:Default + Component>(&self, data: &mut NodeData<N>) {
+        for (index, layout) in [(Hover::INDEX, self.hover), (Clicked::INDEX, self.clicked), (Selected::INDEX, self.selected), (Intro::INDEX, self.intro), (Outro::INDEX, self.outro), (Droppable::INDEX, self.droppable)] {
+            if layout.is_none() { data.layout.remove(&index); }
+        }
+        if self.stack.is_none() { data.stack = UiStack::default(); }
+        if self.depth_bias.is_none() { data.depth_bias = 0.0; }
+        if self.font_size.is_none() { data.font_size = None; }
+        data.mark_dirty();
+    }
+}
+fn apply_layout<S: UiState>(entity: &mut EntityCommands, layout: Option<Layout>) {
+    match layout {
+        Some(layout) => entity.insert(UiLayout::<S>::from(layout)),
+        None => entity.remove::<UiLayout<S>>(),
+    };
+}
+
+
+// #==============#
+// #=== LOADER ===#
+
+/// Error returned by [`UiLayoutAssetLoader`]
+#[derive(Debug, Error)]
+pub enum UiLayoutAssetError {
+    /// The file could not be read
+    #[error("Could not read the file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The file is not a valid layout
+    #[error("Could not parse the layout: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+/// Loader of [`UiLayoutAsset`] from `.lunex.ron` files
+#[derive(Debug, Default)]
+pub struct UiLayoutAssetLoader;
+impl AssetLoader for UiLayoutAssetLoader {
+    type Asset = UiLayoutAsset;
+    type Settings = ();
+    type Error = UiLayoutAssetError;
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<UiLayoutAsset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(UiLayoutAsset::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lunex.ron"]
+    }
+}
+
+
+// #===============#
+// #=== SPAWNER ===#
+
+/// Add this component to [`UiTreeBundle`] entity to spawn its nodes from [`UiLayoutAsset`].
+/// Each node is spawned as a child entity with [`UiLink`] and its layouts. When the file is modified,
+/// the layouts are applied again to the same entities. Nodes removed from the file are despawned
+/// and removed from the tree, unless the tree node existed before the asset was applied.
+/// ## 🛠️ Example
+/// ```
+/// commands.spawn((
+///     UiTreeBundle::<MainUi>::from(UiTree::new2d("Menu")),
+///     UiLayoutSource::new(asset_server.load("menu.lunex.ron")),
+///     MovableByCamera,
+/// ));
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct UiLayoutSource {
+    /// The asset to spawn the nodes from
+    pub handle: Handle<UiLayoutAsset>,
+    /// Spawned entities by their path
+    nodes: HashMap<String, Entity>,
+    /// Paths of the tree nodes created by the asset
+    created: HashSet<String>,
+    /// If the asset was already applied
+    applied: bool,
+}
+impl UiLayoutSource {
+    /// Creates new struct
+    pub fn new(handle: Handle<UiLayoutAsset>) -> Self {
+        UiLayoutSource {
+            handle,
+            nodes: HashMap::new(),
+            created: HashSet::new(),
+            applied: false,
+        }
+    }
+    /// Returns the entity spawned for this path
+    pub fn get(&self, path: impl Borrow<str>) -> Option<Entity> {
+        self.nodes.get(path.borrow()).copied()
+    }
+}
+
+/// This system spawns the nodes of [`UiLayoutSource`] once the asset is loaded and reapplies them when it is modified.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn ui_layout_source_system<T:Component, N:Default + Component>(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<UiLayoutAsset>>,
+    assets: Res<Assets<UiLayoutAsset>>,
+    mut query: Query<(&mut UiLayoutSource, &mut UiTree<T, N>, Entity)>,
+) {
+    let modified: HashSet<AssetId<UiLayoutAsset>> = events.read().filter_map(|event| match event {
+        AssetEvent::Modified { id } => Some(*id),
+        _ => None,
+    }).collect();
+
+    for (mut source, mut ui, tree) in &mut query {
+        if source.applied && !modified.contains(&source.handle.id()) { continue }
+        let Some(asset) = assets.get(&source.handle) else { continue };
+        source.applied = true;
+
+        #[cfg(feature = "verbose")]
+        info!("{} {} - Applying layout asset", "--".yellow(), "ASSET".red());
+
+        for node in &asset.nodes {
+            match source.nodes.get(&node.path).copied() {
+                Some(entity) => {
+                    if let Some(data) = ui.borrow_node_mut(node.path.as_str()).ok().and_then(|node| node.obtain_data_mut()) { node.clear(data); }
+                    node.apply(&mut commands.entity(entity));
+                },
+                None => {
+                    if ui.borrow_node(node.path.as_str()).is_err() { source.created.insert(node.path.clone()); }
+                    let mut entity = commands.spawn((UiLink::<T>::path(node.path.as_str()), SpatialBundle::default()));
+                    entity.set_parent(tree);
+                    node.apply(&mut entity);
+                    let entity = entity.id();
+                    source.nodes.insert(node.path.clone(), entity);
+                },
+            }
+        }
+
+        // Despawn nodes that were removed from the file, the tree restacks their remaining Div siblings
+        let paths: HashSet<&str> = asset.nodes.iter().map(|node| node.path.as_str()).collect();
+        let UiLayoutSource { nodes, created, .. } = &mut *source;
+        nodes.retain(|path, entity| {
+            if paths.contains(path.as_str()) { return true; }
+            commands.entity(*entity).despawn_recursive();
+            if created.remove(path) { let _ = ui.remove_node(path.as_str()); }
+            false
+        });
+    }
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// Plugin registering [`UiLayoutAsset`] and its loader
+pub struct UiAssetPlugin;
+impl Plugin for UiAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<UiLayoutAsset>()
+            .register_asset_loader(UiLayoutAssetLoader);
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn implicit_some () {
+        let asset = UiLayoutAsset::from_bytes(br#"(
+            nodes: [
+                (
+                    path: "Root/Button",
+                    base: Window((size: (rl: (30.0, 10.0)))),
+                    depth_bias: 10.0,
+                    font_size: (em: 1.5),
+                ),
+            ],
+        )"#).unwrap();
+
+        let node = &asset.nodes[0];
+        assert_eq!(node.base, Some(Layout::Window(ui::Window::new().size(Rl((30.0, 10.0))))));
+        assert_eq!(node.hover, None);
+        assert_eq!(node.depth_bias, Some(10.0));
+        assert_eq!(node.font_size, Some(Em(1.5).into()));
+    }
+}
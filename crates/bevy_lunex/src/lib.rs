@@ -78,6 +78,12 @@ impl PluginGroup for UiDefaultPlugins {
         // Add non generic state logic
         builder = builder.add(UiLogicPlugin);
 
+        // Add widgets
+        builder = builder.add(UiWidgetsPlugin);
+
+        // Add materials
+        builder = builder.add(UiMaterialPlugin);
+
         // Add picking
         builder = builder.add(UiLunexPickingPlugin);
         builder = builder.add_group(DefaultPickingPlugins.build().disable::<InputPlugin>());
@@ -91,9 +97,17 @@ impl PluginGroup for UiDefaultPlugins {
 // #======================#
 // #=== PRELUDE EXPORT ===#
 
+#[cfg(feature = "serde")]
+pub mod asset;
+#[cfg(feature = "serde")]
+pub use asset::*;
+
 pub mod logic;
 pub use logic::*;
 
+pub mod material;
+pub use material::*;
+
 pub mod picking;
 pub use picking::*;
 
@@ -103,13 +117,20 @@ pub use structs::*;
 pub mod systems;
 pub use systems::*;
 
+pub mod widgets;
+pub use widgets::*;
+
 
 pub mod prelude {
 
     pub use super::Cursor2d;
     pub use super::actions;
 
+    #[cfg(feature = "serde")]
+    pub use super::asset::*;
+
     pub use super::logic::*;
+    pub use super::material::*;
 
     // BEVY-LUNEX SPECIFIC
     pub use super::UiGenericPlugins;
@@ -120,6 +141,8 @@ pub mod prelude {
 
     pub use super::PickingPortal;
 
+    pub use super::widgets::*;
+
     // RE-EXPORT BEVY MOD PICKING
     pub use bevy_mod_picking::prelude::*;
     
@@ -194,15 +194,15 @@ fn apply_event_set_ui_layout(mut events: EventReader<SetUiLayout>, mut query: Qu
     }
 }
 
-/// This event will override sprite/text color of targetted entity
+/// This event will override sprite/text/material color of targetted entity
 #[derive(Event, PartialEq, Clone, Copy)]
 pub struct SetColor {
     pub target: Entity,
     pub color: Color,
 }
-fn apply_event_set_color(mut events: EventReader<SetColor>, mut materials: ResMut<Assets<StandardMaterial>>, mut query: Query<(Option<&mut Sprite>, Option<&mut Text>, Option<&Handle<StandardMaterial>>)>) {
+fn apply_event_set_color(mut events: EventReader<SetColor>, mut materials: ResMut<Assets<StandardMaterial>>, mut materials2d: ResMut<Assets<ColorMaterial>>, mut query: Query<(Option<&mut Sprite>, Option<&mut Text>, Option<&Handle<StandardMaterial>>, Option<&Handle<ColorMaterial>>)>) {
     for event in events.read() {
-        if let Ok((sprite_option, text_option, material_option)) = query.get_mut(event.target) {
+        if let Ok((sprite_option, text_option, material_option, material2d_option)) = query.get_mut(event.target) {
             if let Some(mut sprite) = sprite_option {
                 sprite.color = event.color;
             }
@@ -216,6 +216,11 @@ fn apply_event_set_color(mut events: EventReader<SetColor>, mut materials: ResMu
                     material.base_color = event.color;
                 }
             }
+            if let Some(material_handle) = material2d_option {
+                if let Some(material) = materials2d.get_mut(material_handle) {
+                    material.color = event.color;
+                }
+            }
         }
     }
 }
@@ -77,6 +77,8 @@ fn on_ui_click_commands_system(mut events: EventReader<UiClickEvent>, mut comman
 
 
 
+/// When clicked on this entity, it will despawn the specified entity through [`UiDespawnCommands::ui_despawn`].
+/// If the entity has [`UiAnimator<Outro>`], the animation is played first.
 #[derive(Component, Debug, Clone, PartialEq, Eq)]
 pub struct OnUiClickDespawn {
     pub target: Option<Entity>,
@@ -94,7 +96,7 @@ impl OnUiClickDespawn {
 fn on_ui_click_despawn_system(mut events: EventReader<UiClickEvent>, mut commands: Commands, query: Query<(&OnUiClickDespawn, Entity)>) {
     for event in events.read() {
         if let Ok((listener, entity)) = query.get(event.target) {
-            commands.entity(if let Some(e) = listener.target { e } else { entity }).despawn_recursive();
+            commands.entity(if let Some(e) = listener.target { e } else { entity }).ui_despawn();
         }
     }
 }
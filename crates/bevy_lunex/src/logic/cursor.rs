@@ -90,8 +90,9 @@ pub enum GamepadCursorMode {
     /// Cursor will freely move on input.
     #[default]
     Free,
-    // /// Will try to snap to nearby nodes on input.
-    //Snap,
+    /// Cursor will glide to the nearest pickable node in the input direction.
+    /// If there is no node in range, it will move freely instead.
+    Snap,
 }
 
 
@@ -184,23 +185,148 @@ fn cursor_change_native(
 }
 
 
+/// Maximum distance in pixels at which [`GamepadCursorMode::Snap`] looks for the next node.
+const SNAP_RANGE: f32 = 600.0;
+/// Minimum cosine of the angle between the input direction and the next node.
+const SNAP_CONE: f32 = 0.5;
+/// How quickly the cursor eases towards the target node.
+const SNAP_EASING: f32 = 14.0;
+/// Distance from a node centre at which a resting cursor gets pulled in.
+const SNAP_MAGNETISM: f32 = 48.0;
+/// Distance from the node centre at which the target counts as reached.
+const SNAP_REACHED: f32 = 2.0;
+
+/// State of a cursor in [`GamepadCursorMode::Snap`].
+#[derive(Debug, Clone, Copy, Default)]
+struct CursorSnap {
+    /// The node the cursor is gliding towards.
+    target: Option<Entity>,
+    /// The input direction that selected the target.
+    direction: Vec2,
+}
+
+/// Query of nodes that the cursor can snap to.
+type SnapNodeQuery<'w, 's> = Query<'w, 's, (Entity, &'static Dimension, &'static GlobalTransform, Has<Element>, &'static ViewVisibility, Option<&'static Pickable>, Option<&'static UiClip>, Option<&'static Parent>)>;
+
+/// Returns the centres of all pickable nodes in cursor space. Nodes without [`Pickable`] are pickable.
+fn snap_node_centres(
+    nodes: &SnapNodeQuery,
+    modals: &ModalQuery,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    window: &Window,
+) -> Vec<(Entity, Vec2)> {
+    let modal_depths = modal_depths(modals);
+    nodes.iter()
+        .filter(|(_, dimension, transform, _, visibility, pickable, _, parent)| visibility.get() && pickable.map(|p| p.is_hoverable) != Some(false) && dimension.size.min_element() > 0.0 && !is_below_modal(&modal_depths, *parent, transform))
+        .filter_map(|(entity, dimension, transform, is_element, _, _, clip, _)| {
+            let mut centre = node_rect(dimension, is_element).center();
+
+            // Snap only to the visible part of nodes inside scroll containers
+            if let Some(clip) = clip {
+                let visible = clip.rect.intersect(node_rect(dimension, is_element));
+                if visible.is_empty() { return None; }
+                centre = visible.center();
+            }
+
+            let viewport = camera.world_to_viewport(camera_transform, transform.transform_point(centre.extend(0.0)))?;
+            Some((entity, Vec2::new(viewport.x - window.width()/2.0, -(viewport.y - window.height()/2.0))))
+        })
+        .collect()
+}
+
+/// Returns the nearest node in the input direction, preferring nodes aligned with it.
+fn snap_find_in_direction(centres: &[(Entity, Vec2)], location: Vec2, direction: Vec2, exclude: Option<Entity>) -> Option<Entity> {
+    centres.iter()
+        .filter(|(entity, _)| Some(*entity) != exclude)
+        .filter_map(|(entity, centre)| {
+            let offset = *centre - location;
+            let distance = offset.length();
+            if distance <= SNAP_REACHED || distance > SNAP_RANGE { return None; }
+            let cos = offset.dot(direction) / distance;
+            if cos < SNAP_CONE { return None; }
+            Some((*entity, distance * (2.0 - cos)))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
 /// This function controls the location of the cursor based on gamepad input
 fn gamepad_move_cursor(
     axis: Res<Axis<GamepadAxis>>,
     time: Res<Time>,
     windows: Query<&Window, With<PrimaryWindow>>,
-    mut query: Query<(&mut Cursor2d, &GamepadCursor)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, &OrthographicProjection)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    nodes: SnapNodeQuery,
+    modals: ModalQuery,
+    mut snaps: Local<HashMap<Entity, CursorSnap>>,
+    mut query: Query<(Entity, &mut Cursor2d, &GamepadCursor, Option<&PointerLocation>)>,
 ) {
     if let Ok(window) = windows.get_single() {
-        for (mut cursor, gamepad) in query.iter_mut() {
+        for (entity, mut cursor, gamepad, pointer) in query.iter_mut() {
             // Pull axis values
             let x = axis.get(GamepadAxis { gamepad: Gamepad::new(gamepad.id), axis_type: GamepadAxisType::LeftStickX });
             let y = axis.get(GamepadAxis { gamepad: Gamepad::new(gamepad.id), axis_type: GamepadAxisType::LeftStickY });
 
             if let (Some(x), Some(y)) = (x, y) {
+                let input = Vec2::new(x, y);
+                let mut free = true;
+
+                if gamepad.mode == GamepadCursorMode::Snap {
+                    // Find the camera the cursor is rendered with
+                    let camera = pointer.and_then(|pointer| pointer.location.as_ref()).and_then(|location| pointer_world_position(location, &cameras, &primary_window));
+                    if let Some(((_, camera, camera_transform, _), _)) = camera {
+                        let centres = snap_node_centres(&nodes, &modals, camera, camera_transform, window);
+                        let snap = snaps.entry(entity).or_default();
+                        let location = cursor.location;
+                        let centre_of = |target: Entity| centres.iter().find(|(e, _)| *e == target).map(|(_, c)| *c);
+
+                        // Forget targets that are no longer reachable
+                        if snap.target.is_some_and(|target| centre_of(target).is_none()) { snap.target = None; }
+
+                        if input != Vec2::ZERO {
+                            let direction = input.normalize();
+                            let reached = snap.target.and_then(centre_of).map(|centre| centre.distance(location) <= SNAP_REACHED).unwrap_or(true);
+
+                            // Pick a new target on a fresh push, a change of direction or once the old one is reached
+                            if reached || snap.direction.dot(direction) < SNAP_CONE {
+                                snap.target = snap_find_in_direction(&centres, location, direction, snap.target);
+                                snap.direction = direction;
+                            }
+                        } else {
+                            snap.direction = Vec2::ZERO;
+
+                            // Pull the resting cursor into the centre of the nearest node
+                            if snap.target.is_none() {
+                                snap.target = centres.iter()
+                                    .map(|(e, centre)| (*e, centre.distance(location)))
+                                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                                    .filter(|(_, distance)| *distance > SNAP_REACHED && *distance <= SNAP_MAGNETISM)
+                                    .map(|(e, _)| e);
+                            }
+                        }
+
+                        // Ease towards the target
+                        if let Some(centre) = snap.target.and_then(centre_of) {
+                            let t = 1.0 - (-SNAP_EASING * gamepad.speed * time.delta_seconds()).exp();
+                            cursor.location = location.lerp(centre, t);
+                            if cursor.location.distance(centre) <= SNAP_REACHED {
+                                cursor.location = centre;
+                                if input == Vec2::ZERO { snap.target = None; }
+                            }
+                            free = false;
+                        }
+                    }
+                } else {
+                    snaps.remove(&entity);
+                }
+
                 // Move the cursor
-                cursor.location.x += x * time.delta_seconds() * 500.0 * gamepad.speed;
-                cursor.location.y += y * time.delta_seconds() * 500.0 * gamepad.speed;
+                if free {
+                    cursor.location.x += x * time.delta_seconds() * 500.0 * gamepad.speed;
+                    cursor.location.y += y * time.delta_seconds() * 500.0 * gamepad.speed;
+                }
 
                 // Clamp the cursor within window
                 let w = window.width()/2.0;
@@ -210,6 +336,9 @@ fn gamepad_move_cursor(
             }
         }
     }
+
+    // Forget despawned cursors
+    snaps.retain(|entity, _| query.contains(*entity));
 }
 
 /// This function controls the location of the cursor based on mouse input
@@ -441,4 +570,32 @@ impl Plugin for CursorPlugin {
             // Other stuff
             .add_systems(Update, on_hover_set_cursor);
     }
-}
\ No newline at end of file
+}
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn snap_direction () {
+        let [a, b, c, d, e] = [0, 1, 2, 3, 4].map(Entity::from_raw);
+        let centres = [(a, Vec2::new(100.0, 0.0)), (b, Vec2::new(60.0, 60.0)), (c, Vec2::new(-100.0, 0.0)), (d, Vec2::new(0.0, -700.0)), (e, Vec2::new(1.0, 0.0))];
+
+        // Aligned nodes are preferred over closer off-axis ones
+        assert_eq!(snap_find_in_direction(&centres, Vec2::ZERO, Vec2::X, None), Some(a));
+        assert_eq!(snap_find_in_direction(&centres, Vec2::ZERO, Vec2::X, Some(a)), Some(b));
+        assert_eq!(snap_find_in_direction(&centres, Vec2::ZERO, Vec2::NEG_X, None), Some(c));
+        assert_eq!(snap_find_in_direction(&centres, Vec2::ZERO, Vec2::Y, None), Some(b));
+
+        // Nodes out of range, already reached or outside of the cone are skipped
+        assert_eq!(snap_find_in_direction(&centres, Vec2::ZERO, Vec2::NEG_Y, None), None);
+        assert_eq!(snap_find_in_direction(&centres, Vec2::new(100.0, 1.0), Vec2::X, None), None);
+
+        // Close off-axis node beats a far aligned one
+        let centres = [(a, Vec2::new(100.0, 0.0)), (b, Vec2::new(30.0, 20.0))];
+        assert_eq!(snap_find_in_direction(&centres, Vec2::ZERO, Vec2::X, None), Some(b));
+    }
+}
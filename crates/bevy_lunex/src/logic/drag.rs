@@ -0,0 +1,320 @@
+use crate::*;
+use bevy::{utils::HashMap, window::PrimaryWindow};
+use lunex_engine::NodeError;
+
+
+// #==============#
+// #=== EVENTS ===#
+
+/// This is an event you can listen to which is sent every time a [`UiDraggable`] node is dropped on a [`UiDropTarget`] that accepts it.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component of the [`UiTree`] the nodes belong to
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct UiDropEvent<T: Component = MainUi> {
+    /// The dragged entity
+    pub source: Entity,
+    /// The entity it was dropped on
+    pub target: Entity,
+    /// The [`UiLink`] path of the dragged entity before it was dropped
+    pub source_path: String,
+    /// The [`UiLink`] path of the entity it was dropped on
+    pub target_path: String,
+    /// The kind of the dragged entity
+    pub kind: String,
+    marker: PhantomData<T>,
+}
+
+
+// #=================#
+// #=== DRAGGABLE ===#
+
+/// How the ghost of a dragged [`UiDraggable`] is created.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum UiDragGhost {
+    /// No ghost is spawned
+    None,
+    /// Translucent copy of the [`Sprite`] of the dragged entity
+    #[default]
+    Sprite,
+    /// Custom function spawning the ghost for the dragged entity.
+    /// The spawned entity should have [`Pickable::IGNORE`] or it will catch the drop itself.
+    Custom(fn(&mut Commands, Entity) -> Entity),
+}
+
+/// Makes the entity draggable with the primary pointer button. While dragging, a ghost entity follows the [`Cursor2d`] or the pointer
+/// and the [`UiDropTarget`] entities that accept the [`UiDraggable::kind`] turn on their [`Droppable`] state.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Inventory/Slot1/Item"),
+///     UiLayout::window_full().pack::<Base>(),
+///     UiDraggable::new("item"),
+///     Pickable::default(),
+///     UiImage2dBundle::from(assets.load("sword.png")),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Default)]
+pub struct UiDraggable {
+    /// Kind of the dragged entity, checked by [`UiDropTarget`]
+    pub kind: String,
+    /// How the ghost is created
+    pub ghost: UiDragGhost,
+}
+impl UiDraggable {
+    /// Creates new struct with the given kind
+    pub fn new(kind: impl Borrow<str>) -> Self {
+        UiDraggable { kind: kind.borrow().to_string(), ghost: UiDragGhost::default() }
+    }
+    /// Replaces the ghost with a new value.
+    pub fn ghost(mut self, ghost: UiDragGhost) -> Self {
+        self.ghost = ghost;
+        self
+    }
+}
+
+/// Makes the entity accept dropped [`UiDraggable`] entities and send [`UiDropEvent`].
+/// If the entity has [`UiAnimator<Droppable>`], it is played while an accepted entity is dragged.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Inventory/Slot2"),
+///     UiLayout::window().pos(Rl((50.0, 0.0))).size(Rl((50.0, 100.0))).pack::<Base>(),
+///     UiDropTarget::new().accept("item").reparent(true),
+///     UiAnimator::<Droppable>::new(),
+///     UiColor::<Droppable>::new(Color::srgb(0.4, 1.0, 0.4)),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct UiDropTarget {
+    /// Kinds of [`UiDraggable`] this target accepts, accepts everything if empty
+    pub accepts: Vec<String>,
+    /// If the dropped node should be moved under the node of this target
+    pub reparent: bool,
+}
+impl UiDropTarget {
+    /// Creates new struct accepting everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds the kind to accepted kinds.
+    pub fn accept(mut self, kind: impl Borrow<str>) -> Self {
+        self.accepts.push(kind.borrow().to_string());
+        self
+    }
+    /// Replaces the reparent option with a new value.
+    /// The dropped node is then moved with [`ui_reparent_node`] to `target_path/name`.
+    pub fn reparent(mut self, reparent: bool) -> Self {
+        self.reparent = reparent;
+        self
+    }
+    /// Returns if the kind is accepted by this target
+    pub fn is_accepted(&self, kind: &str) -> bool {
+        self.accepts.is_empty() || self.accepts.iter().any(|accept| accept == kind)
+    }
+}
+
+/// Moves the node at the path together with its subnodes under the target path in the [`UiTree`] and returns its new path.
+/// The [`UiLink`] of linked entities has to be updated afterwards with [`UiLink::rebase`].
+/// ## 🛠️ Example
+/// ```
+/// let new_path = ui_reparent_node(&mut ui, "Inventory/Slot1/Item", "Inventory/Slot2")?;
+/// for mut link in &mut links {
+///     link.rebase("Inventory/Slot1/Item", &new_path);
+/// }
+/// ```
+pub fn ui_reparent_node<T:Component, N:Default + Component>(ui: &mut UiTree<T, N>, path: &str, target_path: &str) -> Result<String, NodeError> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let new_path = format!("{target_path}/{name}");
+    ui.move_node(path, new_path.as_str())?;
+    Ok(new_path)
+}
+
+
+// #==================#
+// #=== DRAG STATE ===#
+
+/// Drag in progress of a single pointer
+#[derive(Debug, Clone, PartialEq)]
+pub struct UiDrag {
+    /// The dragged entity
+    pub source: Entity,
+    /// The ghost following the pointer
+    pub ghost: Option<Entity>,
+    /// Kind of the dragged entity
+    pub kind: String,
+    /// Offset of the pointer from the centre of the dragged entity
+    offset: Vec2,
+}
+
+/// Resource with all drags currently in progress.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct UiDragState {
+    drags: HashMap<PointerId, UiDrag>,
+}
+impl UiDragState {
+    /// Returns the drag of the pointer if there is one
+    pub fn get(&self, pointer: &PointerId) -> Option<&UiDrag> {
+        self.drags.get(pointer)
+    }
+    /// Returns if anything is being dragged
+    pub fn is_dragging(&self) -> bool {
+        !self.drags.is_empty()
+    }
+}
+
+
+// #===============#
+// #=== SYSTEMS ===#
+
+/// Query of [`Cursor2d`] entities and the camera they are attached to.
+type CursorQuery<'w, 's> = Query<'w, 's, (&'static PointerId, &'static Cursor2d, Option<&'static Parent>)>;
+
+/// Returns the world position of the [`Cursor2d`] controlling the pointer, if there is one.
+fn cursor_world_position(pointer: &PointerId, cursors: &CursorQuery, transforms: &Query<&GlobalTransform>) -> Option<Vec2> {
+    let (_, cursor, parent) = cursors.iter().find(|(id, _, _)| *id == pointer)?;
+    let location = cursor.location.extend(0.0);
+    let position = parent.and_then(|parent| transforms.get(**parent).ok()).map_or(location, |transform| transform.transform_point(location));
+    Some(position.truncate())
+}
+
+fn ui_drag_start_system(
+    mut commands: Commands,
+    mut events: EventReader<Pointer<DragStart>>,
+    mut state: ResMut<UiDragState>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, &OrthographicProjection)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    cursors: CursorQuery,
+    transforms: Query<&GlobalTransform>,
+    query: Query<(&UiDraggable, &Dimension, &GlobalTransform, Has<Element>, Option<&Sprite>, Option<&Handle<Image>>)>,
+) {
+    for event in events.read() {
+        if event.button != PointerButton::Primary { continue }
+        let Ok((draggable, dimension, transform, is_element, sprite, image)) = query.get(event.target) else { continue };
+
+        let centre = transform.transform_point(node_rect(dimension, is_element).center().extend(0.0));
+        let pointer = cursor_world_position(&event.pointer_id, &cursors, &transforms)
+            .or_else(|| pointer_world_position(&event.pointer_location, &cameras, &primary_window).map(|(_, position)| position))
+            .unwrap_or(centre.truncate());
+
+        let ghost = match draggable.ghost {
+            UiDragGhost::None => None,
+            UiDragGhost::Sprite => sprite.map(|sprite| {
+                let (scale, rotation, _) = transform.to_scale_rotation_translation();
+                let sprite = Sprite {
+                    color: sprite.color.with_alpha(sprite.color.alpha() * 0.7),
+                    custom_size: Some(dimension.size),
+                    anchor: bevy::sprite::Anchor::Center,
+                    ..sprite.clone()
+                };
+                let mut ghost = commands.spawn((
+                    SpriteBundle {
+                        sprite,
+                        transform: Transform { translation: centre + Vec3::Z * 100.0, rotation, scale },
+                        ..default()
+                    },
+                    Pickable::IGNORE,
+                ));
+                if let Some(image) = image { ghost.insert(image.clone()); }
+                ghost.id()
+            }),
+            UiDragGhost::Custom(spawn) => Some(spawn(&mut commands, event.target)),
+        };
+
+        state.drags.insert(event.pointer_id, UiDrag { source: event.target, ghost, kind: draggable.kind.clone(), offset: pointer - centre.truncate() });
+    }
+}
+
+fn ui_drag_ghost_system(
+    state: Res<UiDragState>,
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, &OrthographicProjection)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    cursors: CursorQuery,
+    transforms: Query<&GlobalTransform>,
+    mut query: Query<&mut Transform>,
+) {
+    for (pointer, location) in &pointers {
+        let Some(drag) = state.drags.get(pointer) else { continue };
+        let Some(ghost) = drag.ghost else { continue };
+        // Follow the cursor if there is one, the pointer location is rounded to whole pixels
+        let position = cursor_world_position(pointer, &cursors, &transforms)
+            .or_else(|| pointer_world_position(location.location()?, &cameras, &primary_window).map(|(_, position)| position));
+        let Some(position) = position else { continue };
+        let Ok(mut transform) = query.get_mut(ghost) else { continue };
+
+        let position = position - drag.offset;
+        if transform.translation.truncate() != position {
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+        }
+    }
+}
+
+fn ui_drag_end_system(mut commands: Commands, mut events: EventReader<Pointer<DragEnd>>, mut state: ResMut<UiDragState>) {
+    for event in events.read() {
+        if event.button != PointerButton::Primary { continue }
+        let Some(drag) = state.drags.remove(&event.pointer_id) else { continue };
+        if let Some(ghost) = drag.ghost.and_then(|ghost| commands.get_entity(ghost)) { ghost.despawn_recursive(); }
+    }
+}
+
+fn ui_drop_highlight_system(state: Res<UiDragState>, mut query: Query<(Entity, &UiDropTarget, &mut UiAnimator<Droppable>)>) {
+    for (entity, target, mut animator) in &mut query {
+        let droppable = state.drags.values().any(|drag| drag.source != entity && target.is_accepted(&drag.kind));
+        if animator.is_forward() != droppable { animator.set_forward(droppable); }
+    }
+}
+
+/// This system sends [`UiDropEvent`] for every accepted drop and moves the dropped node if [`UiDropTarget::reparent`] is set.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn ui_drop_system<T:Component, N:Default + Component>(
+    mut events: EventReader<Pointer<Drop>>,
+    mut drops: EventWriter<UiDropEvent<T>>,
+    mut uis: Query<(&mut UiTree<T, N>, &Children)>,
+    sources: Query<&UiDraggable>,
+    targets: Query<&UiDropTarget>,
+    mut links: Query<&mut UiLink<T>>,
+) {
+    for event in events.read() {
+        if event.button != PointerButton::Primary || event.dropped == event.target { continue }
+        let (Ok(draggable), Ok(target)) = (sources.get(event.dropped), targets.get(event.target)) else { continue };
+        if !target.is_accepted(&draggable.kind) { continue }
+        let (Ok(source_link), Ok(target_link)) = (links.get(event.dropped), links.get(event.target)) else { continue };
+        let (source_path, target_path) = (source_link.path.clone(), target_link.path.clone());
+
+        if target.reparent {
+            if let Some((mut ui, children)) = uis.iter_mut().find(|(_, children)| children.contains(&event.dropped)) {
+                match ui_reparent_node(&mut ui, &source_path, &target_path) {
+                    Ok(new_path) => {
+                        let mut linked = links.iter_many_mut(children);
+                        while let Some(mut link) = linked.fetch_next() { link.rebase(&source_path, &new_path); }
+                    },
+                    Err(error) => warn!("Unable to move '{source_path}' under '{target_path}': {error}"),
+                }
+            }
+        }
+
+        drops.send(UiDropEvent { source: event.dropped, target: event.target, source_path, target_path, kind: draggable.kind.clone(), marker: PhantomData });
+    }
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// Plugin adding [`UiDraggable`] and [`UiDropTarget`] logic
+pub struct DragPlugin;
+impl Plugin for DragPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<UiDragState>()
+            .add_systems(Update, (
+                ui_drag_start_system,
+                ui_drag_ghost_system,
+                ui_drop_highlight_system,
+            ).chain().in_set(UiSystems::Modify).before(UiSystems::Send))
+            .add_systems(Update, ui_drag_end_system.in_set(UiSystems::Modify).before(UiSystems::Send).after(ui_drop_highlight_system));
+    }
+}
@@ -0,0 +1,274 @@
+use crate::*;
+use std::f32::consts::PI;
+
+
+// #==============#
+// #=== EASING ===#
+
+/// Easing curve remapping linear progress from `0.0` to `1.0`.
+/// Use it in [`UiAnimator`] or [`UiLayoutController`] to smooth the transition.
+/// ## 🛠️ Example
+/// ```
+/// UiAnimator::<Hover>::new().forward_ease(UiEase::OutBack).backward_ease(UiEase::InOutCubic)
+/// ```
+#[derive(Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum UiEase {
+    /// No easing
+    #[default]
+    Linear,
+    InQuad,
+    OutQuad,
+    InOutQuad,
+    InCubic,
+    OutCubic,
+    InOutCubic,
+    InExpo,
+    OutExpo,
+    InOutExpo,
+    /// Slightly pulls back before moving
+    InBack,
+    /// Slightly overshoots the target
+    OutBack,
+    InOutBack,
+    /// Oscillates before moving
+    InElastic,
+    /// Oscillates around the target
+    OutElastic,
+    InOutElastic,
+    InBounce,
+    /// Bounces off the target
+    OutBounce,
+    InOutBounce,
+    /// CSS-like cubic bezier curve defined by two control points `(x1, y1, x2, y2)`.
+    /// The `x` coordinates should be in range `0.0` to `1.0`.
+    CubicBezier(f32, f32, f32, f32),
+    /// Your own curve, this is what `UiLayoutController.method` used to be.
+    /// The function can't be reflected, so it is replaced with [`UiEase::Linear`] curve when loaded from scene files.
+    Custom(#[reflect(ignore, default = "linear")] fn(f32) -> f32),
+}
+impl PartialEq for UiEase {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (UiEase::CubicBezier(a1, b1, c1, d1), UiEase::CubicBezier(a2, b2, c2, d2)) => (a1, b1, c1, d1) == (a2, b2, c2, d2),
+            (UiEase::Custom(f1), UiEase::Custom(f2)) => *f1 as usize == *f2 as usize,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+impl UiEase {
+    /// Remaps the linear progress. Except for [`UiEase::Linear`] and [`UiEase::Custom`], the input is clamped to range `0.0` to `1.0`.
+    pub fn ease(&self, t: f32) -> f32 {
+        match *self {
+            UiEase::Linear => return t,
+            UiEase::Custom(curve) => return curve(t),
+            _ => {},
+        }
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            UiEase::Linear | UiEase::Custom(_) => t,
+            UiEase::InQuad => t * t,
+            UiEase::OutQuad => 1.0 - (1.0 - t).powi(2),
+            UiEase::InOutQuad => if t < 0.5 { 2.0 * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(2) / 2.0 },
+            UiEase::InCubic => t * t * t,
+            UiEase::OutCubic => 1.0 - (1.0 - t).powi(3),
+            UiEase::InOutCubic => if t < 0.5 { 4.0 * t * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0 },
+            UiEase::InExpo => if t == 0.0 { 0.0 } else { 2f32.powf(10.0 * t - 10.0) },
+            UiEase::OutExpo => if t == 1.0 { 1.0 } else { 1.0 - 2f32.powf(-10.0 * t) },
+            UiEase::InOutExpo => match t {
+                0.0 => 0.0,
+                1.0 => 1.0,
+                _ if t < 0.5 => 2f32.powf(20.0 * t - 10.0) / 2.0,
+                _ => (2.0 - 2f32.powf(-20.0 * t + 10.0)) / 2.0,
+            },
+            UiEase::InBack => BACK_C3 * t * t * t - BACK_C1 * t * t,
+            UiEase::OutBack => 1.0 + BACK_C3 * (t - 1.0).powi(3) + BACK_C1 * (t - 1.0).powi(2),
+            UiEase::InOutBack => {
+                let c2 = BACK_C1 * 1.525;
+                if t < 0.5 {
+                    (2.0 * t).powi(2) * ((c2 + 1.0) * 2.0 * t - c2) / 2.0
+                } else {
+                    ((2.0 * t - 2.0).powi(2) * ((c2 + 1.0) * (t * 2.0 - 2.0) + c2) + 2.0) / 2.0
+                }
+            },
+            UiEase::InElastic => match t {
+                0.0 | 1.0 => t,
+                _ => -2f32.powf(10.0 * t - 10.0) * ((t * 10.0 - 10.75) * (2.0 * PI / 3.0)).sin(),
+            },
+            UiEase::OutElastic => match t {
+                0.0 | 1.0 => t,
+                _ => 2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0,
+            },
+            UiEase::InOutElastic => match t {
+                0.0 | 1.0 => t,
+                _ if t < 0.5 => -(2f32.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * (2.0 * PI / 4.5)).sin()) / 2.0,
+                _ => (2f32.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * (2.0 * PI / 4.5)).sin()) / 2.0 + 1.0,
+            },
+            UiEase::InBounce => 1.0 - bounce_out(1.0 - t),
+            UiEase::OutBounce => bounce_out(t),
+            UiEase::InOutBounce => if t < 0.5 { (1.0 - bounce_out(1.0 - 2.0 * t)) / 2.0 } else { (1.0 + bounce_out(2.0 * t - 1.0)) / 2.0 },
+            UiEase::CubicBezier(x1, y1, x2, y2) => {
+                let s = bezier_solve(t, x1, x2);
+                bezier(s, y1, y2)
+            },
+        }
+    }
+}
+
+fn linear() -> fn(f32) -> f32 {
+    |t| t
+}
+
+const BACK_C1: f32 = 1.70158;
+const BACK_C3: f32 = BACK_C1 + 1.0;
+
+fn bounce_out(t: f32) -> f32 {
+    const N1: f32 = 7.5625;
+    const D1: f32 = 2.75;
+    if t < 1.0 / D1 {
+        N1 * t * t
+    } else if t < 2.0 / D1 {
+        let t = t - 1.5 / D1;
+        N1 * t * t + 0.75
+    } else if t < 2.5 / D1 {
+        let t = t - 2.25 / D1;
+        N1 * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D1;
+        N1 * t * t + 0.984375
+    }
+}
+
+/// One axis of cubic bezier starting at `0.0` and ending at `1.0`
+fn bezier(s: f32, p1: f32, p2: f32) -> f32 {
+    let r = 1.0 - s;
+    3.0 * r * r * s * p1 + 3.0 * r * s * s * p2 + s * s * s
+}
+
+/// Finds the bezier parameter for the given `x` coordinate
+fn bezier_solve(x: f32, x1: f32, x2: f32) -> f32 {
+    // Newton's method is fast but can fail on flat slopes
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier(s, x1, x2) - x;
+        if error.abs() < 1e-6 { return s; }
+        let r = 1.0 - s;
+        let slope = 3.0 * r * r * x1 + 6.0 * r * s * (x2 - x1) + 3.0 * s * s * (1.0 - x2);
+        if slope.abs() < 1e-6 { break; }
+        s -= error / slope;
+    }
+
+    // Fallback to bisection
+    let (mut low, mut high) = (0.0, 1.0);
+    s = x;
+    for _ in 0..32 {
+        let value = bezier(s, x1, x2);
+        if (value - x).abs() < 1e-6 { break; }
+        if value < x { low = s } else { high = s }
+        s = (low + high) / 2.0;
+    }
+    s
+}
+
+
+// #==============#
+// #=== SPRING ===#
+
+/// Damped spring used by [`UiAnimator`] instead of constant speed. The transition can overshoot
+/// the target before it settles, so layouts tweened with a spring will wobble.
+/// ## 🛠️ Example
+/// ```
+/// UiAnimator::<Hover>::new().spring(UiSpring::new(300.0, 15.0))
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Default)]
+pub struct UiSpring {
+    /// How strongly the spring pulls towards the target
+    pub stiffness: f32,
+    /// How quickly the oscillation fades out
+    pub damping: f32,
+}
+impl UiSpring {
+    /// Creates new struct
+    pub fn new(stiffness: f32, damping: f32) -> Self {
+        UiSpring { stiffness, damping }
+    }
+    /// Advances the spring by `delta` seconds. Returns the new position and velocity.
+    pub fn step(&self, position: f32, velocity: f32, target: f32, delta: f32) -> (f32, f32) {
+        // Substeps keep stiff springs stable at low framerate
+        let steps = (delta * 240.0).ceil().max(1.0);
+        let dt = delta / steps;
+        let (mut position, mut velocity) = (position, velocity);
+        for _ in 0..steps as usize {
+            let acceleration = -self.stiffness * (position - target) - self.damping * velocity;
+            velocity += acceleration * dt;
+            position += velocity * dt;
+        }
+
+        // Settle at rest
+        if (position - target).abs() < 1e-3 && velocity.abs() < 1e-3 {
+            return (target, 0.0);
+        }
+        (position, velocity)
+    }
+}
+impl Default for UiSpring {
+    fn default() -> Self {
+        UiSpring::new(170.0, 26.0)
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::{UiEase, UiSpring};
+
+    #[test]
+    fn ease_endpoints () {
+        let curves = [
+            UiEase::Linear, UiEase::InQuad, UiEase::OutQuad, UiEase::InOutQuad,
+            UiEase::InCubic, UiEase::OutCubic, UiEase::InOutCubic,
+            UiEase::InExpo, UiEase::OutExpo, UiEase::InOutExpo,
+            UiEase::InBack, UiEase::OutBack, UiEase::InOutBack,
+            UiEase::InElastic, UiEase::OutElastic, UiEase::InOutElastic,
+            UiEase::InBounce, UiEase::OutBounce, UiEase::InOutBounce,
+            UiEase::CubicBezier(0.25, 0.1, 0.25, 1.0),
+            UiEase::CubicBezier(0.68, -0.6, 0.32, 1.6),
+            UiEase::CubicBezier(0.0, 0.0, 1.0, 1.0),
+            UiEase::Custom(|t| t * t),
+        ];
+        for curve in curves {
+            assert!(curve.ease(0.0).abs() < 1e-4, "{curve:?} starts at {}", curve.ease(0.0));
+            assert!((curve.ease(1.0) - 1.0).abs() < 1e-4, "{curve:?} ends at {}", curve.ease(1.0));
+        }
+    }
+
+    #[test]
+    fn ease_custom () {
+        fn half(t: f32) -> f32 { t / 2.0 }
+        assert_eq!(UiEase::Custom(half).ease(0.5), 0.25);
+        // Custom curves are not clamped
+        assert_eq!(UiEase::Custom(half).ease(3.0), 1.5);
+        assert_eq!(UiEase::Custom(half), UiEase::Custom(half));
+        assert_ne!(UiEase::Custom(half), UiEase::Linear);
+        assert_ne!(UiEase::CubicBezier(0.0, 0.0, 1.0, 1.0), UiEase::CubicBezier(0.0, 0.0, 1.0, 0.5));
+    }
+
+    #[test]
+    fn spring_converges () {
+        for spring in [UiSpring::default(), UiSpring::new(300.0, 15.0), UiSpring::new(50.0, 2.0)] {
+            let (mut position, mut velocity) = (0.0, 0.0);
+            for _ in 0..600 {
+                (position, velocity) = spring.step(position, velocity, 1.0, 1.0 / 60.0);
+            }
+            assert_eq!((position, velocity), (1.0, 0.0), "{spring:?} did not settle");
+        }
+
+        // Low framerate does not explode
+        let (position, velocity) = UiSpring::new(1000.0, 10.0).step(0.0, 0.0, 1.0, 0.5);
+        assert!(position.is_finite() && velocity.is_finite());
+    }
+}
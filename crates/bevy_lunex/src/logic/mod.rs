@@ -7,12 +7,36 @@ pub use core::*;
 pub mod cursor;
 pub use cursor::*;
 
+pub mod drag;
+pub use drag::*;
+
+pub mod easing;
+pub use easing::*;
+
+pub mod modal;
+pub use modal::*;
+
+pub mod navigation;
+pub use navigation::*;
+
+pub mod popover;
+pub use popover::*;
+
+pub mod scroll;
+pub use scroll::*;
+
 pub mod states;
 pub use states::*;
 
 pub mod style;
 pub use style::*;
 
+pub mod text_input;
+pub use text_input::*;
+
+pub mod timeline;
+pub use timeline::*;
+
 
 // #====================#
 // #=== LOGIC PLUGIN ===#
@@ -29,7 +53,14 @@ impl Plugin for UiLogicPlugin {
             .add_plugins(CorePlugin)
             .add_plugins(CursorPlugin)
             .add_plugins(DefaultStatesPlugin)
-            .add_plugins(StylePlugin);
+            .add_plugins(DragPlugin)
+            .add_plugins(FocusPlugin)
+            .add_plugins(ModalPlugin)
+            .add_plugins(PopoverPlugin)
+            .add_plugins(ScrollPlugin)
+            .add_plugins(StylePlugin)
+            .add_plugins(TextInputPlugin)
+            .add_plugins(TimelinePlugin);
     }
 }
 
@@ -49,6 +80,17 @@ impl <T:Component, N:Default + Component> Plugin for UiStateLogicPlugin<T, N> {
             .add_plugins(StatePlugin::<T, N, Clicked>::new())
             .add_plugins(StatePlugin::<T, N, Selected>::new())
             .add_plugins(StatePlugin::<T, N, Intro>::new())
-            .add_plugins(StatePlugin::<T, N, Outro>::new());
+            .add_plugins(StatePlugin::<T, N, Outro>::new())
+            .add_plugins(StatePlugin::<T, N, Droppable>::new())
+
+            .add_event::<UiDropEvent<T>>()
+            .add_systems(Update, ui_drop_system::<T, N>.in_set(UiSystems::Modify).before(UiSystems::Send))
+            .add_systems(Update, (
+                ui_popover_dismiss_system::<T>.before(ui_context_menu_system),
+                ui_popover_position_system::<T, N>.after(ui_tooltip_system).after(ui_context_menu_system),
+            ).in_set(UiSystems::Modify).before(UiSystems::Send))
+
+            .add_systems(Update, send_timeline_to_node::<T, N>.in_set(UiSystems::Send).before(send_layout_control_to_node::<T, N>))
+            .add_systems(Update, fetch_focus_from_node::<T, N>.in_set(UiSystems::Fetch).after(UiSystems::Compute));
     }
 }
\ No newline at end of file
@@ -0,0 +1,218 @@
+use crate::*;
+
+
+// #=============#
+// #=== MODAL ===#
+
+/// Makes the node a modal. While it is visible, nodes below it in the same [`UiTree`] can't be picked,
+/// so give it a higher [`UiLayer`] than the nodes it covers. Modals are opened and closed with [`UiModalStack`].
+///
+/// The modal on top of the stack activates its [`UiFocus`] group and can be closed with Escape or the gamepad B button.
+/// Opening the modal plays its [`UiAnimator<Intro>`] and closing it plays its [`UiAnimator<Outro>`] before it is hidden, if present.
+/// ## 🛠️ Example
+/// ```
+/// let pause = ui.spawn((
+///     UiLink::<MainUi>::path("Pause"),
+///     UiLayout::window().pos(Rl(25.0)).size(Rl(50.0)).pack::<Base>(),
+///     UiLayer::Modal,
+///     UiModal::new().group(1).dim(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+///     UiImage2dBundle::from(assets.load("panel.png")),
+/// )).id();
+///
+/// modals.push(pause);
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct UiModal {
+    /// Focus group activated while the modal is on top
+    pub group: usize,
+    /// Color covering the whole tree below the modal while it is open
+    pub dim: Option<Color>,
+    /// If Escape or the gamepad B button closes the modal when it is on top
+    pub closable: bool,
+    /// The spawned dim entity
+    dim_entity: Option<Entity>,
+}
+impl UiModal {
+    /// Creates new closable modal
+    pub fn new() -> Self {
+        UiModal { group: 0, dim: None, closable: true, dim_entity: None }
+    }
+    /// Replaces the focus group with a new value.
+    pub fn group(mut self, group: usize) -> Self {
+        self.group = group;
+        self
+    }
+    /// Replaces the dim color with a new value.
+    pub fn dim(mut self, color: Color) -> Self {
+        self.dim = Some(color);
+        self
+    }
+    /// Replaces the closable option with a new value.
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+}
+impl Default for UiModal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resource with the open [`UiModal`]s. The last one is on top.
+/// ## 🛠️ Example
+/// ```
+/// fn open_settings(mut modals: ResMut<UiModalStack>, settings: Query<Entity, With<SettingsModal>>) {
+///     modals.push(settings.single());
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct UiModalStack {
+    /// The open modals
+    stack: Vec<Entity>,
+    /// Focus group that was active before the first modal opened
+    group: Option<usize>,
+}
+impl UiModalStack {
+    /// Opens the modal on top of the others. If it is already open, it is moved to the top.
+    pub fn push(&mut self, modal: Entity) {
+        self.stack.retain(|entity| *entity != modal);
+        self.stack.push(modal);
+    }
+    /// Closes the modal on top and returns it.
+    pub fn pop(&mut self) -> Option<Entity> {
+        self.stack.pop()
+    }
+    /// Closes the modal. Returns `true` if it was open.
+    pub fn remove(&mut self, modal: Entity) -> bool {
+        let len = self.stack.len();
+        self.stack.retain(|entity| *entity != modal);
+        self.stack.len() != len
+    }
+    /// Returns the modal on top.
+    pub fn get_top(&self) -> Option<Entity> {
+        self.stack.last().copied()
+    }
+    /// Checks if the modal is open.
+    pub fn contains(&self, modal: Entity) -> bool {
+        self.stack.contains(&modal)
+    }
+    /// Checks if any modal is open.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+}
+
+
+// #===============#
+// #=== SYSTEMS ===#
+
+pub(crate) fn ui_modal_close_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    focus: Res<UiFocus>,
+    inputs: Query<(), With<UiTextInput>>,
+    modals: Query<&UiModal>,
+    mut stack: ResMut<UiModalStack>,
+) {
+    // Escape in a text input only removes the focus
+    let editing = focus.get().is_some_and(|entity| inputs.contains(entity));
+    let escape = keys.just_pressed(KeyCode::Escape) && !editing;
+    let back = gamepads.iter().any(|gamepad| buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::East)));
+    if !escape && !back { return; }
+
+    if stack.get_top().and_then(|top| modals.get(top).ok()).is_some_and(|modal| modal.closable) {
+        stack.pop();
+    }
+}
+
+fn ui_modal_system(
+    mut stack: ResMut<UiModalStack>,
+    mut focus: ResMut<UiFocus>,
+    mut query: Query<(Entity, Ref<UiModal>, &mut Visibility, Option<&mut UiAnimator<Intro>>, Option<&mut UiAnimator<Outro>>)>,
+) {
+    // Despawned modals are closed
+    if stack.stack.iter().any(|entity| !query.contains(*entity)) {
+        stack.stack.retain(|entity| query.contains(*entity));
+    }
+
+    // The modal on top takes over the focus until the stack is empty
+    let group = match stack.get_top().and_then(|top| query.get(top).ok()) {
+        Some((_, modal, ..)) => {
+            if stack.group.is_none() { stack.group = Some(focus.get_group()); }
+            Some(modal.group)
+        },
+        None => stack.group.take(),
+    };
+    if let Some(group) = group.filter(|group| *group != focus.get_group()) { focus.set_group(group); }
+
+    for (entity, modal, mut visibility, intro, outro) in &mut query {
+        let open = stack.contains(entity);
+
+        // Closed modals start hidden
+        if modal.is_added() && !open {
+            if *visibility != Visibility::Hidden { *visibility = Visibility::Hidden; }
+            continue;
+        }
+        animate_visibility(open, visibility, intro, outro);
+    }
+}
+
+fn ui_modal_dim_system(
+    mut commands: Commands,
+    window: Query<&bevy::window::Window, With<bevy::window::PrimaryWindow>>,
+    uis: Query<(&Dimension, Option<&SourceFromCamera>)>,
+    mut query: Query<(Entity, &mut UiModal, &Parent, &Transform, Option<&UiAnimator<Intro>>, Option<&UiAnimator<Outro>>)>,
+    mut dims: Query<(&mut Sprite, &mut Transform), Without<UiModal>>,
+) {
+    let scale = if let Ok(window) = window.get_single() { window.resolution.scale_factor() } else { 1.0 };
+    for (entity, mut modal, parent, transform, intro, outro) in &mut query {
+        let Some(color) = modal.dim else { continue };
+        let Ok((dimension, is_camera_sourced)) = uis.get(parent.get()) else { continue };
+        let scale = if is_camera_sourced.is_none() { 1.0 } else { scale };
+
+        // The dim covers the whole tree right below the modal and fades with it
+        let fade = (1.0 - intro.map_or(0.0, |intro| intro.get_value())) * (1.0 - outro.map_or(0.0, |outro| outro.get_value()));
+        let color = color.with_alpha(color.alpha() * fade);
+        let size = dimension.size / scale;
+
+        // The dim is placed at the top-left corner of the tree, right below the modal
+        let tree_space = Transform::from_xyz(0.0, 0.0, transform.translation.z - 0.5);
+        let local = Transform::from_matrix(transform.compute_matrix().inverse() * tree_space.compute_matrix());
+
+        match modal.dim_entity.and_then(|dim| dims.get_mut(dim).ok()) {
+            Some((mut sprite, mut dim_transform)) => {
+                if sprite.color != color { sprite.color = color; }
+                if sprite.custom_size != Some(size) { sprite.custom_size = Some(size); }
+                if *dim_transform != local { *dim_transform = local; }
+            },
+            None => {
+                let dim = commands.spawn(SpriteBundle {
+                    sprite: Sprite { color, custom_size: Some(size), anchor: bevy::sprite::Anchor::TopLeft, ..default() },
+                    transform: local,
+                    ..default()
+                }).set_parent(entity).id();
+                modal.dim_entity = Some(dim);
+            },
+        }
+    }
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// Plugin adding [`UiModal`] logic
+pub struct ModalPlugin;
+impl Plugin for ModalPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<UiModalStack>()
+            .add_systems(Update, (
+                ui_modal_close_system.before(ui_text_input_keyboard_system),
+                ui_modal_system,
+                ui_modal_dim_system,
+            ).chain().in_set(UiSystems::Modify).before(UiSystems::Send));
+    }
+}
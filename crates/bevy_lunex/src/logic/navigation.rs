@@ -0,0 +1,445 @@
+use crate::*;
+use lunex_engine::NodeDataTrait;
+use bevy::utils::HashMap;
+
+
+// #==============#
+// #=== EVENTS ===#
+
+/// Navigation input moving the [`UiFocus`]. Keyboard and gamepad send it automatically,
+/// but you can also send it yourself to support custom bindings.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum UiNavigationEvent {
+    /// Focus the nearest node above
+    Up,
+    /// Focus the nearest node below
+    Down,
+    /// Focus the nearest node on the left
+    Left,
+    /// Focus the nearest node on the right
+    Right,
+    /// Focus the next node in tab order
+    Next,
+    /// Focus the previous node in tab order
+    Previous,
+    /// Send [`UiClickEvent`] for the focused node
+    Confirm,
+}
+
+/// This is an event you can listen to which broadcasts the entity that gained or lost focus.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiFocusEvent {
+    /// The targetted entity
+    pub target: Entity,
+    /// If the entity gained the focus
+    pub focused: bool,
+}
+
+
+// #=============#
+// #=== FOCUS ===#
+
+/// Which [`UiAnimator`] is driven by [`UiFocusable`] when it gains focus
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum UiFocusDrive {
+    /// Drives [`UiAnimator<Hover>`]
+    #[default]
+    Hover,
+    /// Drives [`UiAnimator<Selected>`]
+    Selected,
+}
+
+/// Marks the node as focusable by keyboard and gamepad navigation.
+/// The focused node drives its [`UiAnimator<Hover>`] or [`UiAnimator<Selected>`] and sends [`UiClickEvent`] on confirm.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Menu/Play"),
+///     UiLayout::window().pos(Rl((10.0, 20.0))).size(Rl((30.0, 10.0))).pack::<Base>(),
+///     UiAnimator::<Hover>::new(),
+///     UiFocusable::new().order(0),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+pub struct UiFocusable {
+    /// Navigation only moves between nodes in the active [`UiFocus`] group
+    pub group: usize,
+    /// Position in tab order. Nodes with the same order are sorted by their position from top to bottom.
+    pub order: i32,
+    /// Which state animator the focus drives
+    pub drive: UiFocusDrive,
+    /// Directional navigation on these axes does not move the focus away, so widgets can use it to change their value
+    pub capture: BVec2,
+    /// Computed bounding rectangle of the node in its [`UiTree`], including the rotation
+    pub(crate) rect: Rect,
+    /// The [`UiTree`] entity the node belongs to
+    pub(crate) tree: Option<Entity>,
+}
+impl UiFocusable {
+    /// Creates new struct
+    pub fn new() -> Self {
+        UiFocusable {
+            group: 0,
+            order: 0,
+            drive: UiFocusDrive::Hover,
+            capture: BVec2::FALSE,
+            rect: Rect::default(),
+            tree: None,
+        }
+    }
+    /// Replaces the focus group with a new value.
+    pub fn group(mut self, group: usize) -> Self {
+        self.group = group;
+        self
+    }
+    /// Replaces the tab order with a new value.
+    pub fn order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+    /// Replaces the driven state with a new value.
+    pub fn drive(mut self, drive: UiFocusDrive) -> Self {
+        self.drive = drive;
+        self
+    }
+    /// Replaces the captured navigation axes with a new value.
+    pub fn capture(mut self, horizontal: bool, vertical: bool) -> Self {
+        self.capture = BVec2::new(horizontal, vertical);
+        self
+    }
+    /// Returns the computed bounding rectangle of the node in its [`UiTree`].
+    pub fn get_rect(&self) -> Rect {
+        self.rect
+    }
+}
+impl Default for UiFocusable {
+    fn default() -> Self {
+        UiFocusable::new()
+    }
+}
+
+/// Resource holding the focused [`UiFocusable`] node and the active focus group.
+/// ## 🛠️ Example
+/// ```
+/// fn open_settings(mut focus: ResMut<UiFocus>) {
+///     focus.set_group(1);
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct UiFocus {
+    /// The focused node
+    focused: Option<Entity>,
+    /// The active focus group
+    group: usize,
+}
+impl UiFocus {
+    /// Returns the focused node.
+    pub fn get(&self) -> Option<Entity> {
+        self.focused
+    }
+    /// Moves the focus to the node.
+    pub fn focus(&mut self, entity: Entity) {
+        self.focused = Some(entity);
+    }
+    /// Removes the focus from all nodes.
+    pub fn clear(&mut self) {
+        self.focused = None;
+    }
+    /// Returns the active focus group.
+    pub fn get_group(&self) -> usize {
+        self.group
+    }
+    /// Activates the focus group. The focus is removed if it is in a different group.
+    pub fn set_group(&mut self, group: usize) {
+        self.group = group;
+    }
+}
+
+/// Returns the best candidate in the direction. Candidates further away from the direction axis are penalized.
+fn find_in_direction(from: Rect, direction: Vec2, candidates: impl Iterator<Item = (Entity, Rect)>) -> Option<Entity> {
+    let perpendicular = direction.perp().abs();
+    candidates
+        .filter_map(|(entity, rect)| {
+            let distance = (rect.center() - from.center()).dot(direction);
+            if distance <= 0.0 { return None; }
+
+            // Nodes overlapping on the perpendicular axis are aligned
+            let gap = (from.min - rect.max).max(rect.min - from.max).max(Vec2::ZERO).dot(perpendicular);
+            Some((entity, distance + gap * 2.0))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+/// Returns the candidate after or before the current one in tab order, wrapping around at the ends.
+/// Tab order is explicit, then from top to bottom and left to right. Without current it returns the first candidate.
+fn find_in_tab_order<'a>(current: Option<Entity>, next: bool, candidates: impl Iterator<Item = (Entity, &'a UiFocusable)>) -> Option<Entity> {
+    let mut ordered: Vec<_> = candidates.collect();
+    ordered.sort_by(|a, b| a.1.order.cmp(&b.1.order).then(a.1.rect.min.y.total_cmp(&b.1.rect.min.y)).then(a.1.rect.min.x.total_cmp(&b.1.rect.min.x)));
+    let n = ordered.len();
+    let index = match (current.and_then(|entity| ordered.iter().position(|(candidate, _)| *candidate == entity)), next) {
+        (None, _) => 0,
+        (Some(i), true) => (i + 1) % n,
+        (Some(i), false) => (i + n - 1) % n,
+    };
+    ordered.get(index).map(|(entity, _)| *entity)
+}
+
+
+// #===============#
+// #=== SYSTEMS ===#
+
+/// This system fetches computed [`UiTree`] data and overwrites querried [`UiFocusable`] rectangle.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn fetch_focus_from_node<T:Component, N:Default + Component>(
+    uis: Query<(Entity, &UiTree<T, N>, &Children), Changed<UiTree<T, N>>>,
+    mut query: Query<(&UiLink<T>, &mut UiFocusable)>,
+) {
+    for (tree, ui, children) in &uis {
+        for child in children {
+            // If child matches
+            if let Ok((link, mut focusable)) = query.get_mut(*child) {
+                // If node exists
+                if let Ok(Some(container)) = ui.borrow_data(link.path.clone()) {
+                    // Bounding box of the rotated rectangle
+                    let (pos, size) = (container.rectangle.pos, container.rectangle.size);
+                    let rect = [Vec2::ZERO, Vec2::X, Vec2::Y, Vec2::ONE].iter()
+                        .map(|corner| container.transform.transform_point3(pos + (size * *corner).extend(0.0)).truncate())
+                        .fold(Rect { min: Vec2::INFINITY, max: Vec2::NEG_INFINITY }, |rect, point| rect.union_point(point));
+                    if focusable.rect != rect || focusable.tree != Some(tree) {
+                        let focusable = focusable.bypass_change_detection();
+                        focusable.rect = rect;
+                        focusable.tree = Some(tree);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// While [`UiTextInput`] is focused, horizontal arrows and space are left for editing.
+fn ui_navigation_keyboard_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<UiFocus>,
+    inputs: Query<(), With<UiTextInput>>,
+    mut events: EventWriter<UiNavigationEvent>,
+) {
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let editing = focus.focused.is_some_and(|entity| inputs.contains(entity));
+    for key in keys.get_just_pressed() {
+        events.send(match key {
+            KeyCode::ArrowUp => UiNavigationEvent::Up,
+            KeyCode::ArrowDown => UiNavigationEvent::Down,
+            KeyCode::ArrowLeft if !editing => UiNavigationEvent::Left,
+            KeyCode::ArrowRight if !editing => UiNavigationEvent::Right,
+            KeyCode::Tab if shift => UiNavigationEvent::Previous,
+            KeyCode::Tab => UiNavigationEvent::Next,
+            KeyCode::Space if !editing => UiNavigationEvent::Confirm,
+            KeyCode::Enter | KeyCode::NumpadEnter => UiNavigationEvent::Confirm,
+            _ => continue,
+        });
+    }
+}
+
+/// Gamepads controlling [`GamepadCursor`] are skipped, because they click with the cursor instead.
+fn ui_navigation_gamepad_system(
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    axis: Res<Axis<GamepadAxis>>,
+    cursors: Query<&GamepadCursor>,
+    mut stick: Local<HashMap<usize, (UiNavigationEvent, f32)>>,
+    mut events: EventWriter<UiNavigationEvent>,
+) {
+    for gamepad in gamepads.iter() {
+        if cursors.iter().any(|cursor| cursor.id == gamepad.id) { continue }
+
+        for (button, event) in [
+            (GamepadButtonType::DPadUp, UiNavigationEvent::Up),
+            (GamepadButtonType::DPadDown, UiNavigationEvent::Down),
+            (GamepadButtonType::DPadLeft, UiNavigationEvent::Left),
+            (GamepadButtonType::DPadRight, UiNavigationEvent::Right),
+            (GamepadButtonType::RightTrigger, UiNavigationEvent::Next),
+            (GamepadButtonType::LeftTrigger, UiNavigationEvent::Previous),
+            (GamepadButtonType::South, UiNavigationEvent::Confirm),
+        ] {
+            if buttons.just_pressed(GamepadButton::new(gamepad, button)) { events.send(event); }
+        }
+
+        // Tilting the stick navigates once, holding it repeats
+        let x = axis.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX)).unwrap_or(0.0);
+        let y = axis.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY)).unwrap_or(0.0);
+        let direction = if x.abs().max(y.abs()) < 0.5 { None } else if x.abs() > y.abs() {
+            Some(if x > 0.0 { UiNavigationEvent::Right } else { UiNavigationEvent::Left })
+        } else {
+            Some(if y > 0.0 { UiNavigationEvent::Up } else { UiNavigationEvent::Down })
+        };
+        match (direction, stick.get_mut(&gamepad.id)) {
+            (None, _) => { stick.remove(&gamepad.id); },
+            (Some(direction), Some((last, timer))) if *last == direction => {
+                *timer -= time.delta_seconds();
+                if *timer <= 0.0 {
+                    *timer = 0.15;
+                    events.send(direction);
+                }
+            },
+            (Some(direction), _) => {
+                stick.insert(gamepad.id, (direction, 0.4));
+                events.send(direction);
+            },
+        }
+    }
+}
+
+fn ui_navigation_system(
+    mut events: EventReader<UiNavigationEvent>,
+    mut focus: ResMut<UiFocus>,
+    query: Query<(Entity, &UiFocusable, &InheritedVisibility)>,
+    emitters: Query<&UiClickEmitter>,
+    mut click: EventWriter<UiClickEvent>,
+) {
+    // Only visible nodes of the active group can be focused
+    let group = focus.group;
+    let candidates = || query.iter().filter(move |(_, focusable, visibility)| focusable.group == group && visibility.get());
+
+    // Drop the focus if it became unreachable
+    if let Some(focused) = focus.focused {
+        if !candidates().any(|(entity, ..)| entity == focused) { focus.focused = None; }
+    }
+
+    for event in events.read() {
+        let current = focus.focused.and_then(|entity| query.get(entity).ok());
+        let tab_order = |current: Option<Entity>, next: bool| find_in_tab_order(current, next, candidates().map(|(entity, focusable, _)| (entity, focusable)));
+
+        let direction = match event {
+            UiNavigationEvent::Up => Vec2::NEG_Y,
+            UiNavigationEvent::Down => Vec2::Y,
+            UiNavigationEvent::Left => Vec2::NEG_X,
+            UiNavigationEvent::Right => Vec2::X,
+            UiNavigationEvent::Next | UiNavigationEvent::Previous => {
+                if let Some(next) = tab_order(current.map(|(entity, ..)| entity), *event == UiNavigationEvent::Next) { focus.focused = Some(next); }
+                continue;
+            },
+            UiNavigationEvent::Confirm => {
+                if let Some((entity, ..)) = current {
+                    let target = emitters.get(entity).ok().and_then(|emitter| emitter.target).unwrap_or(entity);
+                    click.send(UiClickEvent { target });
+                }
+                continue;
+            },
+        };
+
+        // Nothing is focused yet, so start at the beginning
+        let Some((entity, focusable, _)) = current else {
+            if let Some(first) = tab_order(None, true) { focus.focused = Some(first); }
+            continue;
+        };
+        if (direction.x != 0.0 && focusable.capture.x) || (direction.y != 0.0 && focusable.capture.y) { continue; }
+        // Rectangles are only comparable inside of the same tree
+        let others = candidates()
+            .filter(|(candidate, other, _)| *candidate != entity && other.tree == focusable.tree)
+            .map(|(candidate, other, _)| (candidate, other.rect));
+        if let Some(next) = find_in_direction(focusable.rect, direction, others) {
+            focus.focused = Some(next);
+        }
+    }
+}
+
+fn ui_focus_apply_system(
+    focus: Res<UiFocus>,
+    mut previous: Local<Option<Entity>>,
+    query: Query<&UiFocusable>,
+    mut hover: Query<&mut UiAnimator<Hover>>,
+    mut selected: Query<&mut UiAnimator<Selected>>,
+    mut events: EventWriter<UiFocusEvent>,
+) {
+    if *previous == focus.focused { return; }
+
+    for (entity, focused) in [(*previous, false), (focus.focused, true)] {
+        let Some(entity) = entity else { continue };
+        if let Ok(focusable) = query.get(entity) {
+            match focusable.drive {
+                UiFocusDrive::Hover => if let Ok(mut animator) = hover.get_mut(entity) { animator.set_forward(focused); },
+                UiFocusDrive::Selected => if let Ok(mut animator) = selected.get_mut(entity) { animator.set_forward(focused); },
+            }
+        }
+        events.send(UiFocusEvent { target: entity, focused });
+    }
+    *previous = focus.focused;
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// Plugin adding keyboard and gamepad navigation between [`UiFocusable`] nodes
+pub struct FocusPlugin;
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<UiFocus>()
+            .add_event::<UiNavigationEvent>()
+            .add_event::<UiFocusEvent>()
+            .add_systems(Update, (
+                ui_navigation_keyboard_system,
+                ui_navigation_gamepad_system,
+                ui_navigation_system,
+                ui_focus_apply_system,
+            ).chain().in_set(UiSystems::Modify).before(UiSystems::Send));
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn focusable(order: i32, x: f32, y: f32) -> UiFocusable {
+        UiFocusable { rect: Rect::new(x, y, x + 10.0, y + 10.0), ..UiFocusable::new().order(order) }
+    }
+
+    #[test]
+    fn navigation_direction () {
+        let [a, b, c, d] = [0, 1, 2, 3].map(Entity::from_raw);
+        let from = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let aligned = (a, Rect::new(50.0, 0.0, 60.0, 10.0));
+        let off_axis = (b, Rect::new(30.0, 40.0, 40.0, 50.0));
+        let left = (c, Rect::new(-50.0, 0.0, -40.0, 10.0));
+        let overlapping = (d, Rect::new(20.0, 5.0, 30.0, 15.0));
+
+        // Closer off-axis candidates lose to aligned ones
+        assert_eq!(find_in_direction(from, Vec2::X, [aligned, off_axis, left].into_iter()), Some(a));
+        assert_eq!(find_in_direction(from, Vec2::X, [aligned, off_axis, overlapping].into_iter()), Some(d));
+        assert_eq!(find_in_direction(from, Vec2::Y, [aligned, off_axis, left].into_iter()), Some(b));
+        assert_eq!(find_in_direction(from, Vec2::NEG_X, [aligned, off_axis, left].into_iter()), Some(c));
+
+        // Nothing in the direction
+        assert_eq!(find_in_direction(from, Vec2::NEG_Y, [aligned, off_axis, left].into_iter()), None);
+        assert_eq!(find_in_direction(from, Vec2::X, [].into_iter()), None);
+    }
+
+    #[test]
+    fn navigation_tab_order () {
+        let [a, b, c, d, e] = [0, 1, 2, 3, 4].map(Entity::from_raw);
+        let focusables = [(a, focusable(0, 0.0, 50.0)), (b, focusable(0, 50.0, 0.0)), (c, focusable(0, 0.0, 0.0)), (d, focusable(-1, 100.0, 100.0)), (e, focusable(1, 0.0, 0.0))];
+        let tab = |current: Option<Entity>, next: bool| find_in_tab_order(current, next, focusables.iter().map(|(entity, focusable)| (*entity, focusable)));
+
+        // Explicit order first, then top to bottom and left to right
+        assert_eq!(tab(None, true), Some(d));
+        assert_eq!(tab(Some(d), true), Some(c));
+        assert_eq!(tab(Some(c), true), Some(b));
+        assert_eq!(tab(Some(b), true), Some(a));
+        assert_eq!(tab(Some(a), true), Some(e));
+        assert_eq!(tab(Some(a), false), Some(b));
+
+        // Wraps around at the ends
+        assert_eq!(tab(Some(e), true), Some(d));
+        assert_eq!(tab(Some(d), false), Some(e));
+        assert_eq!(find_in_tab_order(None, true, [].into_iter()), None);
+    }
+}
@@ -0,0 +1,417 @@
+use crate::*;
+use bevy::window::PrimaryWindow;
+use lunex_engine::{Anchor, NodeDataTrait, NodeTopDataTrait};
+
+
+// #===============#
+// #=== POPOVER ===#
+
+/// What the [`UiPopover`] is attached to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UiPopoverTarget {
+    /// Attached to the node linked to the entity
+    Entity(Entity),
+    /// Attached to the position in the [`UiTree`], measured from the top-left corner
+    Position(Vec2),
+}
+
+/// Places the entity next to the computed rectangle of the target node and shows or hides it.
+/// The entity must use window layout in [`Base`] state, its position is overwritten.
+///
+/// The popover is attached to the [`UiPopover::anchor`] point of the target and placed on the opposite side, so
+/// [`Anchor::BottomCenter`] places it under the target and [`Anchor::CenterRight`] to the right of it.
+/// If it doesn't fit into the root [`Dimension`] of the [`UiTree`], it is flipped to the other side and shifted inside.
+///
+/// Opening the popover plays its [`UiAnimator<Intro>`] and closing it plays its [`UiAnimator<Outro>`] before it is hidden, if present.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Popover"),
+///     UiLayout::window().size(Ab((200.0, 60.0))).pack::<Base>(),
+///     UiPopover::new(button).anchor(Anchor::TopCenter).gap(8.0),
+///     UiAnimator::<Intro>::new(),
+///     UiColor::<Intro>::new(Color::NONE),
+///     UiImage2dBundle::from(assets.load("popover.png")),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct UiPopover {
+    /// What the popover is attached to
+    pub target: UiPopoverTarget,
+    /// Point of the target the popover is attached to
+    pub anchor: Anchor,
+    /// Space between the target and the popover in [`Ab`] units
+    pub gap: f32,
+    /// If the popover can flip to the other side of the target to stay inside the root
+    pub flip: bool,
+    /// If the popover can be shifted to stay inside the root
+    pub shift: bool,
+    /// If pressing anywhere outside of the popover closes it
+    pub dismiss: bool,
+    /// If the popover is shown
+    open: bool,
+}
+impl UiPopover {
+    /// Creates new closed popover attached under the node of the entity
+    pub fn new(target: Entity) -> Self {
+        UiPopover {
+            target: UiPopoverTarget::Entity(target),
+            anchor: Anchor::BottomCenter,
+            gap: 0.0,
+            flip: true,
+            shift: true,
+            dismiss: false,
+            open: false,
+        }
+    }
+    /// Creates new closed popover attached to the position in the [`UiTree`]
+    pub fn at(position: impl Into<Vec2>) -> Self {
+        UiPopover {
+            target: UiPopoverTarget::Position(position.into()),
+            anchor: Anchor::BottomRight,
+            ..UiPopover::new(Entity::PLACEHOLDER)
+        }
+    }
+    /// Replaces the anchor with a new value.
+    pub fn anchor(mut self, anchor: impl Into<Anchor>) -> Self {
+        self.anchor = anchor.into();
+        self
+    }
+    /// Replaces the gap with a new value.
+    pub fn gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+    /// Replaces the flip option with a new value.
+    pub fn flip(mut self, flip: bool) -> Self {
+        self.flip = flip;
+        self
+    }
+    /// Replaces the shift option with a new value.
+    pub fn shift(mut self, shift: bool) -> Self {
+        self.shift = shift;
+        self
+    }
+    /// Replaces the dismiss option with a new value.
+    pub fn dismiss(mut self, dismiss: bool) -> Self {
+        self.dismiss = dismiss;
+        self
+    }
+    /// Checks if the popover is shown
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+    /// Shows or hides the popover.
+    pub fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+    /// Returns the top-left corner of the popover with the size placed next to the target rectangle within the bounds.
+    pub fn get_position(&self, target: Rect, size: Vec2, gap: f32, bounds: Rect) -> Vec2 {
+        let anchor = self.anchor.as_vec();
+        let place = |anchor: Vec2| {
+            let side = Vec2::select(anchor.cmpeq(Vec2::splat(0.5)), Vec2::ZERO, (anchor - 0.5).signum());
+            target.min + target.size() * anchor - size * (1.0 - anchor) + side * gap
+        };
+        let overflow = |position: Vec2| (bounds.min - position).max(Vec2::ZERO) + (position + size - bounds.max).max(Vec2::ZERO);
+
+        let mut position = place(anchor);
+        if self.flip {
+            let flipped = place(1.0 - anchor);
+            let (overflow, flipped_overflow) = (overflow(position), overflow(flipped));
+            for axis in 0..2 {
+                if anchor[axis] != 0.5 && flipped_overflow[axis] < overflow[axis] { position[axis] = flipped[axis]; }
+            }
+        }
+        if self.shift {
+            position = position.min(bounds.max - size).max(bounds.min);
+        }
+        position
+    }
+}
+
+/// Shows the popover entity after the pointer stays over this entity for the delay and hides it on pointer out.
+/// The popover is attached to this entity when shown, so one popover can be shared by many nodes.
+/// ## 🛠️ Example
+/// ```
+/// let tooltip = ui.spawn((
+///     UiLink::<MainUi>::path("Tooltip"),
+///     UiLayout::window().size(Ab((240.0, 40.0))).pack::<Base>(),
+///     UiPopover::new(Entity::PLACEHOLDER).anchor(Anchor::TopCenter).gap(6.0),
+///     Pickable::IGNORE,
+///     UiImage2dBundle::from(assets.load("tooltip.png")),
+/// )).id();
+///
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Menu/Button"),
+///     UiLayout::window().size(Rl((20.0, 10.0))).pack::<Base>(),
+///     UiTooltip::new(tooltip).delay(0.8),
+///     UiZoneBundle::default(),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct UiTooltip {
+    /// The popover entity
+    pub popover: Entity,
+    /// Seconds the pointer has to stay over the entity
+    pub delay: f32,
+    /// Seconds the pointer is over the entity
+    hovered: Option<f32>,
+}
+impl UiTooltip {
+    /// Creates new tooltip with half a second delay
+    pub fn new(popover: Entity) -> Self {
+        UiTooltip { popover, delay: 0.5, hovered: None }
+    }
+    /// Replaces the delay with a new value.
+    pub fn delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Shows the popover entity at the pointer when this entity is pressed with the secondary button.
+/// The popover should have [`UiPopover::dismiss`] set, so it closes when pressing anywhere else.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Files/File"),
+///     UiLayout::window().size(Rl((100.0, 10.0))).pack::<Base>(),
+///     UiContextMenu::new(menu),
+///     UiZoneBundle::default(),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiContextMenu {
+    /// The popover entity
+    pub popover: Entity,
+}
+impl UiContextMenu {
+    /// Creates new struct
+    pub fn new(popover: Entity) -> Self {
+        UiContextMenu { popover }
+    }
+}
+
+
+// #===============#
+// #=== SYSTEMS ===#
+
+fn ui_popover_visibility_system(mut query: Query<(Ref<UiPopover>, &mut Visibility, Option<&mut UiAnimator<Intro>>, Option<&mut UiAnimator<Outro>>)>) {
+    for (popover, mut visibility, intro, outro) in &mut query {
+        // Closed popovers start hidden
+        if popover.is_added() && !popover.open {
+            if *visibility != Visibility::Hidden { *visibility = Visibility::Hidden; }
+            continue;
+        }
+        animate_visibility(popover.open, visibility, intro, outro);
+    }
+}
+
+pub(crate) fn ui_tooltip_system(
+    time: Res<Time>,
+    mut overs: EventReader<Pointer<Over>>,
+    mut outs: EventReader<Pointer<Out>>,
+    mut query: Query<(Entity, &mut UiTooltip)>,
+    mut popovers: Query<&mut UiPopover>,
+) {
+    for event in overs.read() {
+        if let Ok((_, mut tooltip)) = query.get_mut(event.target) { tooltip.hovered = Some(0.0); }
+    }
+    for event in outs.read() {
+        let Ok((_, mut tooltip)) = query.get_mut(event.target) else { continue };
+        tooltip.hovered = None;
+        if let Ok(mut popover) = popovers.get_mut(tooltip.popover) {
+            if popover.open && popover.target == UiPopoverTarget::Entity(event.target) { popover.open = false; }
+        }
+    }
+
+    for (entity, mut tooltip) in &mut query {
+        let Some(hovered) = tooltip.hovered else { continue };
+        if hovered >= tooltip.delay { continue }
+        let hovered = hovered + time.delta_seconds();
+        tooltip.hovered = Some(hovered);
+        if hovered < tooltip.delay { continue }
+
+        let Ok(mut popover) = popovers.get_mut(tooltip.popover) else { continue };
+        popover.target = UiPopoverTarget::Entity(entity);
+        popover.open = true;
+    }
+}
+
+pub(crate) fn ui_context_menu_system(
+    mut events: EventReader<Pointer<Down>>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, &OrthographicProjection)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    query: Query<(&UiContextMenu, &Parent)>,
+    transforms: Query<&GlobalTransform>,
+    mut popovers: Query<&mut UiPopover>,
+) {
+    for event in events.read() {
+        if event.button != PointerButton::Secondary { continue }
+        let Ok((menu, parent)) = query.get(event.target) else { continue };
+        let Some((_, position)) = pointer_world_position(&event.pointer_location, &cameras, &primary_window) else { continue };
+        let Ok(transform) = transforms.get(parent.get()) else { continue };
+        let Ok(mut popover) = popovers.get_mut(menu.popover) else { continue };
+
+        // Nodes are placed from the top-left corner of the tree with inverted y
+        let local = node_local_position(position, transform);
+        popover.target = UiPopoverTarget::Position(Vec2::new(local.x, -local.y));
+        popover.open = true;
+    }
+}
+
+/// This system closes [`UiPopover`]s with [`UiPopover::dismiss`] set when pressing outside of their node or its subnodes.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+pub fn ui_popover_dismiss_system<T:Component>(
+    mut events: EventReader<Pointer<Down>>,
+    links: Query<&UiLink<T>>,
+    mut query: Query<(Entity, &mut UiPopover, &UiLink<T>)>,
+) {
+    for event in events.read() {
+        let pressed = links.get(event.target).ok();
+        for (entity, mut popover, link) in &mut query {
+            if !popover.open || !popover.dismiss || entity == event.target { continue }
+            if pressed.is_some_and(|pressed| pressed.path.strip_prefix(&link.path).is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))) { continue }
+            popover.open = false;
+        }
+    }
+}
+
+/// This system positions open [`UiPopover`]s next to their target using the rectangles computed by the [`UiTree`].
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn ui_popover_position_system<T:Component, N:Default + Component>(
+    uis: Query<(&UiTree<T, N>, &Dimension, Option<&SourceFromCamera>, &Children)>,
+    window: Query<&bevy::window::Window, With<PrimaryWindow>>,
+    links: Query<&UiLink<T>>,
+    mut query: Query<(&UiPopover, &UiLink<T>, &mut UiLayout<Base>)>,
+) {
+    let scale = if let Ok(window) = window.get_single() { window.resolution.scale_factor() } else { 1.0 };
+    for (ui, dimension, is_camera_sourced, children) in &uis {
+        let scale = if is_camera_sourced.is_none() { 1.0 } else { scale };
+        let bounds = Rect::from_corners(Vec2::ZERO, dimension.size / scale);
+        let abs_scale = ui.obtain_topdata().map_or(1.0, |data| data.abs_scale);
+
+        let mut popovers = query.iter_many_mut(children);
+        while let Some((popover, link, mut layout)) = popovers.fetch_next() {
+            if !popover.open { continue }
+            let Layout::Window(window) = &layout.layout else { continue };
+            let Ok(Some(data)) = ui.borrow_data(link.path.as_str()) else { continue };
+            let size = data.rectangle.size;
+
+            let target = match popover.target {
+                UiPopoverTarget::Entity(target) => {
+                    let Ok(Ok(Some(target))) = links.get(target).map(|target| ui.borrow_data(target.path.as_str())) else { continue };
+                    Rect::from_corners(target.rectangle.pos.truncate(), target.rectangle.pos.truncate() + target.rectangle.size)
+                },
+                UiPopoverTarget::Position(position) => Rect::from_corners(position, position),
+            };
+
+            // The layout is placed relative to the scrolled parent node
+            let origin = match link.path.rsplit_once('/') {
+                Some((parent, _)) => match ui.borrow_data(parent) {
+                    Ok(Some(parent)) => parent.rectangle.pos.truncate() - parent.scroll,
+                    _ => continue,
+                },
+                None => Vec2::ZERO,
+            };
+
+            let position = popover.get_position(target, size, popover.gap * abs_scale, bounds);
+            let pos = UiValue::from(Ab((position - origin) / abs_scale));
+            if window.pos != pos || window.anchor != Anchor::TopLeft {
+                if let Layout::Window(window) = &mut layout.layout {
+                    window.pos = pos;
+                    window.anchor = Anchor::TopLeft;
+                }
+            }
+        }
+    }
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// Plugin adding [`UiPopover`], [`UiTooltip`] and [`UiContextMenu`] logic
+pub struct PopoverPlugin;
+impl Plugin for PopoverPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                ui_tooltip_system,
+                ui_context_menu_system,
+                ui_popover_visibility_system,
+            ).chain().in_set(UiSystems::Modify).before(UiSystems::Send));
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BOUNDS: Rect = Rect { min: Vec2::ZERO, max: Vec2::new(800.0, 600.0) };
+    const SIZE: Vec2 = Vec2::new(50.0, 20.0);
+
+    fn position(popover: UiPopover, target: Rect) -> Vec2 {
+        popover.get_position(target, SIZE, 5.0, BOUNDS)
+    }
+
+    #[test]
+    fn popover_sides () {
+        let target = Rect::new(100.0, 100.0, 200.0, 150.0);
+        let popover = UiPopover::new(Entity::PLACEHOLDER);
+        assert_eq!(position(popover.clone().anchor(Anchor::BottomCenter), target), Vec2::new(125.0, 155.0));
+        assert_eq!(position(popover.clone().anchor(Anchor::TopCenter), target), Vec2::new(125.0, 75.0));
+        assert_eq!(position(popover.clone().anchor(Anchor::CenterLeft), target), Vec2::new(45.0, 115.0));
+        assert_eq!(position(popover.clone().anchor(Anchor::CenterRight), target), Vec2::new(205.0, 115.0));
+        assert_eq!(position(popover.clone().anchor(Anchor::BottomRight), target), Vec2::new(205.0, 155.0));
+        assert_eq!(position(popover.anchor(Anchor::TopLeft), target), Vec2::new(45.0, 75.0));
+    }
+
+    #[test]
+    fn popover_flip () {
+        // Overflowing the bottom flips it above the target
+        let target = Rect::new(100.0, 570.0, 200.0, 590.0);
+        let popover = UiPopover::new(Entity::PLACEHOLDER);
+        assert_eq!(position(popover.clone(), target), Vec2::new(125.0, 545.0));
+
+        // Without flip it is shifted inside instead
+        assert_eq!(position(popover.clone().flip(false), target), Vec2::new(125.0, 580.0));
+        assert_eq!(position(popover.flip(false).shift(false), target), Vec2::new(125.0, 595.0));
+
+        // Doesn't flip if the other side overflows more
+        let target = Rect::new(100.0, 0.0, 200.0, 590.0);
+        assert_eq!(position(UiPopover::new(Entity::PLACEHOLDER).shift(false), target), Vec2::new(125.0, 595.0));
+    }
+
+    #[test]
+    fn popover_shift () {
+        // Centre axis is never flipped, only shifted
+        let target = Rect::new(780.0, 100.0, 800.0, 150.0);
+        let popover = UiPopover::new(Entity::PLACEHOLDER);
+        assert_eq!(position(popover.clone(), target), Vec2::new(750.0, 155.0));
+        assert_eq!(position(popover.clone().shift(false), target), Vec2::new(765.0, 155.0));
+
+        let target = Rect::new(0.0, 100.0, 20.0, 150.0);
+        assert_eq!(position(popover.clone().anchor(Anchor::TopCenter), target), Vec2::new(0.0, 75.0));
+        assert_eq!(position(popover.anchor(Anchor::TopCenter).shift(false), target), Vec2::new(-15.0, 75.0));
+    }
+
+    #[test]
+    fn popover_position () {
+        let popover = UiPopover::at((300.0, 200.0));
+        let UiPopoverTarget::Position(point) = popover.target else { panic!() };
+        let target = Rect::from_corners(point, point);
+        assert_eq!(popover.get_position(target, SIZE, 0.0, BOUNDS), Vec2::new(300.0, 200.0));
+
+        // Context menus open up and left in the corner
+        let target = Rect::from_corners(Vec2::new(790.0, 590.0), Vec2::new(790.0, 590.0));
+        assert_eq!(popover.get_position(target, SIZE, 0.0, BOUNDS), Vec2::new(740.0, 570.0));
+    }
+}
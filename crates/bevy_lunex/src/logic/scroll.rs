@@ -0,0 +1,328 @@
+use crate::*;
+use bevy::{input::mouse::{MouseScrollUnit, MouseWheel}, window::PrimaryWindow};
+
+
+// #==============#
+// #=== EVENTS ===#
+
+/// This is an event you can listen to which is sent every time [`UiScroll`] offset is applied to the node.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct UiScrollEvent {
+    /// The entity with the scroll container
+    pub target: Entity,
+    /// The new scroll offset
+    pub offset: Vec2,
+    /// The largest offset that reveals the end of the content
+    pub max: Vec2,
+}
+
+
+// #==============#
+// #=== SCROLL ===#
+
+/// Makes the node a scroll container. All of its subnodes are moved by the scroll offset
+/// and their linked entities are clipped to the node rectangle, see [`UiClip`].
+/// The container can be scrolled with mouse wheel, by dragging the content or with the right stick of a [`GamepadCursor`].
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Menu/List"),
+///     UiLayout::window().size(Rl((50.0, 80.0))).pack::<Base>(),
+///     UiScroll::new().overscroll(60.0),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+pub struct UiScroll {
+    /// The current scroll offset
+    pub offset: Vec2,
+    /// The current scroll velocity, used for inertia after dragging
+    pub velocity: Vec2,
+    /// If the content can be scrolled horizontally
+    pub horizontal: bool,
+    /// If the content can be scrolled vertically
+    pub vertical: bool,
+    /// If the content can be scrolled by dragging
+    pub draggable: bool,
+    /// How far one mouse wheel line scrolls
+    pub speed: f32,
+    /// How quickly the inertia fades out
+    pub friction: f32,
+    /// How far the content can be dragged past its end
+    pub overscroll: f32,
+    /// The spring pulling the content back from overscroll
+    pub bounce: UiSpring,
+    /// The largest offset that reveals the end of the content
+    pub(crate) max: Vec2,
+    /// If the content is being dragged
+    dragging: bool,
+}
+impl UiScroll {
+    /// Creates new vertical scroll container
+    pub fn new() -> Self {
+        UiScroll {
+            offset: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            horizontal: false,
+            vertical: true,
+            draggable: true,
+            speed: 40.0,
+            friction: 5.0,
+            overscroll: 80.0,
+            bounce: UiSpring::new(300.0, 35.0),
+            max: Vec2::ZERO,
+            dragging: false,
+        }
+    }
+    /// Sets if the content can be scrolled horizontally.
+    pub fn horizontal(mut self, horizontal: bool) -> Self {
+        self.horizontal = horizontal;
+        self
+    }
+    /// Sets if the content can be scrolled vertically.
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+    /// Sets if the content can be scrolled by dragging.
+    pub fn draggable(mut self, draggable: bool) -> Self {
+        self.draggable = draggable;
+        self
+    }
+    /// Replaces the mouse wheel line distance with a new value.
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+    /// Replaces the inertia friction with a new value.
+    pub fn friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+    /// Replaces the overscroll distance with a new value. Set it to `0.0` to disable the bounce.
+    pub fn overscroll(mut self, overscroll: f32) -> Self {
+        self.overscroll = overscroll;
+        self
+    }
+    /// Replaces the bounce spring with a new value.
+    pub fn bounce(mut self, bounce: UiSpring) -> Self {
+        self.bounce = bounce;
+        self
+    }
+    /// Returns the largest offset that reveals the end of the content.
+    pub fn get_max(&self) -> Vec2 {
+        self.max
+    }
+    /// Returns the scroll offset relative to the content, from `0.0` to `1.0`.
+    pub fn get_progress(&self) -> Vec2 {
+        Vec2::select(self.max.cmpgt(Vec2::ZERO), self.offset / self.max, Vec2::ZERO)
+    }
+    /// Returns if the content is being dragged.
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+    /// Scrolls to the offset within the content and stops the inertia.
+    pub fn scroll_to(&mut self, offset: impl Into<Vec2>) {
+        self.offset = (offset.into() * self.axes()).clamp(Vec2::ZERO, self.max);
+        self.velocity = Vec2::ZERO;
+    }
+    /// Scrolls by the distance within the content and stops the inertia.
+    pub fn scroll_by(&mut self, delta: impl Into<Vec2>) {
+        self.scroll_to(self.offset + self.axis_delta(delta.into()));
+    }
+
+    /// Mask of the enabled axes
+    fn axes(&self) -> Vec2 {
+        Vec2::new(self.horizontal as u8 as f32, self.vertical as u8 as f32)
+    }
+    /// Redirects vertical input into horizontal only containers
+    fn axis_delta(&self, delta: Vec2) -> Vec2 {
+        if self.horizontal && !self.vertical && delta.x == 0.0 { Vec2::new(delta.y, 0.0) } else { delta }
+    }
+    /// Computes the offset and velocity after inertia and bounce
+    fn simulate(&self, delta: f32) -> (Vec2, Vec2) {
+        let (mut offset, mut velocity) = (self.offset, self.velocity);
+        for axis in 0..2 {
+            if self.axes()[axis] == 0.0 {
+                offset[axis] = 0.0;
+                velocity[axis] = 0.0;
+                continue;
+            }
+            if self.dragging { continue; }
+
+            let bound = offset[axis].clamp(0.0, self.max[axis]);
+            if offset[axis] != bound {
+                // Bounce back from overscroll
+                (offset[axis], velocity[axis]) = self.bounce.step(offset[axis], velocity[axis], bound, delta);
+            } else if velocity[axis] != 0.0 {
+                // Glide with inertia
+                offset[axis] += velocity[axis] * delta;
+                velocity[axis] *= (-self.friction * delta).exp();
+                if velocity[axis].abs() < 1.0 { velocity[axis] = 0.0; }
+            }
+            offset[axis] = offset[axis].clamp(-self.overscroll, self.max[axis] + self.overscroll);
+        }
+        (offset, velocity)
+    }
+}
+impl Default for UiScroll {
+    fn default() -> Self {
+        UiScroll::new()
+    }
+}
+
+
+// #===============#
+// #=== SYSTEMS ===#
+
+/// Query of scroll containers for finding them under pointers
+type ScrollHitQuery<'w, 's> = Query<'w, 's, (Entity, &'static Dimension, &'static GlobalTransform, Has<Element>, Option<&'static UiClip>), With<UiScroll>>;
+
+/// Returns the topmost scroll container under the pointer
+fn find_scroll(
+    location: &pointer::Location,
+    cameras: &Query<(Entity, &Camera, &GlobalTransform, &OrthographicProjection)>,
+    primary_window: &Query<Entity, With<PrimaryWindow>>,
+    containers: &ScrollHitQuery,
+) -> Option<Entity> {
+    let (_, position) = pointer_world_position(location, cameras, primary_window)?;
+    containers.iter()
+        .filter(|(_, dimension, transform, is_element, clip)| {
+            let local = node_local_position(position, transform);
+            node_rect(dimension, *is_element).contains(local) && clip.map(|clip| clip.rect.contains(local)).unwrap_or(true)
+        })
+        .max_by(|a, b| a.2.translation().z.total_cmp(&b.2.translation().z))
+        .map(|(entity, ..)| entity)
+}
+
+fn ui_scroll_wheel_system(
+    mut events: EventReader<MouseWheel>,
+    pointers: Query<&PointerLocation, Without<GamepadCursor>>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, &OrthographicProjection)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    containers: ScrollHitQuery,
+    mut query: Query<&mut UiScroll>,
+) {
+    for event in events.read() {
+        for location in pointers.iter().filter_map(|pointer| pointer.location()) {
+            let Some(entity) = find_scroll(location, &cameras, &primary_window, &containers) else { continue };
+            let Ok(mut scroll) = query.get_mut(entity) else { continue };
+            let unit = match event.unit {
+                MouseScrollUnit::Line => scroll.speed,
+                MouseScrollUnit::Pixel => 1.0,
+            };
+            scroll.scroll_by(-Vec2::new(event.x, event.y) * unit);
+        }
+    }
+}
+
+fn ui_scroll_gamepad_system(
+    time: Res<Time>,
+    axis: Res<Axis<GamepadAxis>>,
+    pointers: Query<(&PointerLocation, &GamepadCursor)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, &OrthographicProjection)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    containers: ScrollHitQuery,
+    mut query: Query<&mut UiScroll>,
+) {
+    for (pointer, gamepad) in &pointers {
+        let Some(location) = pointer.location() else { continue };
+        let x = axis.get(GamepadAxis { gamepad: Gamepad::new(gamepad.id), axis_type: GamepadAxisType::RightStickX }).unwrap_or(0.0);
+        let y = axis.get(GamepadAxis { gamepad: Gamepad::new(gamepad.id), axis_type: GamepadAxisType::RightStickY }).unwrap_or(0.0);
+        if x == 0.0 && y == 0.0 { continue }
+
+        let Some(entity) = find_scroll(location, &cameras, &primary_window, &containers) else { continue };
+        let Ok(mut scroll) = query.get_mut(entity) else { continue };
+        let speed = scroll.speed * 20.0 * time.delta_seconds();
+        scroll.scroll_by(Vec2::new(x, -y) * speed);
+    }
+}
+
+fn ui_scroll_drag_system(
+    time: Res<Time>,
+    mut starts: EventReader<Pointer<DragStart>>,
+    mut drags: EventReader<Pointer<Drag>>,
+    mut ends: EventReader<Pointer<DragEnd>>,
+    clips: Query<&UiClip>,
+    excluded: Query<(), Or<(With<UiSlider>, With<UiSliderHandle>, With<UiDraggable>)>>,
+    mut query: Query<&mut UiScroll>,
+) {
+    // Dragged content can be caught by the container or by any clipped entity inside, except sliders and draggable nodes
+    let container = |target: Entity| if query.contains(target) { Some(target) } else if excluded.contains(target) { None } else { clips.get(target).ok().map(|clip| clip.container) };
+
+    let mut started = Vec::new();
+    for event in starts.read() {
+        if event.button != PointerButton::Primary { continue }
+        started.extend(container(event.target));
+    }
+    let mut ended = Vec::new();
+    for event in ends.read() {
+        if event.button != PointerButton::Primary { continue }
+        ended.extend(container(event.target));
+    }
+    let mut moved = Vec::new();
+    for event in drags.read() {
+        if event.button != PointerButton::Primary { continue }
+        if let Some(entity) = container(event.target) { moved.push((entity, event.delta)); }
+    }
+
+    for entity in started {
+        let Ok(mut scroll) = query.get_mut(entity) else { continue };
+        if !scroll.draggable { continue }
+        scroll.dragging = true;
+        scroll.velocity = Vec2::ZERO;
+    }
+
+    // Content that is held still should not keep its velocity
+    for mut scroll in &mut query {
+        if scroll.dragging && scroll.velocity != Vec2::ZERO { scroll.velocity = Vec2::ZERO; }
+    }
+
+    let delta_seconds = time.delta_seconds();
+    for (entity, delta) in moved {
+        let Ok(mut scroll) = query.get_mut(entity) else { continue };
+        if !scroll.dragging { continue }
+        let mut delta = -delta * scroll.axes();
+
+        // Resist dragging past the end
+        for axis in 0..2 {
+            if scroll.offset[axis] < 0.0 || scroll.offset[axis] > scroll.max[axis] { delta[axis] *= 0.5; }
+        }
+
+        let offset = scroll.offset + delta;
+        scroll.offset = offset.clamp(-Vec2::splat(scroll.overscroll), scroll.max + scroll.overscroll);
+        if delta_seconds > 0.0 { scroll.velocity += delta / delta_seconds; }
+    }
+
+    for entity in ended {
+        let Ok(mut scroll) = query.get_mut(entity) else { continue };
+        scroll.dragging = false;
+    }
+}
+
+fn ui_scroll_system(time: Res<Time>, mut query: Query<&mut UiScroll>) {
+    for mut scroll in &mut query {
+        let (offset, velocity) = scroll.simulate(time.delta_seconds());
+
+        // Velocity alone shouldn't trigger resending of the offset
+        scroll.bypass_change_detection().velocity = velocity;
+        if scroll.offset != offset { scroll.offset = offset; }
+    }
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// Plugin adding [`UiScroll`] input and inertia
+pub struct ScrollPlugin;
+impl Plugin for ScrollPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                ui_scroll_wheel_system,
+                ui_scroll_gamepad_system,
+                ui_scroll_drag_system,
+                ui_scroll_system,
+            ).chain().in_set(UiSystems::Modify).before(UiSystems::Send));
+    }
+}
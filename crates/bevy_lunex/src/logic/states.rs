@@ -2,6 +2,7 @@
 use bevy_kira_audio::prelude::*;
 
 use crate::*;
+use bevy::ecs::system::EntityCommands;
 
 
 // #==============#
@@ -34,12 +35,14 @@ fn set_ui_state_transition<S: UiState>(mut events: EventReader<SetUiStateTransit
 // #=== STATE STRUCTS ===#
 
 /// Control struct for the button state
-#[derive(Component, Debug, Clone, PartialEq)]
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default)]
 pub struct UiAnimator<S: UiState> {
+    #[reflect(ignore)]
     marker: PhantomData<S>,
     /// -1.0 backwards, 1.0 forward
     pub (crate) animation_direction: f32,
-    /// Range from `0.0` to `1.0`
+    /// Range from `0.0` to `1.0`, springs can overshoot it
     pub (crate) animation_transition: f32,
     /// Setting this to true will disable logic with intention that something else will pipe the control data instead
     pub receiver: bool,
@@ -47,6 +50,14 @@ pub struct UiAnimator<S: UiState> {
     pub animation_speed_forward: f32,
     /// Hover animation speed when transitioning back to default
     pub animation_speed_backward: f32,
+    /// Easing curve when transitioning to state
+    pub animation_ease_forward: UiEase,
+    /// Easing curve when transitioning back to default
+    pub animation_ease_backward: UiEase,
+    /// If set, the transition is driven by a damped spring instead of speed and easing
+    pub animation_spring: Option<UiSpring>,
+    /// Velocity of the spring
+    pub (crate) animation_velocity: f32,
 }
 impl <S: UiState> UiAnimator<S> {
     /// Creates new struct
@@ -68,10 +79,53 @@ impl <S: UiState> UiAnimator<S> {
         self.animation_speed_backward = speed;
         self
     }
+    /// Replaces the easing curve for both directions.
+    pub fn ease(mut self, ease: UiEase) -> Self {
+        self.animation_ease_forward = ease;
+        self.animation_ease_backward = ease;
+        self
+    }
+    /// Replaces the forward easing curve with a new value.
+    pub fn forward_ease(mut self, ease: UiEase) -> Self {
+        self.animation_ease_forward = ease;
+        self
+    }
+    /// Replaces the backward easing curve with a new value.
+    pub fn backward_ease(mut self, ease: UiEase) -> Self {
+        self.animation_ease_backward = ease;
+        self
+    }
+    /// Drives the transition with a damped spring instead of speed and easing.
+    pub fn spring(mut self, spring: UiSpring) -> Self {
+        self.animation_spring = Some(spring);
+        self
+    }
     /// Checks if animation is moving forward
     pub fn is_forward(&self) -> bool {
         self.animation_direction == 1.0
     }
+    /// Sets the direction of the animation, forward is towards the state.
+    pub fn set_forward(&mut self, forward: bool) {
+        self.animation_direction = if forward { 1.0 } else { -1.0 };
+    }
+    /// Reverses the direction of the animation.
+    pub fn toggle(&mut self) {
+        self.animation_direction = -self.animation_direction;
+    }
+    /// Returns the current transition ranging from `0.0` to `1.0`.
+    pub fn get_transition(&self) -> f32 {
+        self.animation_transition
+    }
+    /// Returns the transition remapped by the easing curve of the current direction.
+    /// The backward curve is applied from the state back to default.
+    pub fn get_value(&self) -> f32 {
+        if self.animation_spring.is_some() { return self.animation_transition; }
+        if self.is_forward() {
+            self.animation_ease_forward.ease(self.animation_transition)
+        } else {
+            1.0 - self.animation_ease_backward.ease(1.0 - self.animation_transition)
+        }
+    }
 }
 impl <S: UiState> Default for UiAnimator<S> {
     fn default() -> Self {
@@ -82,12 +136,25 @@ impl <S: UiState> Default for UiAnimator<S> {
             receiver: false,
             animation_speed_backward: 8.0,
             animation_speed_forward: 8.0,
+            animation_ease_forward: UiEase::Linear,
+            animation_ease_backward: UiEase::Linear,
+            animation_spring: None,
+            animation_velocity: 0.0,
         }
     }
 }
 fn ui_animation<S: UiState>(time: Res<Time>, mut query: Query<&mut UiAnimator<S>>) {
     for mut control in &mut query {
         if control.receiver { continue }
+        if let Some(spring) = control.animation_spring {
+            let target = if control.is_forward() { 1.0 } else { 0.0 };
+            if control.animation_transition != target || control.animation_velocity != 0.0 {
+                let (transition, velocity) = spring.step(control.animation_transition, control.animation_velocity, target, time.delta_seconds());
+                control.animation_transition = transition;
+                control.animation_velocity = velocity;
+            }
+            continue;
+        }
         if !(
             (control.animation_transition == 0.0 && control.animation_direction.is_sign_negative()) ||
             (control.animation_transition == 1.0 && control.animation_direction.is_sign_positive())
@@ -99,10 +166,95 @@ fn ui_animation<S: UiState>(time: Res<Time>, mut query: Query<&mut UiAnimator<S>
         }
     }
 }
-fn ui_animation_state<S: UiState>(mut query: Query<(&UiAnimator<S>, &mut UiLayoutController), Changed<UiAnimator<S>>>) {
-    for (animator, mut controller) in &mut query {
-        controller.index[1] = Hover::INDEX;
-        controller.tween = animator.animation_transition;
+
+
+/// This struct collects the transitions of all [`UiAnimator`] states on the entity.
+/// It is inserted automatically and resolves them into [`UiLayoutController`], color and [`UiShape`].
+///
+/// Active states are stacked by [`UiState::PRIORITY`]. Both the layout and the color are blended
+/// through all active states starting from [`Base`], so a state activating mid-transition doesn't snap.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct UiStateStack {
+    entries: Vec<UiStateEntry>,
+}
+impl UiStateStack {
+    /// Returns the index of the active state with the highest priority
+    pub fn get_dominant(&self) -> usize {
+        self.active().last().map(|entry| entry.index).unwrap_or(Base::INDEX)
+    }
+    /// Returns the transition of the state with this index
+    pub fn get_transition(&self, index: usize) -> f32 {
+        self.entries.iter().find(|entry| entry.index == index).map(|entry| entry.transition).unwrap_or_default()
+    }
+    /// Active entries sorted from the lowest priority
+    fn active(&self) -> impl DoubleEndedIterator<Item = &UiStateEntry> {
+        self.entries.iter().filter(|entry| entry.transition != 0.0)
+    }
+    /// Blends the shapes of all active states starting from the base shape
+    pub(crate) fn blend_shape(&self, base: UiShapeStyle) -> UiShapeStyle {
+        self.active().fold(base, |shape, entry| entry.shape.map_or(shape, |state_shape| shape.lerp(state_shape, entry.transition)))
+    }
+    /// Blends the layouts of all active states starting from the base layout
+    pub(crate) fn resolve_layout(&self, controller: &mut UiLayoutController) {
+        let mut layouts = self.active().filter(|entry| entry.layout);
+        if let Some(first) = layouts.next() {
+            controller.index = [Base::INDEX, first.index];
+            controller.tween = first.transition;
+            controller.chain = layouts.map(|entry| (entry.index, entry.transition)).collect();
+        } else if controller.tween != 0.0 || !controller.chain.is_empty() {
+            controller.tween = 0.0;
+            controller.chain.clear();
+        }
+    }
+    fn set<S: UiState>(&mut self, transition: f32, layout: bool, color: Option<Color>, shape: Option<UiShapeStyle>) {
+        let entry = UiStateEntry { index: S::INDEX, priority: S::PRIORITY, transition, layout, color, shape };
+        match self.entries.iter_mut().find(|entry| entry.index == S::INDEX) {
+            Some(old) => *old = entry,
+            None => {
+                self.entries.push(entry);
+                self.entries.sort_by_key(|entry| entry.priority);
+            },
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq)]
+struct UiStateEntry {
+    index: usize,
+    priority: usize,
+    transition: f32,
+    layout: bool,
+    color: Option<Color>,
+    shape: Option<UiShapeStyle>,
+}
+fn insert_ui_state_stack<S: UiState>(mut commands: Commands, query: Query<Entity, (Added<UiAnimator<S>>, Without<UiStateStack>)>) {
+    for entity in &query {
+        commands.entity(entity).insert(UiStateStack::default());
+    }
+}
+fn ui_animation_state<S: UiState>(mut query: Query<(&UiAnimator<S>, Option<&UiColor<S>>, Option<&UiShape<S>>, Has<UiLayout<S>>, &mut UiStateStack), Or<(Changed<UiAnimator<S>>, Changed<UiColor<S>>, Changed<UiShape<S>>, Added<UiStateStack>)>>) {
+    for (animator, color, shape, layout, mut stack) in &mut query {
+        stack.set::<S>(animator.get_value(), layout, color.map(|color| color.color), shape.map(|shape| shape.style));
+    }
+}
+pub(crate) fn ui_state_stack_resolve(mut query: Query<(&UiStateStack, Option<&UiColor<Base>>, Option<&mut UiLayoutController>, Entity), Or<(Changed<UiStateStack>, Changed<UiColor<Base>>)>>, mut set_color: EventWriter<actions::SetColor>) {
+    for (stack, basecolor, controller, entity) in &mut query {
+
+        // Blend the layouts of all active states
+        if let Some(mut controller) = controller {
+            stack.resolve_layout(&mut controller);
+        }
+
+        // Blend the colors of all active states
+        let Some(basecolor) = basecolor else { continue };
+        if basecolor.token.is_some() || stack.entries.iter().any(|entry| entry.color.is_some()) {
+            let mut color = basecolor.color;
+            for entry in stack.active() {
+                if let Some(state_color) = entry.color {
+                    color = color.lerp(state_color, entry.transition);
+                }
+            }
+            set_color.send(actions::SetColor { target: entity, color });
+        }
     }
 }
 
@@ -134,10 +286,17 @@ fn ui_state_pipe_system<S: UiState>(query: Query<(&UiAnimator<S>, &UiAnimatorPip
 
 
 /// Default base color component
+///
+/// The color can also reference a [`UiTheme`] token, in which case it is resolved from the theme
+/// and transitions to the new value when the theme changes.
 #[derive(Component, Debug, Clone, PartialEq)]
 pub struct UiColor<S: UiState> {
     /// The base color
     pub color: Color,
+    /// The [`UiTheme`] color token
+    pub token: Option<String>,
+    /// Running transition to a new theme color
+    pub(crate) fade: Option<(Color, Color, f32)>,
     /// Phantom data
     phantom: PhantomData<S>
 }
@@ -146,16 +305,19 @@ impl <S: UiState> UiColor<S> {
     pub fn new(color: Color) -> Self {
         UiColor {
             color,
+            token: None,
+            fade: None,
             phantom: PhantomData,
         }
     }
-}
-fn set_ui_color<S: UiState>(query: Query<(&UiAnimator<S>, &UiColor<Base>, &UiColor<S>, Entity), Changed<UiAnimator<S>>>, mut set_color: EventWriter<actions::SetColor>) {
-    for (hover, basecolor, hovercolor, entity) in &query {
-        set_color.send(actions::SetColor {
-            target: entity,
-            color: basecolor.color.lerp(hovercolor.color, hover.animation_transition),
-        });
+    /// Creates new struct resolved from the [`UiTheme`] color token
+    pub fn token(token: impl Into<String>) -> Self {
+        UiColor {
+            color: Color::NONE,
+            token: Some(token.into()),
+            fade: None,
+            phantom: PhantomData,
+        }
     }
 }
 
@@ -210,6 +372,129 @@ fn hover_leave_system(mut events: EventReader<Pointer<Out>>, mut query: Query<&m
 }
 
 
+// #===============#
+// #=== CLICKED ===#
+
+/// System that changes animation direction on pointer down
+fn clicked_press_system(mut events: EventReader<Pointer<Down>>, mut query: Query<&mut UiAnimator<Clicked>>) {
+    for event in events.read() {
+        if let Ok(mut clicked) = query.get_mut(event.target) {
+            clicked.set_forward(true);
+        }
+    }
+}
+
+/// System that changes animation direction on pointer up
+fn clicked_release_system(mut up: EventReader<Pointer<Up>>, mut out: EventReader<Pointer<Out>>, mut query: Query<&mut UiAnimator<Clicked>>) {
+    for target in up.read().map(|event| event.target).chain(out.read().map(|event| event.target)) {
+        if let Ok(mut clicked) = query.get_mut(target) {
+            if clicked.is_forward() { clicked.set_forward(false); }
+        }
+    }
+}
+
+
+// #================#
+// #=== SELECTED ===#
+
+/// Opts the entity out of toggling its [`UiAnimator<Selected>`] on click, for entities driving the selection themselves.
+/// Widgets like [`UiCheckbox`] or [`UiRadio`] insert it automatically.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UiManualSelect;
+
+/// System that toggles the selection on click. Entities with [`UiManualSelect`] are skipped.
+fn selected_toggle_system(mut events: EventReader<Pointer<Click>>, mut query: Query<&mut UiAnimator<Selected>, Without<UiManualSelect>>) {
+    for event in events.read() {
+        if let Ok(mut selected) = query.get_mut(event.target) {
+            if selected.receiver { continue }
+            selected.toggle();
+        }
+    }
+}
+
+
+// #=============#
+// #=== INTRO ===#
+
+/// System that starts the intro animation on spawn. It plays from the [`Intro`] state back to [`Base`].
+fn intro_spawn_system(mut query: Query<&mut UiAnimator<Intro>, Added<UiAnimator<Intro>>>) {
+    for mut intro in &mut query {
+        if intro.receiver { continue }
+        intro.animation_transition = 1.0;
+        intro.set_forward(false);
+    }
+}
+
+
+// #=============#
+// #=== OUTRO ===#
+
+/// Requests the entity to play its [`Outro`] animation and despawn recursively once it finishes.
+/// Entities without [`UiAnimator<Outro>`] are despawned right away. Insert it with [`UiDespawnCommands::ui_despawn`].
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UiDespawnRequest;
+
+/// Trait adding deferred despawn to [`EntityCommands`]
+pub trait UiDespawnCommands {
+    /// Plays the [`Outro`] animation of the entity and despawns it recursively once the transition reaches `1.0`.
+    fn ui_despawn(&mut self) -> &mut Self;
+}
+impl UiDespawnCommands for EntityCommands<'_> {
+    fn ui_despawn(&mut self) -> &mut Self {
+        self.insert(UiDespawnRequest)
+    }
+}
+
+/// System that starts the outro animation when requested
+fn outro_request_system(mut query: Query<&mut UiAnimator<Outro>, Added<UiDespawnRequest>>) {
+    for mut outro in &mut query {
+        outro.set_forward(true);
+    }
+}
+
+/// System that despawns the entity once the outro animation is finished
+fn outro_despawn_system(mut commands: Commands, query: Query<(Entity, Option<&UiAnimator<Outro>>), With<UiDespawnRequest>>) {
+    for (entity, outro) in &query {
+        let finished = match outro {
+            Some(outro) => outro.animation_transition == 1.0,
+            None => true,
+        };
+        if finished {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+
+/// Shows or hides the entity. Showing plays its [`Intro`] animation and hiding plays its [`Outro`] animation before it is hidden, if present.
+pub(crate) fn animate_visibility(open: bool, mut visibility: Mut<Visibility>, intro: Option<Mut<UiAnimator<Intro>>>, outro: Option<Mut<UiAnimator<Outro>>>) {
+    let hidden = *visibility == Visibility::Hidden;
+    if open {
+        if hidden {
+            *visibility = Visibility::Inherited;
+            if let Some(mut intro) = intro {
+                intro.animation_transition = 1.0;
+                intro.set_forward(false);
+            }
+            if let Some(mut outro) = outro {
+                outro.animation_transition = 0.0;
+                outro.set_forward(false);
+            }
+        } else if let Some(mut outro) = outro.filter(|outro| outro.is_forward()) {
+            outro.set_forward(false);
+        }
+    } else if !hidden {
+        match outro {
+            Some(mut outro) => {
+                if !outro.is_forward() { outro.set_forward(true); }
+                if outro.animation_transition == 1.0 { *visibility = Visibility::Hidden; }
+            },
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+
 // #===============#
 // #=== PLUGINS ===#
 
@@ -221,15 +506,18 @@ impl <T:Component, N:Default + Component, S: UiState> StatePlugin<T,N,S> {
 }
 impl <T:Component, N:Default + Component, S: UiState> Plugin for StatePlugin<T,N,S> {
     fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<StateStackPlugin>() {
+            app.add_plugins(StateStackPlugin);
+        }
+
         app
             .add_event::<SetUiStateTransition<S>>()
             .add_systems(Update, set_ui_state_transition::<S>.run_if(on_event::<SetUiStateTransition<S>>()))
 
             .add_systems(Update, ui_state_pipe_system::<S>)
 
-            .add_systems(Update, ui_animation_state::<S>)
-
-            .add_systems(Update, (ui_animation::<S>, set_ui_color::<S>.after(UiSystems::Process)).chain())
+            .add_systems(Update, insert_ui_state_stack::<S>)
+            .add_systems(Update, (ui_animation::<S>, ui_animation_state::<S>).chain().before(ui_state_stack_resolve))
 
             .add_systems(Update, send_layout_to_node::<T, N, S>.in_set(UiSystems::Send).before(send_content_size_to_node::<T, N>));
     }
@@ -240,6 +528,14 @@ impl <T:Component, N:Default + Component, S: UiState> Default for StatePlugin<T,
     }
 }
 
+/// Plugin resolving [`UiStateStack`] into layout and color, shared by all [`StatePlugin`]s
+pub struct StateStackPlugin;
+impl Plugin for StateStackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, ui_state_stack_resolve.in_set(UiSystems::Modify).before(UiSystems::Send));
+    }
+}
+
 pub struct DefaultStatesPlugin;
 impl Plugin for DefaultStatesPlugin {
     fn build(&self, app: &mut App) {
@@ -249,7 +545,206 @@ impl Plugin for DefaultStatesPlugin {
             .add_systems(Update, on_hover_play_sound_system.run_if(on_event::<Pointer<Over>>()));
 
         app
+            .register_type::<UiEase>()
+            .register_type::<UiLayoutController>()
+            .register_type::<UiSpring>()
+            .register_type::<UiAnimator<Hover>>()
+            .register_type::<UiAnimator<Clicked>>()
+            .register_type::<UiAnimator<Selected>>()
+            .register_type::<UiAnimator<Intro>>()
+            .register_type::<UiAnimator<Outro>>()
+            .register_type::<UiAnimator<Droppable>>()
+
             .add_systems(Update, hover_enter_system.run_if(on_event::<Pointer<Over>>()))
-            .add_systems(Update, hover_leave_system.run_if(on_event::<Pointer<Out>>()));
+            .add_systems(Update, hover_leave_system.run_if(on_event::<Pointer<Out>>()))
+
+            .add_systems(Update, clicked_press_system.run_if(on_event::<Pointer<Down>>()))
+            .add_systems(Update, clicked_release_system)
+            .add_systems(Update, selected_toggle_system.run_if(on_event::<Pointer<Click>>()))
+            .add_systems(Update, intro_spawn_system)
+            .add_systems(Update, (outro_request_system, outro_despawn_system).chain());
+    }
+}
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+    use bevy::render::camera::NormalizedRenderTarget;
+    use bevy_mod_picking::backend::HitData;
+    use bevy_mod_picking::pointer::{Location, PointerButton, PointerId};
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_event::<Pointer<Down>>()
+            .add_event::<Pointer<Up>>()
+            .add_event::<Pointer<Out>>()
+            .add_event::<Pointer<Click>>()
+            .add_event::<actions::SetColor>();
+        app
+    }
+
+    fn update(app: &mut App, seconds: f32) {
+        app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+    }
+
+    fn send<E: std::fmt::Debug + Clone + Reflect>(app: &mut App, target: Entity, event: E) {
+        let location = Location { target: NormalizedRenderTarget::Image(Handle::default()), position: Vec2::ZERO };
+        app.world_mut().send_event(Pointer::new(PointerId::Mouse, location, target, event));
     }
-}
\ No newline at end of file
+
+    fn hit() -> HitData {
+        HitData::new(Entity::PLACEHOLDER, 0.0, None, None)
+    }
+
+    fn transition<S: UiState>(app: &App, entity: Entity) -> f32 {
+        app.world().get::<UiAnimator<S>>(entity).unwrap().get_transition()
+    }
+
+    #[test]
+    fn state_stack_layout () {
+        let mut stack = UiStateStack::default();
+        let mut controller = UiLayoutController::default();
+
+        // Single state tweens from base
+        stack.set::<Hover>(0.5, true, None, None);
+        stack.resolve_layout(&mut controller);
+        assert_eq!((controller.index, controller.tween, controller.chain.clone()), ([Base::INDEX, Hover::INDEX], 0.5, vec![]));
+
+        // Higher state is blended on top instead of snapping
+        stack.set::<Clicked>(0.25, true, None, None);
+        stack.resolve_layout(&mut controller);
+        assert_eq!((controller.index, controller.tween, controller.chain.clone()), ([Base::INDEX, Hover::INDEX], 0.5, vec![(Clicked::INDEX, 0.25)]));
+        assert_eq!(stack.get_dominant(), Clicked::INDEX);
+
+        // States are ordered by priority, not by index
+        stack.set::<Selected>(1.0, true, None, None);
+        stack.resolve_layout(&mut controller);
+        assert_eq!(controller.chain, vec![(Selected::INDEX, 1.0), (Clicked::INDEX, 0.25)]);
+
+        // Inactive states and states without layout are skipped
+        stack.set::<Hover>(0.0, true, None, None);
+        stack.set::<Selected>(1.0, false, None, None);
+        stack.resolve_layout(&mut controller);
+        assert_eq!((controller.index, controller.tween, controller.chain.clone()), ([Base::INDEX, Clicked::INDEX], 0.25, vec![]));
+
+        stack.set::<Clicked>(0.0, true, None, None);
+        stack.resolve_layout(&mut controller);
+        assert_eq!((controller.tween, controller.chain.clone()), (0.0, vec![]));
+    }
+
+    #[test]
+    fn state_stack_color () {
+        let mut app = app();
+        app.add_systems(Update, ui_state_stack_resolve);
+
+        let mut stack = UiStateStack::default();
+        stack.set::<Hover>(1.0, false, Some(Color::hsl(0.0, 1.0, 0.8)), None);
+        stack.set::<Clicked>(0.5, false, Some(Color::hsl(0.0, 1.0, 0.4)), None);
+        let entity = app.world_mut().spawn((stack, UiColor::<Base>::new(Color::hsl(0.0, 1.0, 0.2)))).id();
+        app.update();
+
+        let events = app.world().resource::<Events<actions::SetColor>>();
+        let event = events.iter_current_update_events().next().unwrap();
+        assert_eq!(event.target, entity);
+        assert!((Hsla::from(event.color).lightness - 0.6).abs() < 1e-4);
+    }
+
+    #[test]
+    fn clicked_transition () {
+        let mut app = app();
+        app.add_systems(Update, (clicked_press_system, clicked_release_system, ui_animation::<Clicked>).chain());
+        let entity = app.world_mut().spawn(UiAnimator::<Clicked>::new()).id();
+
+        send(&mut app, entity, Down { button: PointerButton::Primary, hit: hit() });
+        update(&mut app, 0.1);
+        assert!(app.world().get::<UiAnimator<Clicked>>(entity).unwrap().is_forward());
+        assert!((transition::<Clicked>(&app, entity) - 0.8).abs() < 1e-4);
+
+        update(&mut app, 0.1);
+        assert_eq!(transition::<Clicked>(&app, entity), 1.0);
+
+        // Leaving the entity releases the click too
+        send(&mut app, entity, Out { hit: hit() });
+        update(&mut app, 0.05);
+        assert!(!app.world().get::<UiAnimator<Clicked>>(entity).unwrap().is_forward());
+        assert!((transition::<Clicked>(&app, entity) - 0.6).abs() < 1e-4);
+
+        send(&mut app, entity, Down { button: PointerButton::Primary, hit: hit() });
+        update(&mut app, 0.0);
+        send(&mut app, entity, Up { button: PointerButton::Primary, hit: hit() });
+        update(&mut app, 1.0);
+        assert_eq!(transition::<Clicked>(&app, entity), 0.0);
+    }
+
+    #[test]
+    fn selected_transition () {
+        let mut app = app();
+        app.add_systems(Update, (selected_toggle_system, ui_animation::<Selected>).chain());
+        let entity = app.world_mut().spawn(UiAnimator::<Selected>::new()).id();
+        let manual = app.world_mut().spawn((UiAnimator::<Selected>::new(), UiManualSelect)).id();
+
+        send(&mut app, entity, Click { button: PointerButton::Primary, hit: hit() });
+        send(&mut app, manual, Click { button: PointerButton::Primary, hit: hit() });
+        update(&mut app, 1.0);
+        assert_eq!(transition::<Selected>(&app, entity), 1.0);
+        assert_eq!(transition::<Selected>(&app, manual), 0.0);
+
+        // Selection stays until clicked again
+        update(&mut app, 1.0);
+        assert_eq!(transition::<Selected>(&app, entity), 1.0);
+        send(&mut app, entity, Click { button: PointerButton::Primary, hit: hit() });
+        update(&mut app, 1.0);
+        assert_eq!(transition::<Selected>(&app, entity), 0.0);
+    }
+
+    #[test]
+    fn intro_transition () {
+        let mut app = app();
+        app.add_systems(Update, (intro_spawn_system, ui_animation::<Intro>, ui_animation_state::<Intro>, ui_state_stack_resolve).chain());
+        let entity = app.world_mut().spawn((UiAnimator::<Intro>::new().backward_speed(2.0), UiLayout::window_full().pack::<Intro>(), UiLayoutController::default(), UiStateStack::default())).id();
+
+        // Starts in the intro state and plays back to base
+        update(&mut app, 0.0);
+        assert_eq!(transition::<Intro>(&app, entity), 1.0);
+        let controller = app.world().get::<UiLayoutController>(entity).unwrap();
+        assert_eq!((controller.index, controller.tween), ([Base::INDEX, Intro::INDEX], 1.0));
+
+        update(&mut app, 0.25);
+        assert_eq!(app.world().get::<UiLayoutController>(entity).unwrap().tween, 0.5);
+
+        update(&mut app, 0.25);
+        assert_eq!(transition::<Intro>(&app, entity), 0.0);
+        assert_eq!(app.world().get::<UiLayoutController>(entity).unwrap().tween, 0.0);
+    }
+
+    #[test]
+    fn outro_despawn () {
+        let mut app = app();
+        app.add_systems(Update, (outro_request_system, ui_animation::<Outro>, outro_despawn_system).chain());
+        let entity = app.world_mut().spawn(UiAnimator::<Outro>::new().forward_speed(2.0)).with_children(|ui| { ui.spawn_empty(); }).id();
+        let child = app.world().get::<Children>(entity).unwrap()[0];
+        let instant = app.world_mut().spawn_empty().id();
+
+        app.world_mut().commands().entity(entity).ui_despawn();
+        app.world_mut().commands().entity(instant).ui_despawn();
+        app.world_mut().flush();
+
+        // Entities without outro are despawned right away
+        update(&mut app, 0.25);
+        assert!(app.world().get_entity(instant).is_none());
+        assert_eq!(transition::<Outro>(&app, entity), 0.5);
+
+        // The rest survives until the outro finishes
+        update(&mut app, 0.2);
+        assert!(app.world().get_entity(entity).is_some());
+        update(&mut app, 0.1);
+        assert!(app.world().get_entity(entity).is_none());
+        assert!(app.world().get_entity(child).is_none());
+    }
+}
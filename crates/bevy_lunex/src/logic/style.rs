@@ -1,4 +1,5 @@
 use crate::*;
+use bevy::utils::HashMap;
 
 
 /// Color lerping functionality
@@ -14,12 +15,395 @@ impl LerpColor for Color {
 }
 
 
+// #=============#
+// #=== THEME ===#
+
+/// Palette of the status colors in [`UiTheme`]. It fills the `accent`, `success`, `warning`, `error` and `info` tokens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum UiPalette {
+    /// Regular palette
+    #[default]
+    Standard,
+    /// Okabe-Ito palette distinguishable with all common types of color blindness
+    ColorBlind,
+}
+impl UiPalette {
+    /// Returns the status colors as `(token, color)` pairs
+    pub fn get_colors(&self) -> [(&'static str, Color); 5] {
+        match self {
+            UiPalette::Standard => [
+                ("accent", Color::srgb(0.25, 0.5, 1.0)),
+                ("success", Color::srgb(0.2, 0.75, 0.3)),
+                ("warning", Color::srgb(1.0, 0.75, 0.1)),
+                ("error", Color::srgb(0.9, 0.2, 0.2)),
+                ("info", Color::srgb(0.3, 0.7, 0.9)),
+            ],
+            UiPalette::ColorBlind => [
+                ("accent", Color::srgb_u8(0, 114, 178)),
+                ("success", Color::srgb_u8(0, 158, 115)),
+                ("warning", Color::srgb_u8(230, 159, 0)),
+                ("error", Color::srgb_u8(213, 94, 0)),
+                ("info", Color::srgb_u8(86, 180, 233)),
+            ],
+        }
+    }
+}
+
+/// Resource with named style tokens. Components reference the tokens by name, for example
+/// [`UiColor::token`], [`UiTextToken`], [`UiSpacingToken`] or [`UiShape::radius_token`], and are updated
+/// across all trees when the theme changes. Colors transition to the new value over [`UiTheme::transition`] seconds.
+/// ## 🛠️ Example
+/// ```
+/// app.insert_resource(UiTheme::dark().palette(UiPalette::ColorBlind).color("panel", Color::srgb(0.1, 0.1, 0.2)));
+///
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Button"),
+///     UiLayout::div().pack::<Base>(),
+///     UiSpacingToken::new().padding("md"),
+///     UiColor::<Base>::token("surface"),
+///     UiColor::<Hover>::token("accent"),
+///     UiAnimator::<Hover>::new(),
+/// ));
+///
+/// // Switch to the light mode
+/// *theme = UiTheme::light();
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct UiTheme {
+    /// Color tokens
+    pub colors: HashMap<String, Color>,
+    /// Font tokens
+    pub fonts: HashMap<String, Handle<Font>>,
+    /// Font size tokens
+    pub font_sizes: HashMap<String, UiValueType<f32>>,
+    /// Spacing tokens
+    pub spacing: HashMap<String, UiValue<f32>>,
+    /// Corner radius tokens
+    pub radii: HashMap<String, f32>,
+    /// Duration of the color transition in seconds when the theme changes
+    pub transition: f32,
+}
+impl UiTheme {
+    /// Creates new empty theme
+    pub fn new() -> Self {
+        UiTheme {
+            colors: HashMap::new(),
+            fonts: HashMap::new(),
+            font_sizes: HashMap::new(),
+            spacing: HashMap::new(),
+            radii: HashMap::new(),
+            transition: 0.25,
+        }
+    }
+    /// Creates new dark theme with the standard palette
+    pub fn dark() -> Self {
+        UiTheme::new().with_defaults()
+            .color("background", Color::srgb(0.08, 0.08, 0.1))
+            .color("surface", Color::srgb(0.15, 0.15, 0.18))
+            .color("border", Color::srgb(0.3, 0.3, 0.35))
+            .color("text", Color::srgb(0.93, 0.93, 0.95))
+            .color("text_muted", Color::srgb(0.6, 0.6, 0.65))
+            .palette(UiPalette::Standard)
+    }
+    /// Creates new light theme with the standard palette
+    pub fn light() -> Self {
+        UiTheme::new().with_defaults()
+            .color("background", Color::srgb(0.96, 0.96, 0.97))
+            .color("surface", Color::srgb(1.0, 1.0, 1.0))
+            .color("border", Color::srgb(0.8, 0.8, 0.83))
+            .color("text", Color::srgb(0.1, 0.1, 0.12))
+            .color("text_muted", Color::srgb(0.42, 0.42, 0.47))
+            .palette(UiPalette::Standard)
+    }
+    /// Default spacing and radius tokens shared by the presets
+    fn with_defaults(self) -> Self {
+        self.spacing("xs", Ab(4.0))
+            .spacing("sm", Ab(8.0))
+            .spacing("md", Ab(16.0))
+            .spacing("lg", Ab(24.0))
+            .spacing("xl", Ab(32.0))
+            .radius("sm", 4.0)
+            .radius("md", 8.0)
+            .radius("lg", 16.0)
+    }
+    /// Replaces the status colors with the palette.
+    pub fn palette(mut self, palette: UiPalette) -> Self {
+        for (token, color) in palette.get_colors() {
+            self.colors.insert(token.to_string(), color);
+        }
+        self
+    }
+    /// Inserts the color token.
+    pub fn color(mut self, token: impl Into<String>, color: Color) -> Self {
+        self.colors.insert(token.into(), color);
+        self
+    }
+    /// Inserts the font token.
+    pub fn font(mut self, token: impl Into<String>, font: Handle<Font>) -> Self {
+        self.fonts.insert(token.into(), font);
+        self
+    }
+    /// Inserts the font size token.
+    pub fn font_size(mut self, token: impl Into<String>, size: impl Into<UiValueType<f32>>) -> Self {
+        self.font_sizes.insert(token.into(), size.into());
+        self
+    }
+    /// Inserts the spacing token.
+    pub fn spacing(mut self, token: impl Into<String>, spacing: impl Into<UiValue<f32>>) -> Self {
+        self.spacing.insert(token.into(), spacing.into());
+        self
+    }
+    /// Inserts the corner radius token.
+    pub fn radius(mut self, token: impl Into<String>, radius: f32) -> Self {
+        self.radii.insert(token.into(), radius);
+        self
+    }
+    /// Replaces the transition duration with a new value.
+    pub fn transition(mut self, transition: f32) -> Self {
+        self.transition = transition;
+        self
+    }
+    /// Returns the color token.
+    pub fn get_color(&self, token: &str) -> Option<Color> {
+        self.colors.get(token).copied()
+    }
+    /// Returns the font token.
+    pub fn get_font(&self, token: &str) -> Option<Handle<Font>> {
+        self.fonts.get(token).cloned()
+    }
+    /// Returns the font size token.
+    pub fn get_font_size(&self, token: &str) -> Option<UiValueType<f32>> {
+        self.font_sizes.get(token).copied()
+    }
+    /// Returns the spacing token.
+    pub fn get_spacing(&self, token: &str) -> Option<UiValue<f32>> {
+        self.spacing.get(token).copied()
+    }
+    /// Returns the corner radius token.
+    pub fn get_radius(&self, token: &str) -> Option<f32> {
+        self.radii.get(token).copied()
+    }
+}
+impl Default for UiTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// Sets the font and [`UiTextSize`] of the text from [`UiTheme`] tokens.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Title"),
+///     UiLayout::window().size(Rl(50.0)).pack::<Base>(),
+///     UiText2dBundle::default(),
+///     UiTextToken::new().font("heading").size("lg"),
+///     UiColor::<Base>::token("text"),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct UiTextToken {
+    /// The font token
+    pub font: Option<String>,
+    /// The font size token
+    pub size: Option<String>,
+}
+impl UiTextToken {
+    /// Creates new struct
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Replaces the font token with a new value.
+    pub fn font(mut self, token: impl Into<String>) -> Self {
+        self.font = Some(token.into());
+        self
+    }
+    /// Replaces the font size token with a new value.
+    pub fn size(mut self, token: impl Into<String>) -> Self {
+        self.size = Some(token.into());
+        self
+    }
+}
+
+/// Sets the padding and margin of [`Div`] layout in [`UiLayout<Base>`] and the gap of [`UiStack`] from [`UiTheme`] spacing tokens.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Menu"),
+///     UiLayout::div().pack::<Base>(),
+///     UiStack::new().direction(StackDirection::Vertical),
+///     UiSpacingToken::new().padding("lg").gap("sm"),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct UiSpacingToken {
+    /// The padding token
+    pub padding: Option<String>,
+    /// The margin token
+    pub margin: Option<String>,
+    /// The gap token
+    pub gap: Option<String>,
+}
+impl UiSpacingToken {
+    /// Creates new struct
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Replaces the padding token with a new value.
+    pub fn padding(mut self, token: impl Into<String>) -> Self {
+        self.padding = Some(token.into());
+        self
+    }
+    /// Replaces the margin token with a new value.
+    pub fn margin(mut self, token: impl Into<String>) -> Self {
+        self.margin = Some(token.into());
+        self
+    }
+    /// Replaces the gap token with a new value.
+    pub fn gap(mut self, token: impl Into<String>) -> Self {
+        self.gap = Some(token.into());
+        self
+    }
+}
+
+
+// #===============#
+// #=== SYSTEMS ===#
+
+/// This system resolves [`UiColor`] tokens and transitions them when [`UiTheme`] changes.
+/// It is added for all built-in states, add it yourself for custom states.
+pub fn ui_color_token_system<S: UiState>(
+    time: Res<Time>,
+    theme: Res<UiTheme>,
+    mut fading: Local<Vec<Entity>>,
+    mut query: ParamSet<(Query<(Entity, &mut UiColor<S>)>, Query<(Entity, &mut UiColor<S>), Added<UiColor<S>>>)>,
+) {
+    // Resolve all colors when the theme changes, otherwise only the new ones
+    let mut resolve = |entity: Entity, mut color: Mut<UiColor<S>>| {
+        let Some(target) = color.token.as_deref().and_then(|token| theme.get_color(token)) else { return };
+        if color.is_added() || theme.transition <= 0.0 {
+            color.color = target;
+            color.fade = None;
+        } else if color.fade.map_or(color.color, |(_, to, _)| to) != target {
+            color.fade = Some((color.color, target, 0.0));
+            if !fading.contains(&entity) { fading.push(entity); }
+        }
+    };
+    if theme.is_changed() {
+        for (entity, color) in &mut query.p0() { resolve(entity, color); }
+    } else {
+        for (entity, color) in &mut query.p1() { resolve(entity, color); }
+    }
+
+    // Advance the running transitions
+    let mut colors = query.p0();
+    fading.retain(|entity| {
+        let Ok((_, mut color)) = colors.get_mut(*entity) else { return false };
+        let Some((from, to, progress)) = color.fade else { return false };
+        let progress = (progress + time.delta_seconds() / theme.transition.max(f32::EPSILON)).min(1.0);
+        color.color = from.lerp(to, progress);
+        color.fade = if progress < 1.0 { Some((from, to, progress)) } else { None };
+        color.fade.is_some()
+    });
+}
+
+/// This system resolves [`UiShape::radius_token`] when [`UiTheme`] or the shape changes.
+/// It is added for all built-in states, add it yourself for custom states.
+pub fn ui_shape_token_system<S: UiState>(
+    theme: Res<UiTheme>,
+    mut query: ParamSet<(Query<&mut UiShape<S>>, Query<&mut UiShape<S>, Changed<UiShape<S>>>)>,
+) {
+    let resolve = |mut shape: Mut<UiShape<S>>| {
+        if let Some(radius) = shape.radius_token.as_deref().and_then(|token| theme.get_radius(token)) {
+            if shape.style.radius != Vec4::splat(radius) { shape.style.radius = Vec4::splat(radius); }
+        }
+    };
+    if theme.is_changed() {
+        query.p0().iter_mut().for_each(resolve);
+    } else {
+        query.p1().iter_mut().for_each(resolve);
+    }
+}
+
+/// This system sends the themed [`UiColor<Base>`] of entities without any [`UiAnimator`]
+fn ui_color_token_base_system(query: Query<(Entity, &UiColor<Base>), (Changed<UiColor<Base>>, Without<UiStateStack>)>, mut set_color: EventWriter<actions::SetColor>) {
+    for (entity, color) in &query {
+        if color.token.is_some() {
+            set_color.send(actions::SetColor { target: entity, color: color.color });
+        }
+    }
+}
+
+/// This system applies [`UiTextToken`] when [`UiTheme`] changes
+fn ui_text_token_system(mut commands: Commands, theme: Res<UiTheme>, mut query: Query<(Entity, Ref<UiTextToken>, &mut Text, Option<&mut UiTextSize>)>) {
+    for (entity, token, mut text, size) in &mut query {
+        if !theme.is_changed() && !token.is_changed() { continue }
+
+        if let Some(font) = token.font.as_deref().and_then(|font| theme.get_font(font)) {
+            if text.sections.iter().any(|section| section.style.font != font) {
+                for section in &mut text.sections {
+                    section.style.font = font.clone();
+                }
+            }
+        }
+        if let Some(font_size) = token.size.as_deref().and_then(|size| theme.get_font_size(size)) {
+            match size {
+                Some(mut size) => if size.size != font_size { size.size = font_size },
+                None => { commands.entity(entity).insert(UiTextSize::new().size(font_size)); },
+            }
+        }
+    }
+}
+
+
+/// This system applies [`UiSpacingToken`] when [`UiTheme`] changes
+fn ui_spacing_token_system(theme: Res<UiTheme>, mut query: Query<(Ref<UiSpacingToken>, Option<&mut UiLayout<Base>>, Option<&mut UiStack>)>) {
+    for (token, layout, stack) in &mut query {
+        if !theme.is_changed() && !token.is_changed() { continue }
+
+        if let Some(mut layout) = layout {
+            if let Layout::Div(div) = &layout.layout {
+                let mut new = *div;
+                if let Some(padding) = token.padding.as_deref().and_then(|padding| theme.get_spacing(padding)) { new.padding = padding.into(); }
+                if let Some(margin) = token.margin.as_deref().and_then(|margin| theme.get_spacing(margin)) { new.margin = margin.into(); }
+                if *div != new { layout.layout = new.into(); }
+            }
+        }
+        if let (Some(mut stack), Some(gap)) = (stack, token.gap.as_deref().and_then(|gap| theme.get_spacing(gap))) {
+            let gap: UiValue<Vec2> = gap.into();
+            if stack.gap != gap { stack.gap = gap; }
+        }
+    }
+}
+
 // #==============#
 // #=== PLUGIN ===#
 
+/// Plugin adding [`UiTheme`] logic
 pub struct StylePlugin;
 impl Plugin for StylePlugin {
-    fn build(&self, _app: &mut App) {
-        //app;
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<UiTheme>()
+            .add_systems(Update, (
+                ui_color_token_system::<Base>,
+                ui_color_token_system::<Hover>,
+                ui_color_token_system::<Clicked>,
+                ui_color_token_system::<Selected>,
+                ui_color_token_system::<Intro>,
+                ui_color_token_system::<Outro>,
+                ui_color_token_system::<Droppable>,
+            ).before(ui_state_stack_resolve))
+            .add_systems(Update, (
+                ui_shape_token_system::<Base>,
+                ui_shape_token_system::<Hover>,
+                ui_shape_token_system::<Clicked>,
+                ui_shape_token_system::<Selected>,
+                ui_shape_token_system::<Intro>,
+                ui_shape_token_system::<Outro>,
+                ui_shape_token_system::<Droppable>,
+            ).before(ui_state_stack_resolve))
+            .add_systems(Update, ui_color_token_base_system.after(ui_color_token_system::<Base>))
+            .add_systems(Update, (ui_text_token_system, ui_spacing_token_system).in_set(UiSystems::Modify).before(UiSystems::Send));
     }
 }
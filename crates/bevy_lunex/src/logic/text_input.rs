@@ -0,0 +1,640 @@
+use crate::*;
+use bevy::{input::{keyboard::{Key, KeyboardInput}, ButtonState}, sprite::Anchor, text::{update_text2d_layout, TextLayoutInfo}, window::{Ime, PrimaryWindow}};
+
+
+// #=================#
+// #=== CLIPBOARD ===#
+
+/// Backend providing clipboard access to [`UiClipboard`].
+/// Implement it to connect text inputs to the system clipboard, for example with the `arboard` crate.
+pub trait UiClipboardBackend: Send + Sync + 'static {
+    /// Returns the text stored in the clipboard.
+    fn get(&mut self) -> Option<String>;
+    /// Stores the text in the clipboard.
+    fn set(&mut self, text: String);
+}
+
+/// Clipboard backend storing the text only inside of the application. Used by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UiLocalClipboard {
+    text: Option<String>,
+}
+impl UiClipboardBackend for UiLocalClipboard {
+    fn get(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+    fn set(&mut self, text: String) {
+        self.text = Some(text);
+    }
+}
+
+/// Resource used by [`UiTextInput`] for copy, cut and paste.
+/// ## 🛠️ Example
+/// ```
+/// app.insert_resource(UiClipboard::new(MySystemClipboard));
+/// ```
+#[derive(Resource)]
+pub struct UiClipboard {
+    backend: Box<dyn UiClipboardBackend>,
+}
+impl UiClipboard {
+    /// Creates new clipboard with the given backend.
+    pub fn new(backend: impl UiClipboardBackend) -> Self {
+        UiClipboard { backend: Box::new(backend) }
+    }
+    /// Returns the text stored in the clipboard.
+    pub fn get(&mut self) -> Option<String> {
+        self.backend.get()
+    }
+    /// Stores the text in the clipboard.
+    pub fn set(&mut self, text: impl Into<String>) {
+        self.backend.set(text.into())
+    }
+}
+impl Default for UiClipboard {
+    fn default() -> Self {
+        UiClipboard::new(UiLocalClipboard::default())
+    }
+}
+
+
+// #==================#
+// #=== TEXT INPUT ===#
+
+/// Turns [`UiText2dBundle`] into editable text field. It is focused by clicking on it and edited with keyboard.
+/// Every edit sends [`UiChangeEvent`] with the new value.
+///
+/// The entity becomes [`UiFocusable`], so it can also be focused with navigation. To focus it by clicking
+/// on a background node, add [`UiClickEmitter`] pointing to this entity to the background.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Form/Name/Text"),
+///     UiLayout::window().pos(Rl((5.0, 50.0))).anchor(Anchor::CenterLeft).pack::<Base>(),
+///     UiText2dBundle {
+///         text: Text::from_section("", TextStyle { font_size: 60.0, ..default() }),
+///         ..default()
+///     },
+///     UiTextInput::new().placeholder("Your name").max_length(16),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct UiTextInput {
+    /// Text shown while the value is empty
+    pub placeholder: String,
+    /// Maximum number of characters in the value
+    pub max_length: Option<usize>,
+    /// Character replacing every character of the value, used for passwords
+    pub mask: Option<char>,
+    /// Color of the caret and the selection
+    pub caret_color: Color,
+    /// The edited text
+    value: String,
+    /// Caret position in characters
+    caret: usize,
+    /// The other end of the selection in characters
+    anchor: usize,
+    /// Text being composed by the input method
+    preedit: String,
+    /// Caret position within the composed text in bytes
+    preedit_caret: Option<usize>,
+    /// Time since the last edit, used for blinking
+    blink: f32,
+    /// Color of the text captured on spawn
+    pub(crate) color: Option<Color>,
+    /// Entities rendering the caret and the selection
+    pub(crate) visuals: Option<(Entity, Entity)>,
+}
+impl UiTextInput {
+    /// Creates new empty text input.
+    pub fn new() -> Self {
+        UiTextInput {
+            placeholder: String::new(),
+            max_length: None,
+            mask: None,
+            caret_color: Color::WHITE,
+            value: String::new(),
+            caret: 0,
+            anchor: 0,
+            preedit: String::new(),
+            preedit_caret: None,
+            blink: 0.0,
+            color: None,
+            visuals: None,
+        }
+    }
+    /// Replaces the value with a new one.
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.set_value(value);
+        self
+    }
+    /// Replaces the placeholder with a new value.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+    /// Replaces the maximum length with a new value.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self.set_value(self.value.clone());
+        self
+    }
+    /// Replaces the mask character with a new value.
+    pub fn mask(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+    /// Masks the value with `*`, used for passwords.
+    pub fn password(self) -> Self {
+        self.mask('*')
+    }
+    /// Replaces the caret color with a new value.
+    pub fn caret_color(mut self, color: Color) -> Self {
+        self.caret_color = color;
+        self
+    }
+
+    /// Returns the value.
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+    /// Replaces the value and moves the caret to the end. Does not send [`UiChangeEvent`].
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        if let Some(max) = self.max_length {
+            self.value = self.value.chars().take(max).collect();
+        }
+        self.caret = self.value.chars().count();
+        self.anchor = self.caret;
+    }
+    /// Returns the caret position in characters.
+    pub fn get_caret(&self) -> usize {
+        self.caret
+    }
+    /// Returns the selected range in characters, if any.
+    pub fn get_selection(&self) -> Option<(usize, usize)> {
+        (self.caret != self.anchor).then_some((self.caret.min(self.anchor), self.caret.max(self.anchor)))
+    }
+    /// Returns the selected text, if any.
+    pub fn get_selected_text(&self) -> Option<String> {
+        self.get_selection().map(|(start, end)| self.value.chars().skip(start).take(end - start).collect())
+    }
+    /// Selects the whole value.
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.caret = self.value.chars().count();
+    }
+
+    /// Returns the byte offset of the character index.
+    fn byte(text: &str, index: usize) -> usize {
+        text.char_indices().nth(index).map_or(text.len(), |(byte, _)| byte)
+    }
+    /// Moves the caret, extending the selection if requested.
+    fn move_caret(&mut self, caret: usize, extend: bool) {
+        self.caret = caret.min(self.value.chars().count());
+        if !extend { self.anchor = self.caret; }
+    }
+    /// Removes the selected text. Returns true if anything was removed.
+    fn delete_selection(&mut self) -> bool {
+        let Some((start, end)) = self.get_selection() else { return false };
+        self.value.replace_range(Self::byte(&self.value, start)..Self::byte(&self.value, end), "");
+        self.move_caret(start, false);
+        true
+    }
+    /// Replaces the selection with the text, respecting the maximum length. Returns true if the value changed.
+    fn insert(&mut self, text: &str) -> bool {
+        let deleted = self.delete_selection();
+        let space = self.max_length.map_or(usize::MAX, |max| max.saturating_sub(self.value.chars().count()));
+        let text: String = text.chars().filter(|c| !c.is_control()).take(space).collect();
+        if text.is_empty() { return deleted; }
+        self.value.insert_str(Self::byte(&self.value, self.caret), &text);
+        self.move_caret(self.caret + text.chars().count(), false);
+        true
+    }
+    /// Removes the selection or the character before the caret.
+    fn backspace(&mut self) -> bool {
+        if self.delete_selection() { return true; }
+        if self.caret == 0 { return false; }
+        self.anchor = self.caret - 1;
+        self.delete_selection()
+    }
+    /// Removes the selection or the character after the caret.
+    fn delete(&mut self) -> bool {
+        if self.delete_selection() { return true; }
+        if self.caret >= self.value.chars().count() { return false; }
+        self.anchor = self.caret + 1;
+        self.delete_selection()
+    }
+
+    /// Returns true if the placeholder is displayed instead of the value.
+    fn is_placeholder(&self) -> bool {
+        self.value.is_empty() && self.preedit.is_empty()
+    }
+    /// Returns the displayed text, with the value masked and the composed text inserted at the caret.
+    fn display(&self) -> String {
+        if self.is_placeholder() { return self.placeholder.clone(); }
+        let mut text = match self.mask {
+            Some(mask) => self.value.chars().map(|_| mask).collect(),
+            None => self.value.clone(),
+        };
+        let preedit = match self.mask {
+            Some(mask) => self.preedit.chars().map(|_| mask).collect(),
+            None => self.preedit.clone(),
+        };
+        text.insert_str(Self::byte(&text, self.caret), &preedit);
+        text
+    }
+    /// Returns the character index in the displayed text matching the index in the value.
+    fn display_index(&self, index: usize) -> usize {
+        if self.is_placeholder() { return 0; }
+        if index < self.caret || self.preedit.is_empty() { return index; }
+        let composed = self.preedit_caret.map_or(self.preedit.chars().count(), |byte| self.preedit[..byte.min(self.preedit.len())].chars().count());
+        if index == self.caret { index + composed } else { index + self.preedit.chars().count() }
+    }
+}
+impl Default for UiTextInput {
+    fn default() -> Self {
+        UiTextInput::new()
+    }
+}
+
+/// Returns horizontal positions of character boundaries in the laid out text, paired with their byte offsets.
+fn text_stops(text: &str, info: &TextLayoutInfo, scale: f32) -> Vec<(usize, f32)> {
+    let mut stops = Vec::new();
+    for glyph in info.glyphs.iter().filter(|glyph| glyph.section_index == 0) {
+        let Some(len) = text.get(glyph.byte_index..).and_then(|rest| rest.chars().next()).map(char::len_utf8) else { continue };
+        let half = glyph.size.x / 2.0;
+        stops.push((glyph.byte_index, (glyph.position.x - half) / scale));
+        stops.push((glyph.byte_index + len, (glyph.position.x + half) / scale));
+    }
+    if !stops.iter().any(|(byte, _)| *byte == 0) { stops.push((0, 0.0)); }
+    if !stops.iter().any(|(byte, _)| *byte == text.len()) { stops.push((text.len(), info.logical_size.x)); }
+    stops
+}
+
+/// Returns the horizontal position of the byte offset. Whitespace has no glyphs, so it is interpolated.
+fn stop_position(stops: &[(usize, f32)], byte: usize) -> f32 {
+    let exact: Vec<f32> = stops.iter().filter(|(b, _)| *b == byte).map(|(_, x)| *x).collect();
+    if !exact.is_empty() { return exact.iter().sum::<f32>() / exact.len() as f32; }
+
+    let previous = stops.iter().filter(|(b, _)| *b < byte).max_by_key(|(b, _)| *b);
+    let next = stops.iter().filter(|(b, _)| *b > byte).min_by_key(|(b, _)| *b);
+    match (previous, next) {
+        (Some((b1, x1)), Some((b2, x2))) => x1 + (x2 - x1) * (byte - b1) as f32 / (b2 - b1) as f32,
+        (Some((_, x)), None) | (None, Some((_, x))) => *x,
+        (None, None) => 0.0,
+    }
+}
+
+/// Returns the offset of the glyph space in the local space of the text entity.
+fn text_origin(info: &TextLayoutInfo, anchor: &Anchor) -> Vec2 {
+    info.logical_size * -(anchor.as_vec() + 0.5)
+}
+
+
+// #===============#
+// #=== SYSTEMS ===#
+
+/// Returns true if any of the modifier keys used for shortcuts is pressed.
+fn shortcut_pressed(keys: &ButtonInput<KeyCode>) -> bool {
+    keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight, KeyCode::SuperLeft, KeyCode::SuperRight])
+}
+
+fn ui_text_input_setup_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut UiTextInput, &Text, Has<UiFocusable>), Added<UiTextInput>>,
+) {
+    for (entity, mut input, text, is_focusable) in &mut query {
+        input.color = text.sections.first().map(|section| section.style.color);
+        if !is_focusable { commands.entity(entity).insert(UiFocusable::new()); }
+
+        // Spawn hidden sprites for the caret and the selection
+        let mut visuals = (Entity::PLACEHOLDER, Entity::PLACEHOLDER);
+        commands.entity(entity).with_children(|parent| {
+            visuals.0 = parent.spawn(SpriteBundle { visibility: Visibility::Hidden, ..default() }).id();
+            visuals.1 = parent.spawn(SpriteBundle { visibility: Visibility::Hidden, ..default() }).id();
+        });
+        input.visuals = Some(visuals);
+    }
+}
+
+/// Focuses the text input on click and places the caret under the pointer. Clicking elsewhere removes the focus.
+fn ui_text_input_click_system(
+    mut events: EventReader<Pointer<Down>>,
+    mut focus: ResMut<UiFocus>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, &OrthographicProjection)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    emitters: Query<&UiClickEmitter>,
+    mut query: Query<(&mut UiTextInput, &TextLayoutInfo, &Anchor, &GlobalTransform)>,
+) {
+    let scale = windows.get_single().map_or(1.0, |window| window.scale_factor());
+    for event in events.read() {
+        let target = emitters.get(event.target).ok().and_then(|emitter| emitter.target).unwrap_or(event.target);
+        let Ok((mut input, info, anchor, transform)) = query.get_mut(target) else {
+            if focus.get().is_some_and(|focused| query.contains(focused)) { focus.clear(); }
+            continue;
+        };
+        focus.focus(target);
+        input.blink = 0.0;
+
+        // Find the character boundary closest to the pointer
+        let Some((_, position)) = pointer_world_position(&event.pointer_location, &cameras, &primary_window) else { continue };
+        let x = node_local_position(position, transform).x - text_origin(info, anchor).x;
+        let text = input.display();
+        let stops = text_stops(&text, info, scale);
+        let caret = (0..=input.value.chars().count())
+            .min_by(|a, b| {
+                let a = stop_position(&stops, UiTextInput::byte(&text, input.display_index(*a)));
+                let b = stop_position(&stops, UiTextInput::byte(&text, input.display_index(*b)));
+                (a - x).abs().total_cmp(&(b - x).abs())
+            })
+            .unwrap_or(0);
+        input.move_caret(caret, false);
+    }
+}
+
+/// Edits the focused text input with keyboard and input method.
+pub(crate) fn ui_text_input_keyboard_system(
+    mut keyboard: EventReader<KeyboardInput>,
+    mut ime: EventReader<Ime>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut focus: ResMut<UiFocus>,
+    mut clipboard: ResMut<UiClipboard>,
+    mut query: Query<&mut UiTextInput>,
+    mut events: EventWriter<UiChangeEvent>,
+) {
+    let Some((target, mut input)) = focus.get().and_then(|entity| Some((entity, query.get_mut(entity).ok()?))) else {
+        keyboard.clear();
+        ime.clear();
+        return;
+    };
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let shortcut = shortcut_pressed(&keys);
+    let mut changed = false;
+
+    // Committed composition already contains the typed characters, so they are not inserted twice
+    let mut committed = false;
+    for event in ime.read() {
+        match event {
+            Ime::Preedit { value, cursor, .. } => {
+                input.preedit = value.clone();
+                input.preedit_caret = cursor.map(|(start, _)| start);
+            },
+            Ime::Commit { value, .. } => {
+                input.preedit.clear();
+                changed |= input.insert(value);
+                committed = true;
+            },
+            _ => {},
+        }
+    }
+
+    for event in keyboard.read() {
+        if event.state != ButtonState::Pressed { continue; }
+        let length = input.value.chars().count();
+        match &event.logical_key {
+            Key::Character(_) if shortcut => match event.key_code {
+                KeyCode::KeyA => input.select_all(),
+                KeyCode::KeyC => if let Some(text) = input.get_selected_text().filter(|_| input.mask.is_none()) { clipboard.set(text); },
+                KeyCode::KeyX => if let Some(text) = input.get_selected_text().filter(|_| input.mask.is_none()) {
+                    clipboard.set(text);
+                    changed |= input.delete_selection();
+                },
+                KeyCode::KeyV => if let Some(text) = clipboard.get() { changed |= input.insert(&text); },
+                _ => {},
+            },
+            Key::Character(c) if !committed && input.preedit.is_empty() => changed |= input.insert(c),
+            Key::Space if !committed && input.preedit.is_empty() => changed |= input.insert(" "),
+            Key::Backspace => changed |= input.backspace(),
+            Key::Delete => changed |= input.delete(),
+            Key::ArrowLeft => {
+                let caret = match input.get_selection() {
+                    Some((start, _)) if !shift => start,
+                    _ => input.caret.saturating_sub(1),
+                };
+                input.move_caret(caret, shift);
+            },
+            Key::ArrowRight => {
+                let caret = match input.get_selection() {
+                    Some((_, end)) if !shift => end,
+                    _ => input.caret + 1,
+                };
+                input.move_caret(caret, shift);
+            },
+            Key::Home => input.move_caret(0, shift),
+            Key::End => input.move_caret(length, shift),
+            Key::Escape => focus.clear(),
+            _ => continue,
+        }
+        input.blink = 0.0;
+    }
+
+    if changed {
+        events.send(UiChangeEvent { target, value: input.value.clone() });
+    }
+}
+
+/// Writes the displayed text into the [`Text`] component.
+fn ui_text_input_display_system(
+    mut query: Query<(&UiTextInput, &mut Text), Changed<UiTextInput>>,
+) {
+    for (input, mut text) in &mut query {
+        let color = input.color.unwrap_or(Color::WHITE);
+        if text.sections.is_empty() { text.sections.push(TextSection::default()); }
+        text.sections.truncate(1);
+
+        let section = &mut text.sections[0];
+        section.value = input.display();
+        section.style.color = if input.is_placeholder() { color.with_alpha(color.alpha() * 0.5) } else { color };
+    }
+}
+
+/// Places the caret and selection sprites over the laid out text and moves the input method popup to the caret.
+fn ui_text_input_caret_system(
+    time: Res<Time>,
+    focus: Res<UiFocus>,
+    mut enabled: Local<bool>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut query: Query<(Entity, &mut UiTextInput, &Text, &TextLayoutInfo, &Anchor, &GlobalTransform)>,
+    mut visuals: Query<(&mut Sprite, &mut Transform, &mut Visibility)>,
+) {
+    let Ok(mut window) = windows.get_single_mut() else { return };
+    let scale = window.scale_factor();
+    let mut editing = false;
+
+    for (entity, mut input, text, info, anchor, transform) in &mut query {
+        let Some((caret, selection)) = input.visuals else { continue };
+        let focused = focus.get() == Some(entity);
+        input.bypass_change_detection().blink += time.delta_seconds();
+
+        let display = input.display();
+        let stops = text_stops(&display, info, scale);
+        let position = |index: usize| stop_position(&stops, UiTextInput::byte(&display, input.display_index(index)));
+        let origin = text_origin(info, anchor);
+        let font_size = text.sections.first().map_or(0.0, |section| section.style.font_size);
+        let height = if info.logical_size.y > 0.0 { info.logical_size.y } else { font_size };
+        let y = origin.y + height / 2.0;
+
+        // The caret blinks, but stays visible while typing
+        if let Ok((mut sprite, mut sprite_transform, mut visibility)) = visuals.get_mut(caret) {
+            let x = origin.x + position(input.caret);
+            sprite.color = input.caret_color;
+            sprite.custom_size = Some(Vec2::new((height * 0.06).max(1.0), height));
+            sprite_transform.translation = Vec3::new(x, y, 0.01);
+            *visibility = if focused && input.get_selection().is_none() && input.blink % 1.0 < 0.5 { Visibility::Inherited } else { Visibility::Hidden };
+
+            // Move the input method popup under the caret
+            if focused {
+                editing = true;
+                let world = transform.transform_point(Vec3::new(x, origin.y, 0.0));
+                if let Some(viewport) = cameras.iter().filter(|(camera, _)| camera.is_active).find_map(|(camera, camera_transform)| camera.world_to_viewport(camera_transform, world)) {
+                    if window.ime_position != viewport { window.ime_position = viewport; }
+                }
+            }
+        }
+
+        if let Ok((mut sprite, mut sprite_transform, mut visibility)) = visuals.get_mut(selection) {
+            let Some((start, end)) = input.get_selection().filter(|_| focused) else {
+                *visibility = Visibility::Hidden;
+                continue;
+            };
+            let (start, end) = (position(start), position(end));
+            sprite.color = input.caret_color.with_alpha(input.caret_color.alpha() * 0.35);
+            sprite.custom_size = Some(Vec2::new(end - start, height));
+            sprite_transform.translation = Vec3::new(origin.x + (start + end) / 2.0, y, -0.01);
+            *visibility = Visibility::Inherited;
+        }
+    }
+
+    // Enable the input method only while editing
+    if *enabled != editing {
+        *enabled = editing;
+        window.ime_enabled = editing;
+    }
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// Plugin adding editing logic for [`UiTextInput`]
+pub struct TextInputPlugin;
+impl Plugin for TextInputPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<UiClipboard>()
+            .add_systems(Update, (
+                ui_text_input_setup_system,
+                ui_text_input_click_system,
+                ui_text_input_keyboard_system,
+                ui_text_input_display_system,
+            ).chain().in_set(UiSystems::Modify).before(UiSystems::Send))
+            .add_systems(PostUpdate, ui_text_input_caret_system.after(update_text2d_layout).before(TransformSystem::TransformPropagate));
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::UiTextInput;
+
+    #[test]
+    fn insert_max_length () {
+        let mut input = UiTextInput::new().max_length(5).value("abc");
+        assert!(input.insert("defgh"));
+        assert_eq!(input.get_value(), "abcde");
+        assert_eq!(input.get_caret(), 5);
+
+        // Full input does not change
+        assert!(!input.insert("x"));
+        assert_eq!(input.get_value(), "abcde");
+
+        // Control characters are dropped
+        let mut input = UiTextInput::new();
+        assert!(input.insert("a\nb\tc"));
+        assert_eq!(input.get_value(), "abc");
+    }
+
+    #[test]
+    fn backspace_delete_utf8 () {
+        let mut input = UiTextInput::new().value("aé😀ß");
+        assert!(input.backspace());
+        assert_eq!(input.get_value(), "aé😀");
+        assert!(input.backspace());
+        assert_eq!(input.get_value(), "aé");
+        assert_eq!(input.get_caret(), 2);
+
+        // Delete at the end does nothing, in the middle removes the next character
+        assert!(!input.delete());
+        input.move_caret(1, false);
+        assert!(input.delete());
+        assert_eq!(input.get_value(), "a");
+        assert_eq!(input.get_caret(), 1);
+
+        input.move_caret(0, false);
+        assert!(!input.backspace());
+        assert!(input.delete());
+        assert_eq!(input.get_value(), "");
+    }
+
+    #[test]
+    fn selection_replace () {
+        let mut input = UiTextInput::new().value("héllo wörld");
+        input.move_caret(6, false);
+        input.move_caret(11, true);
+        assert_eq!(input.get_selected_text().as_deref(), Some("wörld"));
+
+        // Typing replaces the selection
+        assert!(input.insert("ünïcode"));
+        assert_eq!(input.get_value(), "héllo ünïcode");
+        assert_eq!(input.get_selection(), None);
+        assert_eq!(input.get_caret(), 13);
+
+        // Backspace removes only the selection
+        input.move_caret(0, false);
+        input.move_caret(2, true);
+        assert!(input.backspace());
+        assert_eq!(input.get_value(), "llo ünïcode");
+
+        // Selection replace respects the maximum length
+        let mut input = UiTextInput::new().max_length(4).value("abcd");
+        input.move_caret(1, false);
+        input.move_caret(3, true);
+        assert!(input.delete_selection());
+        assert_eq!(input.get_value(), "ad");
+        input.select_all();
+        assert!(input.insert("wxyz!"));
+        assert_eq!(input.get_value(), "wxyz");
+    }
+
+    #[test]
+    fn display_index_preedit () {
+        let mut input = UiTextInput::new().value("ab");
+        input.move_caret(1, false);
+        assert_eq!(input.display_index(2), 2);
+
+        // Composed text is inserted at the caret
+        input.preedit = "日本語".to_string();
+        input.preedit_caret = Some("日本".len());
+        assert_eq!(input.display(), "a日本語b");
+        assert_eq!(input.display_index(0), 0);
+        assert_eq!(input.display_index(1), 3);
+        assert_eq!(input.display_index(2), 5);
+
+        // Without the caret the composed text is passed
+        input.preedit_caret = None;
+        assert_eq!(input.display_index(1), 4);
+
+        // Placeholder is displayed for empty input
+        let mut input = UiTextInput::new().placeholder("Name");
+        assert_eq!(input.display_index(0), 0);
+        input.preedit = "ñ".to_string();
+        assert_eq!(input.display(), "ñ");
+        assert_eq!(input.display_index(0), 1);
+    }
+}
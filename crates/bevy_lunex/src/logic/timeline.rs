@@ -0,0 +1,398 @@
+use crate::*;
+use lunex_engine::NodeDataTrait;
+
+
+// #==============#
+// #=== EVENTS ===#
+
+/// This is an event you can listen to which broadcasts the progress of [`UiTimeline`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiTimelineEvent {
+    /// The entity with the timeline
+    pub target: Entity,
+    /// What happened
+    pub kind: UiTimelineEventKind,
+}
+
+/// The kind of [`UiTimelineEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum UiTimelineEventKind {
+    /// The timeline started playing from the beginning
+    Started,
+    /// The timeline reached the keyframe with this index
+    Keyframe(usize),
+    /// The timeline finished, only sent in [`UiTimelineMode::Once`]
+    Completed,
+}
+
+
+// #================#
+// #=== TIMELINE ===#
+
+/// A single pose of [`UiTimeline`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct UiKeyframe {
+    /// The layout of this pose
+    pub layout: Layout,
+    /// How long it takes to tween into this pose in seconds
+    pub duration: f32,
+    /// How long to wait before tweening into this pose in seconds
+    pub delay: f32,
+    /// The easing curve of the tween into this pose
+    pub ease: UiEase,
+}
+impl UiKeyframe {
+    /// Creates new keyframe
+    pub fn new(layout: impl Into<Layout>, duration: f32) -> Self {
+        UiKeyframe {
+            layout: layout.into(),
+            duration,
+            delay: 0.0,
+            ease: UiEase::Linear,
+        }
+    }
+    /// Replaces the delay with a new value.
+    pub fn delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+    /// Replaces the easing curve with a new value.
+    pub fn ease(mut self, ease: UiEase) -> Self {
+        self.ease = ease;
+        self
+    }
+}
+
+/// How [`UiTimeline`] behaves after it reaches the last keyframe
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum UiTimelineMode {
+    /// Stops at the last keyframe
+    #[default]
+    Once,
+    /// Tweens from the last keyframe back to the first one and repeats
+    Loop,
+    /// Plays backwards to the first keyframe and repeats
+    PingPong,
+}
+
+/// This struct sequences multiple layout keyframes and writes them into [`UiLayoutController`].
+/// The first keyframe is the starting pose, each next keyframe is tweened into after its delay.
+/// Progress is broadcasted with [`UiTimelineEvent`].
+///
+/// Keyframe layouts are stored in the node from index [`UiTimeline::LAYOUT_INDEX`]. The timeline
+/// takes over [`UiLayoutController`], so don't combine it with layout of other [`UiState`]s on the same entity.
+/// ## 🛠️ Example
+/// ```
+/// UiTimeline::new(vec![
+///     UiKeyframe::new(UiLayout::window_full().y(Rl(0.0)), 0.0),
+///     UiKeyframe::new(UiLayout::window_full().y(Rl(-5.0)), 0.4).ease(UiEase::OutQuad),
+///     UiKeyframe::new(UiLayout::window_full().y(Rl(0.0)), 0.4).ease(UiEase::OutBounce).delay(0.1),
+/// ]).mode(UiTimelineMode::Loop),
+/// UiLayoutController::default(),
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+pub struct UiTimeline {
+    /// The poses to sequence
+    pub keyframes: Vec<UiKeyframe>,
+    /// What happens after the last keyframe
+    pub mode: UiTimelineMode,
+    /// Setting this to false will pause the timeline
+    pub playing: bool,
+    /// The keyframe currently being tweened into
+    edge: usize,
+    /// Time spent on the current keyframe
+    time: f32,
+    /// If the timeline is playing backwards
+    reverse: bool,
+    /// If the timeline was already played once
+    repeated: bool,
+    /// If the timeline has sent the started event
+    started: bool,
+}
+impl UiTimeline {
+    /// Layout index of the first keyframe in the node
+    pub const LAYOUT_INDEX: usize = 1000;
+
+    /// Creates new timeline that starts playing right away
+    pub fn new(keyframes: Vec<UiKeyframe>) -> Self {
+        UiTimeline {
+            keyframes,
+            mode: UiTimelineMode::Once,
+            playing: true,
+            edge: 0,
+            time: 0.0,
+            reverse: false,
+            repeated: false,
+            started: false,
+        }
+    }
+    /// Replaces the mode with a new value.
+    pub fn mode(mut self, mode: UiTimelineMode) -> Self {
+        self.mode = mode;
+        self
+    }
+    /// Sets if the timeline starts playing right away.
+    pub fn playing(mut self, playing: bool) -> Self {
+        self.playing = playing;
+        self
+    }
+    /// Starts playing the timeline from the beginning.
+    pub fn restart(&mut self) {
+        self.playing = true;
+        self.edge = 0;
+        self.time = 0.0;
+        self.reverse = false;
+        self.repeated = false;
+        self.started = false;
+    }
+    /// Returns the index of the keyframe the timeline is currently at or tweening into.
+    pub fn get_keyframe(&self) -> usize {
+        if self.reverse { self.edge.saturating_sub(1) } else { self.edge }
+    }
+
+    /// Layout indexes and tween of the current position
+    fn resolve(&self) -> ([usize; 2], f32) {
+        let n = self.keyframes.len();
+        let Some(key) = self.keyframes.get(self.edge) else { return ([Self::LAYOUT_INDEX; 2], 0.0) };
+        let from = match self.edge {
+            0 if self.repeated && self.mode == UiTimelineMode::Loop => n - 1,
+            0 => 0,
+            edge => edge - 1,
+        };
+        let time = if self.reverse { key.delay + key.duration - self.time } else { self.time };
+        let progress = if key.duration > 0.0 { ((time - key.delay) / key.duration).clamp(0.0, 1.0) } else if time >= key.delay { 1.0 } else { 0.0 };
+        ([Self::LAYOUT_INDEX + from, Self::LAYOUT_INDEX + self.edge], key.ease.ease(progress))
+    }
+
+    /// Advances the timeline and returns reached events
+    fn advance(&mut self, delta: f32) -> Vec<UiTimelineEventKind> {
+        let mut events = Vec::new();
+        let n = self.keyframes.len();
+        if n == 0 { return events; }
+        self.edge = self.edge.min(n - 1);
+        if !self.started {
+            self.started = true;
+            events.push(UiTimelineEventKind::Started);
+        }
+
+        // A timeline without any length can't repeat
+        let mode = match self.mode {
+            _ if self.keyframes.iter().all(|key| key.delay + key.duration <= 0.0) => UiTimelineMode::Once,
+            UiTimelineMode::PingPong if n == 1 => UiTimelineMode::Loop,
+            mode => mode,
+        };
+        // Only ping pong with at least two keyframes can play backwards
+        if mode != UiTimelineMode::PingPong || self.edge == 0 { self.reverse = false; }
+
+        self.time += delta;
+        for _ in 0..n * 2 {
+            let key = &self.keyframes[self.edge];
+            let length = key.delay + key.duration;
+            if self.time < length { break; }
+            self.time -= length;
+
+            if !self.reverse {
+                events.push(UiTimelineEventKind::Keyframe(self.edge));
+                if self.edge + 1 < n {
+                    self.edge += 1;
+                    continue;
+                }
+                match mode {
+                    UiTimelineMode::Once => {
+                        self.playing = false;
+                        self.time = length;
+                        events.push(UiTimelineEventKind::Completed);
+                        break;
+                    },
+                    UiTimelineMode::Loop => {
+                        self.edge = 0;
+                        self.repeated = true;
+                    },
+                    UiTimelineMode::PingPong => {
+                        self.reverse = true;
+                    },
+                }
+            } else {
+                events.push(UiTimelineEventKind::Keyframe(self.edge - 1));
+                if self.edge > 1 {
+                    self.edge -= 1;
+                } else {
+                    self.reverse = false;
+                    self.repeated = true;
+                    self.edge = 1;
+                }
+            }
+        }
+        events
+    }
+}
+fn ui_timeline_system(time: Res<Time>, mut query: Query<(&mut UiTimeline, &mut UiLayoutController, Entity)>, mut events: EventWriter<UiTimelineEvent>) {
+    for (mut timeline, mut controller, entity) in &mut query {
+        if !timeline.playing { continue }
+
+        // Playback state shouldn't trigger resending of the keyframes
+        let timeline = timeline.bypass_change_detection();
+        for kind in timeline.advance(time.delta_seconds()) {
+            events.send(UiTimelineEvent { target: entity, kind });
+        }
+
+        let (index, tween) = timeline.resolve();
+        if controller.index != index || controller.tween != tween {
+            controller.index = index;
+            controller.tween = tween;
+        }
+    }
+}
+
+/// This system takes [`UiTimeline`] keyframes and overwrites coresponding [`UiTree`] data.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn send_timeline_to_node<T:Component, N:Default + Component>(
+    mut uis: Query<(&mut UiTree<T, N>, &Children)>,
+    query: Query<(&UiLink<T>, &UiTimeline), (Changed<UiTimeline>, Without<UiTree<T, N>>)>,
+) {
+    for (mut ui, children) in &mut uis {
+        for child in children {
+            // If child matches
+            if let Ok((link, timeline)) = query.get(*child) {
+                // If node exists
+                if let Ok(node) = ui.borrow_or_create_ui_node_mut(link.path.clone()) {
+                    //Should always be Some but just in case
+                    if let Some(container) = node.obtain_data_mut() {
+                        #[cfg(feature = "verbose")]
+                        info!("{} {} - Received Timeline data", "->".blue(), link.path.yellow().bold());
+                        for (i, key) in timeline.keyframes.iter().enumerate() {
+                            container.layout.insert(UiTimeline::LAYOUT_INDEX + i, key.layout);
+                        }
+                        container.mark_dirty();
+                    }
+                }
+            }
+        }
+    }
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// Plugin adding [`UiTimeline`] playback
+pub struct TimelinePlugin;
+impl Plugin for TimelinePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<UiTimelineEvent>()
+            .add_systems(Update, ui_timeline_system.in_set(UiSystems::Modify).before(UiSystems::Send));
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use UiTimelineEventKind::*;
+
+    fn key(duration: f32) -> UiKeyframe {
+        UiKeyframe::new(ui::Window::new(), duration)
+    }
+
+    #[test]
+    fn timeline_once () {
+        let mut timeline = UiTimeline::new(vec![key(0.0), key(1.0), key(1.0)]);
+        assert_eq!(timeline.advance(0.0), vec![Started, Keyframe(0)]);
+        assert_eq!(timeline.resolve(), ([1000, 1001], 0.0));
+
+        assert_eq!(timeline.advance(0.5), vec![]);
+        assert_eq!(timeline.resolve(), ([1000, 1001], 0.5));
+
+        assert_eq!(timeline.advance(0.6), vec![Keyframe(1)]);
+        assert_eq!(timeline.get_keyframe(), 2);
+        assert_eq!(timeline.advance(1.0), vec![Keyframe(2), Completed]);
+        assert_eq!(timeline.resolve(), ([1001, 1002], 1.0));
+        assert!(!timeline.playing);
+    }
+
+    #[test]
+    fn timeline_loop () {
+        let mut timeline = UiTimeline::new(vec![key(1.0), key(1.0)]).mode(UiTimelineMode::Loop);
+        assert_eq!(timeline.advance(1.0), vec![Started, Keyframe(0)]);
+        assert_eq!(timeline.advance(1.0), vec![Keyframe(1)]);
+
+        // The first keyframe is tweened into from the last one
+        assert_eq!(timeline.resolve(), ([1001, 1000], 0.0));
+        assert_eq!(timeline.advance(0.5), vec![]);
+        assert_eq!(timeline.resolve(), ([1001, 1000], 0.5));
+        assert_eq!(timeline.advance(0.5), vec![Keyframe(0)]);
+        assert!(timeline.playing);
+    }
+
+    #[test]
+    fn timeline_ping_pong () {
+        let mut timeline = UiTimeline::new(vec![key(0.0), key(1.0), key(1.0)]).mode(UiTimelineMode::PingPong);
+        assert_eq!(timeline.advance(0.0), vec![Started, Keyframe(0)]);
+        assert_eq!(timeline.advance(1.0), vec![Keyframe(1)]);
+        assert_eq!(timeline.advance(1.0), vec![Keyframe(2)]);
+
+        // Playing backwards
+        assert_eq!(timeline.get_keyframe(), 1);
+        assert_eq!(timeline.resolve(), ([1001, 1002], 1.0));
+        assert_eq!(timeline.advance(0.5), vec![]);
+        assert_eq!(timeline.resolve(), ([1001, 1002], 0.5));
+        assert_eq!(timeline.advance(0.5), vec![Keyframe(1)]);
+        assert_eq!(timeline.resolve(), ([1000, 1001], 1.0));
+
+        // Playing forward again
+        assert_eq!(timeline.advance(1.0), vec![Keyframe(0)]);
+        assert_eq!(timeline.resolve(), ([1000, 1001], 0.0));
+        assert_eq!(timeline.get_keyframe(), 1);
+    }
+
+    #[test]
+    fn timeline_delay () {
+        let mut timeline = UiTimeline::new(vec![key(0.0), key(1.0).delay(0.5)]);
+        assert_eq!(timeline.advance(0.0), vec![Started, Keyframe(0)]);
+        assert_eq!(timeline.advance(0.25), vec![]);
+        assert_eq!(timeline.resolve(), ([1000, 1001], 0.0));
+        assert_eq!(timeline.advance(0.75), vec![]);
+        assert_eq!(timeline.resolve(), ([1000, 1001], 0.5));
+        assert_eq!(timeline.advance(0.5), vec![Keyframe(1), Completed]);
+    }
+
+    #[test]
+    fn timeline_zero_length () {
+        // Timeline without any length completes right away instead of looping forever
+        let mut timeline = UiTimeline::new(vec![key(0.0), key(0.0)]).mode(UiTimelineMode::Loop);
+        assert_eq!(timeline.advance(0.0), vec![Started, Keyframe(0), Keyframe(1), Completed]);
+        assert_eq!(timeline.resolve(), ([1000, 1001], 1.0));
+        assert!(!timeline.playing);
+
+        let mut timeline = UiTimeline::new(vec![]);
+        assert_eq!(timeline.advance(1.0), vec![]);
+        assert_eq!(timeline.resolve(), ([1000, 1000], 0.0));
+    }
+
+    #[test]
+    fn timeline_reverse_reset () {
+        let reversing = || {
+            let mut timeline = UiTimeline::new(vec![key(0.0), key(1.0), key(1.0)]).mode(UiTimelineMode::PingPong);
+            timeline.advance(2.0);
+            timeline.advance(0.5);
+            assert_eq!(timeline.get_keyframe(), 1);
+            timeline
+        };
+
+        // Cutting the keyframes while playing backwards
+        let mut timeline = reversing();
+        timeline.keyframes.truncate(1);
+        assert_eq!(timeline.advance(1.0), vec![Keyframe(0), Completed]);
+
+        // Switching the mode while playing backwards
+        let mut timeline = reversing();
+        timeline.mode = UiTimelineMode::Once;
+        assert_eq!(timeline.advance(0.5), vec![Keyframe(2), Completed]);
+    }
+}
@@ -0,0 +1,344 @@
+use crate::*;
+use bevy::{asset::load_internal_asset, render::{primitives::Aabb, render_resource::{AsBindGroup, ShaderRef, ShaderType}}, sprite::{Material2d, Material2dPlugin, Mesh2dHandle}};
+
+
+// #===============#
+// #=== SHADERS ===#
+
+const UI_SHAPE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(0x6c75_6e65_7873_6861_7065_0000_0000_0001);
+const UI_SHAPE_2D_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(0x6c75_6e65_7873_6861_7065_0000_0000_0002);
+const UI_SHAPE_3D_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(0x6c75_6e65_7873_6861_7065_0000_0000_0003);
+
+
+// #=============#
+// #=== SHAPE ===#
+
+/// Parameters of the shape drawn by [`UiShapeMaterial`] and [`UiShapeMaterial3d`].
+/// All lengths are in the node space, the offset `y` points down like in layouts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UiShapeStyle {
+    /// The fill color
+    pub color: Color,
+    /// The second fill color, the fill is a gradient if set
+    pub gradient: Option<Color>,
+    /// The gradient direction in radians, `0.0` goes from left to right
+    pub gradient_angle: f32,
+    /// The corner radius. `x-top-left`, `y-top-right`, `z-bottom-right`, `w-bottom-left`
+    pub radius: Vec4,
+    /// The border width, drawn inside the shape
+    pub border: f32,
+    /// The border color
+    pub border_color: Color,
+    /// The drop shadow color
+    pub shadow_color: Color,
+    /// The drop shadow offset
+    pub shadow_offset: Vec2,
+    /// The drop shadow blur
+    pub shadow_blur: f32,
+}
+impl UiShapeStyle {
+    /// Blends all parameters with other style
+    pub fn lerp(&self, other: UiShapeStyle, value: f32) -> UiShapeStyle {
+        UiShapeStyle {
+            color: self.color.lerp(other.color, value),
+            gradient: Some(self.get_gradient().lerp(other.get_gradient(), value)),
+            gradient_angle: self.gradient_angle.lerp(other.gradient_angle, value),
+            radius: self.radius.lerp(other.radius, value),
+            border: self.border.lerp(other.border, value),
+            border_color: self.border_color.lerp(other.border_color, value),
+            shadow_color: self.shadow_color.lerp(other.shadow_color, value),
+            shadow_offset: self.shadow_offset.lerp(other.shadow_offset, value),
+            shadow_blur: self.shadow_blur.lerp(other.shadow_blur, value),
+        }
+    }
+    /// Returns the second fill color
+    pub fn get_gradient(&self) -> Color {
+        self.gradient.unwrap_or(self.color)
+    }
+    /// Returns how far the drop shadow reaches outside the shape
+    pub fn get_margin(&self) -> f32 {
+        if self.shadow_color.alpha() == 0.0 { return 0.0 }
+        self.shadow_blur + self.shadow_offset.abs().max_element()
+    }
+}
+impl Default for UiShapeStyle {
+    fn default() -> Self {
+        UiShapeStyle {
+            color: Color::WHITE,
+            gradient: None,
+            gradient_angle: 0.0,
+            radius: Vec4::ZERO,
+            border: 0.0,
+            border_color: Color::BLACK,
+            shadow_color: Color::NONE,
+            shadow_offset: Vec2::ZERO,
+            shadow_blur: 0.0,
+        }
+    }
+}
+
+/// Shape drawn by [`UiShape2dBundle`] and [`UiShape3dBundle`]. Its size comes from [`Dimension`].
+///
+/// Shapes of other states are blended through [`UiStateStack`] like [`UiColor`].
+/// The mesh is grown to fit the drop shadow of [`UiShape<Base>`], so set the largest shadow there.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Button"),
+///     UiLayout::window().size(Rl(20.0)).pack::<Base>(),
+///     UiShape2dBundle::new(&mut materials),
+///     UiShape::<Base>::new().color(Color::srgb(0.2, 0.2, 0.3)).radius(12.0).shadow(Color::srgba(0.0, 0.0, 0.0, 0.5), Vec2::new(0.0, 4.0), 8.0),
+///     UiShape::<Hover>::new().color(Color::srgb(0.3, 0.3, 0.5)).radius(16.0).border(2.0, Color::WHITE),
+///     UiAnimator::<Hover>::new(),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct UiShape<S: UiState = Base> {
+    /// The shape parameters
+    pub style: UiShapeStyle,
+    /// The [`UiTheme`] corner radius token, it overwrites the radius when set
+    pub radius_token: Option<String>,
+    /// Phantom data
+    phantom: PhantomData<S>,
+}
+impl <S: UiState> UiShape<S> {
+    /// Creates new white rectangle
+    pub fn new() -> Self {
+        UiShape { style: UiShapeStyle::default(), radius_token: None, phantom: PhantomData }
+    }
+    /// Replaces the fill color with a new value.
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.color = color;
+        self
+    }
+    /// Replaces the fill with a gradient to the color in the direction.
+    pub fn gradient(mut self, color: Color, angle: f32) -> Self {
+        self.style.gradient = Some(color);
+        self.style.gradient_angle = angle;
+        self
+    }
+    /// Replaces all corner radii with a new value.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.style.radius = Vec4::splat(radius);
+        self
+    }
+    /// Replaces the corner radii with new values. `x-top-left`, `y-top-right`, `z-bottom-right`, `w-bottom-left`
+    pub fn radii(mut self, radius: impl Into<Vec4>) -> Self {
+        self.style.radius = radius.into();
+        self
+    }
+    /// Replaces all corner radii with the [`UiTheme`] radius token.
+    pub fn radius_token(mut self, token: impl Into<String>) -> Self {
+        self.radius_token = Some(token.into());
+        self
+    }
+    /// Replaces the border with a new value.
+    pub fn border(mut self, width: f32, color: Color) -> Self {
+        self.style.border = width;
+        self.style.border_color = color;
+        self
+    }
+    /// Replaces the drop shadow with a new value.
+    pub fn shadow(mut self, color: Color, offset: impl Into<Vec2>, blur: f32) -> Self {
+        self.style.shadow_color = color;
+        self.style.shadow_offset = offset.into();
+        self.style.shadow_blur = blur;
+        self
+    }
+    /// Returns the same shape for a different state.
+    pub fn state<N: UiState>(&self) -> UiShape<N> {
+        UiShape { style: self.style, radius_token: self.radius_token.clone(), phantom: PhantomData }
+    }
+}
+impl <S: UiState> Default for UiShape<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+// #=================#
+// #=== MATERIALS ===#
+
+pub use uniform::UiShapeUniform;
+#[allow(dead_code)] // Newer compilers report the checks derived by `ShaderType` as unused
+mod uniform {
+    use super::*;
+
+    /// Shader data of [`UiShapeStyle`]
+    #[derive(ShaderType, Debug, Clone, Copy, Default, PartialEq)]
+    pub struct UiShapeUniform {
+        pub color: LinearRgba,
+        pub gradient: LinearRgba,
+        pub border_color: LinearRgba,
+        pub shadow_color: LinearRgba,
+        pub radius: Vec4,
+        pub size: Vec2,
+        pub shadow_offset: Vec2,
+        pub gradient_angle: f32,
+        pub border: f32,
+        pub shadow_blur: f32,
+        pub margin: f32,
+    }
+}
+impl UiShapeUniform {
+    /// Creates new uniform for the node size and mesh margin
+    pub fn new(style: &UiShapeStyle, size: Vec2, margin: f32) -> Self {
+        UiShapeUniform {
+            color: style.color.into(),
+            gradient: style.get_gradient().into(),
+            border_color: style.border_color.into(),
+            shadow_color: style.shadow_color.into(),
+            radius: style.radius,
+            size,
+            shadow_offset: style.shadow_offset,
+            gradient_angle: style.gradient_angle,
+            border: style.border,
+            shadow_blur: style.shadow_blur,
+            margin,
+        }
+    }
+}
+
+/// 2D material drawing [`UiShape`] with a signed distance field
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone, Default)]
+pub struct UiShapeMaterial {
+    #[uniform(0)]
+    pub shape: UiShapeUniform,
+}
+impl Material2d for UiShapeMaterial {
+    fn fragment_shader() -> ShaderRef {
+        UI_SHAPE_2D_SHADER_HANDLE.into()
+    }
+}
+
+/// 3D material drawing [`UiShape`] with a signed distance field
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone, Default)]
+pub struct UiShapeMaterial3d {
+    #[uniform(0)]
+    pub shape: UiShapeUniform,
+}
+impl Material for UiShapeMaterial3d {
+    fn fragment_shader() -> ShaderRef {
+        UI_SHAPE_3D_SHADER_HANDLE.into()
+    }
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+}
+
+
+// #===============#
+// #=== BUNDLES ===#
+
+/// Additional bundle for `UiNode` entity.
+/// Provides functionality to bind [`UiShape`] in 2D to `UiNode`.
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct UiShape2dBundle {
+    /// Quad mesh that is generated every time node is changed.
+    pub mesh: Mesh2dHandle,
+    /// The material drawing the shape.
+    pub material: Handle<UiShapeMaterial>,
+    /// Marks this as node element.
+    pub element: Element,
+    /// Contains the ui node size.
+    pub dimension: Dimension,
+    /// The visibility of the entity.
+    pub visibility: Visibility,
+    /// The inherited visibility of the entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// The view visibility of the entity.
+    pub view_visibility: ViewVisibility,
+    /// The transform of the entity.
+    pub transform: Transform,
+    /// The global transform of the entity.
+    pub global_transform: GlobalTransform,
+}
+impl UiShape2dBundle {
+    pub fn new(materials: &mut ResMut<'_, Assets<UiShapeMaterial>>) -> Self {
+        UiShape2dBundle {
+            material: materials.add(UiShapeMaterial::default()),
+            ..default()
+        }
+    }
+}
+
+/// Additional bundle for `UiNode` entity.
+/// Provides functionality to bind [`UiShape`] in 3D on a plane mesh to `UiNode`.
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct UiShape3dBundle {
+    /// Quad mesh that is generated every time node is changed.
+    pub mesh: Handle<Mesh>,
+    /// The material drawing the shape.
+    pub material: Handle<UiShapeMaterial3d>,
+    /// Image boundary for culling.
+    pub aabb: Aabb,
+    /// Marks this as node element.
+    pub element: Element,
+    /// Contains the ui node size.
+    pub dimension: Dimension,
+    /// The visibility of the entity.
+    pub visibility: Visibility,
+    /// The inherited visibility of the entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// The view visibility of the entity.
+    pub view_visibility: ViewVisibility,
+    /// The transform of the entity.
+    pub transform: Transform,
+    /// The global transform of the entity.
+    pub global_transform: GlobalTransform,
+}
+impl UiShape3dBundle {
+    pub fn new(materials: &mut ResMut<'_, Assets<UiShapeMaterial3d>>) -> Self {
+        UiShape3dBundle {
+            material: materials.add(UiShapeMaterial3d::default()),
+            ..default()
+        }
+    }
+}
+
+
+// #===============#
+// #=== SYSTEMS ===#
+
+/// This system blends [`UiShape`] of all active states and overwrites the material parameters.
+fn ui_shape_resolve(
+    mut materials2d: ResMut<Assets<UiShapeMaterial>>,
+    mut materials3d: ResMut<Assets<UiShapeMaterial3d>>,
+    query: Query<(&UiShape, &Dimension, Option<&UiStateStack>, Option<&Handle<UiShapeMaterial>>, Option<&Handle<UiShapeMaterial3d>>), Or<(Changed<UiShape>, Changed<Dimension>, Changed<UiStateStack>)>>,
+) {
+    for (base, dimension, stack, material2d, material3d) in &query {
+        let style = stack.map_or(base.style, |stack| stack.blend_shape(base.style));
+        let shape = UiShapeUniform::new(&style, dimension.size, base.style.get_margin());
+
+        if let Some(handle) = material2d {
+            if materials2d.get(handle).is_some_and(|material| material.shape != shape) {
+                if let Some(material) = materials2d.get_mut(handle) { material.shape = shape; }
+            }
+        }
+        if let Some(handle) = material3d {
+            if materials3d.get(handle).is_some_and(|material| material.shape != shape) {
+                if let Some(material) = materials3d.get_mut(handle) { material.shape = shape; }
+            }
+        }
+    }
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// Plugin adding [`UiShapeMaterial`] and [`UiShapeMaterial3d`]
+pub struct UiMaterialPlugin;
+impl Plugin for UiMaterialPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, UI_SHAPE_SHADER_HANDLE, "shaders/shape.wgsl", Shader::from_wgsl);
+        load_internal_asset!(app, UI_SHAPE_2D_SHADER_HANDLE, "shaders/shape2d.wgsl", Shader::from_wgsl);
+        load_internal_asset!(app, UI_SHAPE_3D_SHADER_HANDLE, "shaders/shape3d.wgsl", Shader::from_wgsl);
+
+        app
+            .add_plugins(Material2dPlugin::<UiShapeMaterial>::default())
+            .add_plugins(MaterialPlugin::<UiShapeMaterial3d>::default())
+            .add_systems(Update, ui_shape_resolve.in_set(UiSystems::Process).after(UiSystems::Fetch));
+    }
+}
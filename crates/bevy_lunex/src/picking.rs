@@ -2,10 +2,10 @@ use bevy::prelude::*;
 use bevy_mod_picking::{backend::PointerHits, prelude::*};
 use lunex_engine::YInvert;
 use std::cmp::Ordering;
-use bevy::window::PrimaryWindow;
+use bevy::{utils::HashMap, window::PrimaryWindow};
 use bevy_mod_picking::backend::prelude::*;
 
-use crate::{Dimension, Element};
+use crate::{Dimension, Element, UiClip, UiModal};
 
 
 // #===============#
@@ -35,55 +35,42 @@ pub fn lunex_picking(
             &GlobalTransform,
             Option<&Pickable>,
             &ViewVisibility,
+            Option<&UiClip>,
+            Option<&Parent>,
         )
     >,
+    modals: ModalQuery,
     mut output: EventWriter<PointerHits>,
 ) {
     let mut sorted_nodes: Vec<_> = node_query.iter().collect();
+    let modal_depths = modal_depths(&modals);
     sorted_nodes.sort_by(|a, b| { (b.3.translation().z).partial_cmp(&a.3.translation().z).unwrap_or(Ordering::Equal) });
 
     for (pointer, location) in pointers.iter().filter_map(|(pointer, pointer_location)| { pointer_location.location().map(|loc| (pointer, loc)) }) {
         let mut blocked = false;
-        let Some((cam_entity, camera, cam_transform, cam_ortho)) = cameras.iter().filter(|(_, camera, _, _)| camera.is_active)
-            .find(|(_, camera, _, _)| {
-                camera
-                    .target
-                    .normalize(Some(match primary_window.get_single() {
-                        Ok(w) => w,
-                        Err(_) => return false,
-                    }))
-                    .unwrap()
-                    == location.target
-            })
-        else { continue; };
-
-        let Some(cursor_pos_world) = camera.viewport_to_world_2d(cam_transform, location.position) else { continue; };
+        let Some(((cam_entity, camera, _, cam_ortho), cursor_pos_world)) = pointer_world_position(location, &cameras, &primary_window) else { continue; };
 
         let picks: Vec<(Entity, HitData)> = sorted_nodes
             .iter()
             .copied()
-            .filter(|(.., visibility)| visibility.get())
+            .filter(|(.., visibility, _, _)| visibility.get())
+            .filter(|(_, _, _, node_transform, .., parent)| !is_below_modal(&modal_depths, *parent, node_transform))
             .filter_map(
-                |(entity, dimension, element, node_transform, pickable, ..)| {
+                |(entity, dimension, element, node_transform, pickable, _, clip, _)| {
                     if blocked {
                         return None;
                     }
 
-                    let pos = if element.is_none() { dimension.size.invert_y() / 2.0 } else { Vec2::ZERO };
-
-                    let rect = Rect::from_center_size(pos, dimension.size);
+                    let rect = node_rect(dimension, element.is_some());
 
                     /* let s = rect.max - rect.min;
                     let p = (rect.min + s/2.0).extend(0.0) + node_transform.translation();
                     gizmos.rect(p, Quat::from_rotation_y(0.0), s, Color::linear_rgb(0.0, 0.0, 1.0)); */
 
-                    // Transform cursor pos to sprite coordinate system
-                    let cursor_pos_sprite = node_transform
-                        .affine()
-                        .inverse()
-                        .transform_point3((cursor_pos_world, 0.0).into());
+                    let cursor_pos_sprite = node_local_position(cursor_pos_world, node_transform);
 
-                    let is_cursor_in_sprite = rect.contains(cursor_pos_sprite.truncate());
+                    // Parts hidden by scroll containers are not pickable
+                    let is_cursor_in_sprite = rect.contains(cursor_pos_sprite) && clip.map(|clip| clip.rect.contains(cursor_pos_sprite)).unwrap_or(true);
                     blocked = is_cursor_in_sprite && pickable.map(|p| p.should_block_lower) != Some(false);
 
                     // HitData requires a depth as calculated from the camera's near clipping plane
@@ -99,6 +86,68 @@ pub fn lunex_picking(
     }
 }
 
+/// Finds the active camera rendering to the pointer location and returns it together with the pointer position in world space.
+pub(crate) fn pointer_world_position<'a>(
+    location: &pointer::Location,
+    cameras: &'a Query<(Entity, &Camera, &GlobalTransform, &OrthographicProjection)>,
+    primary_window: &Query<Entity, With<PrimaryWindow>>,
+) -> Option<((Entity, &'a Camera, &'a GlobalTransform, &'a OrthographicProjection), Vec2)> {
+    let camera = cameras.iter().filter(|(_, camera, _, _)| camera.is_active)
+        .find(|(_, camera, _, _)| {
+            camera
+                .target
+                .normalize(Some(match primary_window.get_single() {
+                    Ok(w) => w,
+                    Err(_) => return false,
+                }))
+                .unwrap()
+                == location.target
+        })?;
+    let position = camera.1.viewport_to_world_2d(camera.2, location.position)?;
+    Some((camera, position))
+}
+
+/// Query of [`UiModal`] nodes that block the nodes below them.
+pub(crate) type ModalQuery<'w, 's> = Query<'w, 's, (&'static Parent, &'static GlobalTransform, &'static ViewVisibility), With<UiModal>>;
+
+/// Returns the depth of the topmost visible [`UiModal`] for each tree entity.
+pub(crate) fn modal_depths(modals: &ModalQuery) -> HashMap<Entity, f32> {
+    let mut depths = HashMap::new();
+    for (parent, transform, visibility) in modals {
+        if !visibility.get() { continue }
+        let depth = transform.translation().z;
+        depths.entry(parent.get()).and_modify(|max: &mut f32| *max = max.max(depth)).or_insert(depth);
+    }
+    depths
+}
+
+/// Checks if the node is placed below a visible [`UiModal`] of its tree.
+pub(crate) fn is_below_modal(depths: &HashMap<Entity, f32>, parent: Option<&Parent>, transform: &GlobalTransform) -> bool {
+    parent.and_then(|parent| depths.get(&parent.get())).is_some_and(|depth| transform.translation().z < *depth)
+}
+
+/// Returns the rectangle of the node in its local space. [`Element`]s are centered, other nodes are aligned by their top-left corner.
+pub(crate) fn node_rect(dimension: &Dimension, is_element: bool) -> Rect {
+    let pos = if is_element { Vec2::ZERO } else { dimension.size.invert_y() / 2.0 };
+    Rect::from_center_size(pos, dimension.size)
+}
+
+/// Transforms the world position into the local space of the node.
+pub(crate) fn node_local_position(position: Vec2, transform: &GlobalTransform) -> Vec2 {
+    // Transform cursor ray to sprite coordinate system
+    let inverse = transform.affine().inverse();
+    let ray_origin = inverse.transform_point3((position, 0.0).into());
+    let ray_direction = inverse.transform_vector3(Vec3::Z);
+
+    // Intersect the ray with the sprite plane, so rotated nodes are picked correctly
+    let local = if ray_direction.z.abs() > f32::EPSILON {
+        ray_origin - ray_direction * (ray_origin.z / ray_direction.z)
+    } else {
+        ray_origin
+    };
+    local.truncate()
+}
+
 
 // #===============================#
 // #=== VIEWPORT PORTAL PICKING ===#
@@ -0,0 +1,50 @@
+#define_import_path bevy_lunex::shape
+
+struct UiShape {
+    color: vec4<f32>,
+    gradient: vec4<f32>,
+    border_color: vec4<f32>,
+    shadow_color: vec4<f32>,
+    // top-left, top-right, bottom-right, bottom-left
+    radius: vec4<f32>,
+    size: vec2<f32>,
+    shadow_offset: vec2<f32>,
+    gradient_angle: f32,
+    border: f32,
+    shadow_blur: f32,
+    margin: f32,
+};
+
+// Signed distance to a box with rounded corners, y points down
+fn sd_rounded_box(p: vec2<f32>, half_size: vec2<f32>, radius: vec4<f32>) -> f32 {
+    var r = select(select(radius.w, radius.x, p.y < 0.0), select(radius.z, radius.y, p.y < 0.0), p.x > 0.0);
+    r = min(r, min(half_size.x, half_size.y));
+    let q = abs(p) - half_size + r;
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - r;
+}
+
+// Color of the shape at the mesh uv, the mesh covers the node grown by the margin
+fn shape_color(uv: vec2<f32>, shape: UiShape) -> vec4<f32> {
+    let p = (uv - 0.5) * (shape.size + 2.0 * shape.margin);
+    let half_size = shape.size * 0.5;
+    let d = sd_rounded_box(p, half_size, shape.radius);
+    let aa = max(fwidth(d), 0.0001);
+
+    // Gradient along the angle across the node
+    let direction = vec2<f32>(cos(shape.gradient_angle), sin(shape.gradient_angle));
+    let extent = max(abs(direction.x) * half_size.x + abs(direction.y) * half_size.y, 0.0001);
+    let fill = mix(shape.color, shape.gradient, clamp(dot(p, direction) / extent * 0.5 + 0.5, 0.0, 1.0));
+
+    // Border is drawn inside the edge
+    let border = select(0.0, clamp((d + shape.border) / aa + 0.5, 0.0, 1.0), shape.border > 0.0);
+    let body_color = mix(fill, shape.border_color, border);
+    let body = body_color.a * clamp(0.5 - d / aa, 0.0, 1.0);
+
+    // Shadow below the body
+    let blur = max(shape.shadow_blur, aa);
+    let shadow = shape.shadow_color.a * (1.0 - smoothstep(-blur, blur, sd_rounded_box(p - shape.shadow_offset, half_size, shape.radius)));
+
+    let alpha = body + shadow * (1.0 - body);
+    let rgb = (body_color.rgb * body + shape.shadow_color.rgb * shadow * (1.0 - body)) / max(alpha, 0.0001);
+    return vec4<f32>(rgb, alpha);
+}
@@ -0,0 +1,9 @@
+#import bevy_pbr::forward_io::VertexOutput
+#import bevy_lunex::shape::{UiShape, shape_color}
+
+@group(2) @binding(0) var<uniform> shape: UiShape;
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    return shape_color(mesh.uv, shape);
+}
@@ -8,49 +8,64 @@ use bevy::{render::primitives::Aabb, sprite::{Anchor, Material2d, Mesh2dHandle,
 /// Trait for creating new UI states
 pub trait UiState where Self: Send + Sync + 'static {
     const INDEX: usize;
+    /// When multiple states are active, the one with higher priority is on top
+    const PRIORITY: usize = Self::INDEX;
 }
 
 
 /// UI state of a component, this is the normal default
-#[derive(Component, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
 pub struct Base;
 impl UiState for Base {
     const INDEX: usize = 0;
 }
 
 /// UI state of a component, is active on hover
-#[derive(Component, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
 pub struct Hover;
 impl UiState for Hover {
     const INDEX: usize = 1;
+    const PRIORITY: usize = 1;
 }
 
 /// UI state of a component, is active when clicked
-#[derive(Component, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
 pub struct Clicked;
 impl UiState for Clicked {
     const INDEX: usize = 2;
+    const PRIORITY: usize = 3;
 }
 
 /// UI state of a component, is active when selected
-#[derive(Component, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
 pub struct Selected;
 impl UiState for Selected {
     const INDEX: usize = 3;
+    const PRIORITY: usize = 2;
 }
 
 /// UI state of a component, is active after entity is spawned
-#[derive(Component, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
 pub struct Intro;
 impl UiState for Intro {
     const INDEX: usize = 4;
+    const PRIORITY: usize = 4;
 }
 
 /// UI state of a component, is active before entity is despawned
-#[derive(Component, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
 pub struct Outro;
 impl UiState for Outro {
     const INDEX: usize = 5;
+    const PRIORITY: usize = 5;
+}
+
+/// UI state of a component, is active while a dragged node it accepts is held, see [`UiDropTarget`]
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+pub struct Droppable;
+impl UiState for Droppable {
+    const INDEX: usize = 6;
+    const PRIORITY: usize = 6;
 }
 
 
@@ -85,7 +100,9 @@ impl Dimension {
     }
 }
 
-/// # WIP - used for Div layout
+/// This struct holds the size of the node content. Div layout uses it to size itself,
+/// if the node has no Div subnodes. The parent Div also includes it if this node is declarative, like a text label.
+/// Lunex uses this component to mirror content size into parent [`UiTree`].
 #[derive(Component, Debug, Default, Clone, Copy, PartialEq, Reflect)]
 pub struct UiContent {
     pub size: Vec2,
@@ -96,6 +113,72 @@ impl UiContent {
     }
 }
 
+/// This struct draws the border of the [`Div`] layout. Lunex fetches the computed border widths into it
+/// and spawns a sprite for each side as a child of the entity.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Root/Card"),
+///     UiLayout::div().pad(Ab(10.0)).border(Ab((2.0, 1.0, 2.0, 4.0))).pack::<Base>(),
+///     UiBorder::new(Color::WHITE).bottom(Color::srgb(1.0, 0.8, 0.2)),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct UiBorder {
+    /// The color of each side. `left`, `top`, `right`, `bottom`
+    pub color: [Color; 4],
+    /// The border widths fetched from the node. `x-left`, `y-top`, `z-right`, `w-bottom`
+    pub(crate) width: Vec4,
+    /// The spawned side entities
+    pub(crate) sides: Option<[Entity; 4]>,
+}
+impl UiBorder {
+    /// Creates new struct with the same color on all sides
+    pub fn new(color: Color) -> Self {
+        UiBorder { color: [color; 4], width: Vec4::ZERO, sides: None }
+    }
+    /// Replaces the left side color with a new value.
+    pub fn left(mut self, color: Color) -> Self {
+        self.color[0] = color;
+        self
+    }
+    /// Replaces the top side color with a new value.
+    pub fn top(mut self, color: Color) -> Self {
+        self.color[1] = color;
+        self
+    }
+    /// Replaces the right side color with a new value.
+    pub fn right(mut self, color: Color) -> Self {
+        self.color[2] = color;
+        self
+    }
+    /// Replaces the bottom side color with a new value.
+    pub fn bottom(mut self, color: Color) -> Self {
+        self.color[3] = color;
+        self
+    }
+    /// Returns the border widths fetched from the node. `x-left`, `y-top`, `z-right`, `w-bottom`
+    pub fn get_width(&self) -> Vec4 {
+        self.width
+    }
+}
+
+/// This struct holds the part of the entity that is visible inside of the [`UiScroll`] containers it is nested in.
+/// Lunex inserts it into all linked entities of the container subnodes. The rectangle is in the local space of the entity,
+/// the same space as [`Dimension`] is picked in. Meshes and sprites are cropped to it, other entities are hidden when it is empty.
+/// Text is not cropped, it is hidden as soon as a part of it is clipped.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct UiClip {
+    /// The visible rectangle
+    pub rect: Rect,
+    /// The innermost scroll container
+    pub container: Entity,
+    /// If the sprite rect was overwritten by the clipping
+    pub(crate) cropped: bool,
+    /// The visibility the entity had before it was hidden by the clipping
+    pub(crate) hidden: Option<Visibility>,
+}
+
 
 /// This struct is used to specify size of the font in UI.
 #[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
@@ -120,6 +203,155 @@ impl UiTextSize {
     }
 }
 
+/// What happens with the text lines that don't fit into [`UiTextWrap`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum UiTextOverflow {
+    /// All lines are shown, even if they reach outside of the node
+    #[default]
+    Visible,
+    /// The lines past the limit are cut off
+    Clip,
+    /// The lines past the limit are cut off and the last line ends with `…`
+    Ellipsis,
+}
+
+/// This struct switches the text from scaling to fit into wrapping mode. The computed width of the node
+/// becomes the wrap bound of the text, the glyphs are not scaled and the resulting text size is sent as [`UiContent`].
+/// The size parameters of the layout are not overwritten by the text size. With [`UiTextSize`], the font size is resolved
+/// from it with the font size of the node, see [`UiFontSize`]. Otherwise the font keeps its size.
+///
+/// With [`UiTextOverflow::Clip`] or [`UiTextOverflow::Ellipsis`], the lines past `max_lines` or below the node are cut off.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Description"),
+///     UiLayout::window().pos(Rl(10.0)).size(Rl((80.0, 30.0))).pack::<Base>(),
+///     UiText2dBundle {
+///         text: Text::from_section(long_localized_string, TextStyle { font_size: 20.0, ..default() }),
+///         ..default()
+///     },
+///     UiTextWrap::new().justify(JustifyText::Center).max_lines(3).overflow(UiTextOverflow::Ellipsis),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct UiTextWrap {
+    /// Horizontal alignment of the lines
+    pub justify: JustifyText,
+    /// Vertical alignment of the text block inside of the node
+    pub align: Align,
+    /// Maximum number of visible lines
+    pub max_lines: Option<usize>,
+    /// What happens with the lines that don't fit
+    pub overflow: UiTextOverflow,
+    /// The full text set by the user
+    pub(crate) source: Vec<String>,
+    /// The text currently shown, possibly cut off
+    pub(crate) shown: Vec<String>,
+    /// The node size the text was last wrapped for
+    pub(crate) size: Vec2,
+}
+impl UiTextWrap {
+    /// Creates new struct with left aligned text at the top of the node
+    pub fn new() -> Self {
+        UiTextWrap {
+            justify: JustifyText::Left,
+            align: Align::START,
+            max_lines: None,
+            overflow: UiTextOverflow::Visible,
+            source: Vec::new(),
+            shown: Vec::new(),
+            size: Vec2::ZERO,
+        }
+    }
+    /// Replaces the horizontal alignment with a new value.
+    pub fn justify(mut self, justify: JustifyText) -> Self {
+        self.justify = justify;
+        self
+    }
+    /// Replaces the vertical alignment with a new value.
+    pub fn align(mut self, align: impl Into<Align>) -> Self {
+        self.align = align.into();
+        self
+    }
+    /// Replaces the line limit with a new value.
+    pub fn max_lines(mut self, lines: usize) -> Self {
+        self.max_lines = Some(lines);
+        self
+    }
+    /// Replaces the overflow mode with a new value.
+    pub fn overflow(mut self, overflow: UiTextOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+impl Default for UiTextWrap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// This struct holds the font sizes of the text sections before they were scaled by the font size of the node.
+/// Lunex inserts it into all text elements, there is no need to add it manually.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct UiTextFontBase {
+    /// The authored font sizes
+    pub(crate) base: Vec<f32>,
+    /// The last font sizes written by Lunex
+    pub(crate) applied: Vec<f32>,
+}
+
+/// This struct splits the texture of the element mesh into nine slices. The corners keep their size,
+/// the edges stretch along one axis and the center stretches along both. Lunex rebuilds the mesh when [`Dimension`] changes.
+/// Works with [`UiImageSlice2dBundle`] and [`UiMaterial3dBundle`].
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Panel"),
+///     UiLayout::window().size(Rl(50.0)).pack::<Base>(),
+///     UiImageSlice2dBundle::from_image(&mut materials, assets.load("panel.png")),
+///     UiSlice::nine(Ab(16.0), 0.25),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct UiSlice {
+    /// The size of the frame on the node. `x-left`, `y-top`, `z-right`, `w-bottom`
+    pub border: UiValue<Vec4>,
+    /// The size of the frame in the texture as a fraction of the texture size. `x-left`, `y-top`, `z-right`, `w-bottom`
+    pub texture: Vec4,
+    /// The frame size resolved by Lunex
+    pub(crate) size: Vec4,
+}
+impl UiSlice {
+    /// Creates new struct
+    pub fn new(border: impl Into<UiValue<Vec4>>, texture: impl Into<Vec4>) -> Self {
+        UiSlice { border: border.into(), texture: texture.into(), size: Vec4::ZERO }
+    }
+    /// Creates nine slices with the same frame size on all sides
+    pub fn nine(border: impl Into<UiValue<f32>>, texture: f32) -> Self {
+        UiSlice::new(border.into(), Vec4::splat(texture))
+    }
+    /// Creates three slices with the frame only on the left and right side
+    pub fn horizontal(border: impl Into<UiValue<f32>>, texture: f32) -> Self {
+        let border = border.into();
+        let mut slice = UiSlice::new(UiValue::<Vec4>::new(), Vec4::new(texture, 0.0, texture, 0.0));
+        slice.border.set_x(border);
+        slice.border.set_z(border);
+        slice
+    }
+    /// Creates three slices with the frame only on the top and bottom side
+    pub fn vertical(border: impl Into<UiValue<f32>>, texture: f32) -> Self {
+        let border = border.into();
+        let mut slice = UiSlice::new(UiValue::<Vec4>::new(), Vec4::new(0.0, texture, 0.0, texture));
+        slice.border.set_y(border);
+        slice.border.set_w(border);
+        slice
+    }
+    /// Returns the resolved frame size. `x-left`, `y-top`, `z-right`, `w-bottom`
+    pub fn get_size(&self) -> Vec4 {
+        self.size
+    }
+}
+
 // #=======================#
 // #=== MAIN COMPONENTS ===#
 
@@ -246,24 +478,24 @@ impl PackageLayout for ui::Div {
     }
 }
 
+impl <S> From<UiLayout<S>> for Layout {
+    fn from(val: UiLayout<S>) -> Self {
+        val.layout
+    }
+}
+
 /// This struct controls what 2 layouts should be computed and lerped between.
-#[derive(Component, Debug, Clone, PartialEq)]
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default)]
 pub struct UiLayoutController {
     /// Indexes of the two layouts to tween between
     pub index: [usize; 2],
     /// The transition ranging from 0.0 to 1.0
     pub tween: f32,
-    /// The method called for smoothing the tween value
-    pub method: fn(f32) -> f32,
-}
-impl Default for UiLayoutController {
-    fn default() -> Self {
-        UiLayoutController { 
-            index: [0, 0],
-            tween: 0.0,
-            method: |i|{i},
-        }
-    }
+    /// The easing curve used for smoothing the tween value. Use [`UiEase::Custom`] for your own function.
+    pub method: UiEase,
+    /// Layouts blended on top of the tweened pair in this order, each with its own tween value
+    pub chain: Vec<(usize, f32)>,
 }
 
 
@@ -293,6 +525,15 @@ impl <T> UiLink<T> {
             marker: PhantomData,
         }
     }
+    /// Replaces the path prefix if this link points to the node or any of its subnodes. Returns `true` if the path changed.
+    pub fn rebase(&mut self, path: &str, new_path: &str) -> bool {
+        match self.path.strip_prefix(path) {
+            Some("") => self.path = new_path.to_string(),
+            Some(rest) if rest.starts_with('/') => self.path = format!("{new_path}{rest}"),
+            _ => return false,
+        }
+        true
+    }
 }
 impl <T> Default for UiLink<T> {
     fn default() -> Self {
@@ -310,6 +551,59 @@ impl <T> Default for UiLink<T> {
 #[derive(Component, Debug, Default, Clone, Copy, PartialEq, Reflect)]
 pub struct UiDepthBias (pub f32);
 
+/// This struct holds the font size of the node that the [`Em`] unit is relative to. It is inherited by all subnodes,
+/// the [`Em`] unit in the font size itself is relative to the inherited font size. The root font size is [`MasterData::font_size`].
+/// Text elements linked to the node scale with it, the default `16px` keeps their font size as it is.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Menu"),
+///     UiLayout::window_full().pack::<Base>(),
+///     UiFontSize::new(Em(1.5)),
+/// ));
+/// ```
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Reflect)]
+pub struct UiFontSize (pub UiValue<f32>);
+impl UiFontSize {
+    /// Creates new struct
+    pub fn new(size: impl Into<UiValue<f32>>) -> Self {
+        UiFontSize(size.into())
+    }
+}
+
+/// Named depth layers of the [`UiTree`]. Unlike [`UiDepthBias`], the layer is inherited,
+/// so the node is placed together with all of its subnodes above the nodes of lower layers.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Pause"),
+///     UiLayout::window_full().pack::<Base>(),
+///     UiLayer::Menu,
+/// ));
+/// ```
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Reflect)]
+pub enum UiLayer {
+    /// Backgrounds and world markers, has no bias
+    #[default]
+    Background,
+    /// Heads-up display
+    Hud,
+    /// Menus
+    Menu,
+    /// Modal dialogs, see [`UiModal`]
+    Modal,
+    /// Tooltips and popovers
+    Tooltip,
+}
+impl UiLayer {
+    /// Depth between two layers. Nodes nested deeper than this reach into the next layer.
+    pub const SPACING: f32 = 100.0;
+    /// Returns the depth bias of the layer
+    pub fn get_bias(&self) -> f32 {
+        *self as u8 as f32 * Self::SPACING
+    }
+}
+
 
 // #====================#
 // #=== MAIN BUNDLES ===#
@@ -548,6 +842,39 @@ impl From<Handle<Image>> for UiImage2dBundle {
 }
 
 
+/// Additional bundle for `UiNode` entity.
+/// Provides functionality to bind image mesh in 2D to `UiNode`. Add [`UiSlice`] to slice the image.
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct UiImageSlice2dBundle {
+    /// Sliced mesh that is generated every time node is changed.
+    pub mesh: Mesh2dHandle,
+    /// The material with the image.
+    pub material: Handle<ColorMaterial>,
+    /// Marks this as node element.
+    pub element: Element,
+    /// Contains the ui node size.
+    pub dimension: Dimension,
+    /// The visibility of the entity.
+    pub visibility: Visibility,
+    /// The inherited visibility of the entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// The view visibility of the entity.
+    pub view_visibility: ViewVisibility,
+    /// The transform of the entity.
+    pub transform: Transform,
+    /// The global transform of the entity.
+    pub global_transform: GlobalTransform,
+}
+impl UiImageSlice2dBundle {
+    pub fn from_image(materials: &mut ResMut<'_, Assets<ColorMaterial>>, value: Handle<Image>) -> Self {
+        UiImageSlice2dBundle {
+            material: materials.add(ColorMaterial { texture: Some(value), ..default() }),
+            ..default()
+        }
+    }
+}
+
+
 /// Additional bundle for `UiNode` entity.
 /// Provides functionality to bind text to `UiNode`.
 #[derive(Bundle, Clone, Debug, Default)]
@@ -566,6 +893,8 @@ pub struct UiText2dBundle {
     pub element: Element,
     /// Contains the ui node size.
     pub dimension: Dimension,
+    /// Contains the text size, used as content of Div layout.
+    pub content: UiContent,
     /// The visibility of the entity.
     pub visibility: Visibility,
     /// The inherited visibility of the entity.
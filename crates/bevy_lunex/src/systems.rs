@@ -1,5 +1,5 @@
 use crate::*;
-use bevy::{math::Vec3A, render::primitives::Aabb, sprite::Mesh2dHandle, text::TextLayoutInfo, window::PrimaryWindow};
+use bevy::{math::Vec3A, render::{mesh::{Indices, PrimitiveTopology, VertexAttributeValues}, primitives::Aabb, render_asset::RenderAssetUsages, view::VisibilitySystems}, sprite::{Anchor, Mesh2dHandle}, text::{Text2dBounds, TextLayoutInfo}, window::PrimaryWindow};
 use lunex_engine::*;
 
 
@@ -45,13 +45,12 @@ pub fn debug_draw_gizmo<T:Component, N:Default + Component, G:GizmoConfigGroup>(
 
                 if let Some(Layout::Solid(_)) = container.layout.get(&container.layout_index[0]) { color = Color::linear_rgb(1.0, 1.0, 0.0) }
 
-                let mut pos = container.rectangle.pos.invert_y() + transform.translation();
-                pos.x += container.rectangle.size.x / 2.0;
-                pos.y += container.rectangle.size.y / -2.0;
+                let center = container.rectangle.pos + (container.rectangle.size / 2.0).extend(0.0);
+                let pos = container.transform.transform_point3(center).invert_y() + transform.translation();
 
                 gizmos.rect(
                     pos,
-                    Quat::from_rotation_y(0.0),
+                    container.transform.to_scale_rotation_translation().1.invert_y(),
                     container.rectangle.size,
                     color,
                 );
@@ -171,6 +170,7 @@ pub fn send_layout_to_node<T:Component, N:Default + Component, S: UiState>(
                         #[cfg(feature = "verbose")]
                         info!("{} {} - Received Layout data", "->".blue(), link.path.yellow().bold());
                         container.layout.insert(S::INDEX, layout.layout);
+                        container.mark_dirty();
                     }
                 }
             }
@@ -197,7 +197,9 @@ pub fn send_layout_control_to_node<T:Component, N:Default + Component>(
                         #[cfg(feature = "verbose")]
                         info!("{} {} - Tweening between [{}] [{}] - {}", "->".blue(), link.path.yellow().bold(), control.index[0], control.index[1], control.tween);
                         container.layout_index = control.index;
-                        container.layout_tween = (control.method)(control.tween);
+                        container.layout_tween = control.method.ease(control.tween);
+                        container.layout_chain = control.chain.iter().map(|(index, tween)| (*index, control.method.ease(*tween))).collect();
+                        container.mark_dirty();
                     }
                 }
             }
@@ -205,7 +207,6 @@ pub fn send_layout_control_to_node<T:Component, N:Default + Component>(
     }
 }
 
-/// # WORK IN PROGRESS!!! DOES NOTHING CURRENTLY.
 /// This system takes [`UiStack`] data and overwrites coresponding [`UiTree`] data.
 /// ## 📦 Types
 /// * Generic `(T)` - Marker component grouping entities into one widget type
@@ -225,6 +226,7 @@ pub fn send_stack_to_node<T:Component, N:Default + Component>(
                         #[cfg(feature = "verbose")]
                         info!("{} {} - Received Stack data", "->".blue(), link.path.yellow().bold());
                         container.stack = stack.clone();
+                        container.mark_dirty();
                     }
                 }
             }
@@ -251,6 +253,121 @@ pub fn send_depth_bias_to_node<T:Component, N:Default + Component>(
                         #[cfg(feature = "verbose")]
                         info!("{} {} - Received Depth bias data", "->".blue(), link.path.yellow().bold());
                         container.depth_bias = bias.0;
+                        container.mark_dirty();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// This system takes [`UiLayer`] data and overwrites coresponding [`UiTree`] data.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn send_layer_to_node<T:Component, N:Default + Component>(
+    mut uis: Query<(&mut UiTree<T, N>, &Children)>,
+    query: Query<(&UiLink<T>, &UiLayer), Changed<UiLayer>>,
+    links: Query<&UiLink<T>, Without<UiLayer>>,
+    mut removed: RemovedComponents<UiLayer>,
+) {
+    let removed: Vec<Entity> = removed.read().collect();
+    for (mut ui, children) in &mut uis {
+        for child in children {
+            // If child matches
+            if let Ok((link, layer)) = query.get(*child) {
+                // If node exists
+                if let Ok(node) = ui.borrow_node_mut(link.path.clone()) {
+                    //Should always be Some but just in case
+                    if let Some(container) = node.obtain_data_mut() {
+                        #[cfg(feature = "verbose")]
+                        info!("{} {} - Received Layer data", "->".blue(), link.path.yellow().bold());
+                        container.layer_bias = layer.get_bias();
+                        container.mark_dirty();
+                    }
+                }
+            }
+            // If child had the layer removed
+            if !removed.contains(child) { continue; }
+            if let Ok(link) = links.get(*child) {
+                if let Ok(node) = ui.borrow_node_mut(link.path.clone()) {
+                    if let Some(container) = node.obtain_data_mut() {
+                        #[cfg(feature = "verbose")]
+                        info!("{} {} - Removed Layer data", "->".blue(), link.path.yellow().bold());
+                        container.layer_bias = 0.0;
+                        container.mark_dirty();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// This system takes [`UiFontSize`] data and overwrites coresponding [`UiTree`] data.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn send_font_size_to_node<T:Component, N:Default + Component>(
+    mut uis: Query<(&mut UiTree<T, N>, &Children)>,
+    query: Query<(&UiLink<T>, &UiFontSize), Changed<UiFontSize>>,
+    links: Query<&UiLink<T>, Without<UiFontSize>>,
+    mut removed: RemovedComponents<UiFontSize>,
+) {
+    let removed: Vec<Entity> = removed.read().collect();
+    for (mut ui, children) in &mut uis {
+        for child in children {
+            // If child matches
+            if let Ok((link, font_size)) = query.get(*child) {
+                // If node exists
+                if let Ok(node) = ui.borrow_node_mut(link.path.clone()) {
+                    //Should always be Some but just in case
+                    if let Some(container) = node.obtain_data_mut() {
+                        #[cfg(feature = "verbose")]
+                        info!("{} {} - Received Font size data", "->".blue(), link.path.yellow().bold());
+                        container.font_size = Some(font_size.0);
+                        container.mark_dirty();
+                    }
+                }
+            }
+            // If child had the font size removed
+            if !removed.contains(child) { continue; }
+            if let Ok(link) = links.get(*child) {
+                if let Ok(node) = ui.borrow_node_mut(link.path.clone()) {
+                    if let Some(container) = node.obtain_data_mut() {
+                        #[cfg(feature = "verbose")]
+                        info!("{} {} - Removed Font size data", "->".blue(), link.path.yellow().bold());
+                        container.font_size = None;
+                        container.mark_dirty();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// This system takes [`UiScroll`] offset and overwrites coresponding [`UiTree`] data.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn send_scroll_to_node<T:Component, N:Default + Component>(
+    mut uis: Query<(&mut UiTree<T, N>, &Children)>,
+    query: Query<(&UiLink<T>, &UiScroll), Changed<UiScroll>>,
+    mut events: EventWriter<UiScrollEvent>,
+) {
+    for (mut ui, children) in &mut uis {
+        for child in children {
+            // If child matches
+            if let Ok((link, scroll)) = query.get(*child) {
+                // If node exists
+                if let Ok(node) = ui.borrow_node_mut(link.path.clone()) {
+                    //Should always be Some but just in case
+                    if let Some(container) = node.obtain_data_mut() {
+                        if container.scroll == scroll.offset { continue; }
+                        #[cfg(feature = "verbose")]
+                        info!("{} {} - Received Scroll data", "->".blue(), link.path.yellow().bold());
+                        container.scroll = scroll.offset;
+                        container.mark_dirty();
+                        events.send(UiScrollEvent { target: *child, offset: scroll.offset, max: scroll.max });
                     }
                 }
             }
@@ -258,7 +375,6 @@ pub fn send_depth_bias_to_node<T:Component, N:Default + Component>(
     }
 }
 
-/// # WORK IN PROGRESS!!! DOES NOTHING CURRENTLY.
 /// This system takes [`UiContent`] data and overwrites coresponding [`UiTree`] data.
 /// ## 📦 Types
 /// * Generic `(T)` - Marker component grouping entities into one widget type
@@ -278,6 +394,7 @@ pub fn send_content_size_to_node<T:Component, N:Default + Component>(
                         #[cfg(feature = "verbose")]
                         info!("{} {} - Received Content size data", "->".blue(), link.path.yellow().bold());
                         container.content_size = content.size;
+                        container.mark_dirty();
                     }
                 }
             }
@@ -303,7 +420,10 @@ pub fn fetch_transform_from_node<T:Component, N:Default + Component>(
                     if let Some(container) = node.obtain_data() {
                         #[cfg(feature = "verbose")]
                         info!("{} {} - Linked {} fetched Transform data from node", "<-".bright_green(), link.path.yellow().bold(), "ENTITY".blue());
-                        transform.translation = container.rectangle.pos.invert_y();
+                        let translation = container.transform.transform_point3(container.rectangle.pos).invert_y();
+                        let rotation = container.transform.to_scale_rotation_translation().1.invert_y();
+                        if transform.as_ref().translation != translation { transform.translation = translation; }
+                        if transform.as_ref().rotation != rotation { transform.rotation = rotation; }
                     }
                 }
             }
@@ -339,6 +459,204 @@ pub fn fetch_dimension_from_node<T:Component, N:Default + Component>(
     }
 }
 
+/// This system fetches computed [`UiTree`] data and overwrites querried [`UiBorder`] widths.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn fetch_border_from_node<T:Component, N:Default + Component>(
+    uis: Query<(&UiTree<T, N>, &Children), Changed<UiTree<T, N>>>,
+    mut query: Query<(&UiLink<T>, &mut UiBorder)>,
+) {
+    for (ui, children) in &uis {
+        for child in children {
+            // If child matches
+            if let Ok((link, mut border)) = query.get_mut(*child) {
+                // If node exists
+                if let Ok(node) = ui.borrow_node(link.path.clone()) {
+                    //Should always be Some but just in case
+                    if let Some(container) = node.obtain_data() {
+                        if border.as_ref().width != container.border { border.width = container.border; }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// This system fetches computed [`UiTree`] data and resolves the frame size of querried [`UiSlice`].
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn fetch_slice_from_node<T:Component, N:Default + Component>(
+    uis: Query<(Ref<UiTree<T, N>>, &Children)>,
+    mut query: Query<(&UiLink<T>, &mut UiSlice)>,
+) {
+    for (ui, children) in &uis {
+        let abs_scale = ui.obtain_topdata().map_or(1.0, |master| master.abs_scale);
+        let viewport = ui.obtain_data().map_or(Vec2::ZERO, |data| data.rectangle.size);
+        for child in children {
+            // If child matches and changed
+            let Ok((link, mut slice)) = query.get_mut(*child) else { continue };
+            if !ui.is_changed() && !slice.is_changed() { continue }
+
+            // If node exists
+            if let Ok(node) = ui.borrow_node(link.path.clone()) {
+                if let Some(container) = node.obtain_data() {
+                    let size = container.rectangle.size;
+                    let frame = slice.border.evaluate(Vec4::splat(abs_scale), size.xyxy(), viewport.xyxy(), Vec4::splat(container.computed_font_size));
+                    if slice.size != frame { slice.size = frame; }
+                }
+            }
+        }
+    }
+}
+
+/// This system fetches computed [`UiTree`] data and overwrites querried [`UiScroll`] content size.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn fetch_scroll_from_node<T:Component, N:Default + Component>(
+    uis: Query<(&UiTree<T, N>, &Children), Changed<UiTree<T, N>>>,
+    mut query: Query<(&UiLink<T>, &mut UiScroll)>,
+) {
+    for (ui, children) in &uis {
+        for child in children {
+            // If child matches
+            if let Ok((link, mut scroll)) = query.get_mut(*child) {
+                // If node exists
+                if let Ok(node) = ui.borrow_node(link.path.clone()) {
+                    //Should always be Some but just in case
+                    if let Some(container) = node.obtain_data() {
+                        let max = (container.scroll_size - container.rectangle.size).max(Vec2::ZERO);
+                        if scroll.max != max {
+                            #[cfg(feature = "verbose")]
+                            info!("{} {} - Linked {} fetched Scroll data from node: {:?}", "<-".bright_green(), link.path.yellow().bold(), "ENTITY".blue(), max);
+                            // The content size alone shouldn't trigger resending of the offset
+                            scroll.bypass_change_detection().max = max;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// This system fetches computed [`UiTree`] data and overwrites the font size of querried text elements.
+/// The font sizes scale with the font size of the node, the default `16px` keeps them as they are.
+/// [`UiTextWrap`] text with [`UiTextSize`] is resolved from it with the font size of the node instead.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn fetch_text_size_from_node<T:Component, N:Default + Component>(
+    mut commands: Commands,
+    uis: Query<(Ref<UiTree<T, N>>, &Children)>,
+    mut query: Query<(&UiLink<T>, &mut Text, Has<UiTextWrap>, Option<Ref<UiTextSize>>, Option<&mut UiTextFontBase>), With<Element>>,
+) {
+    for (ui, children) in &uis {
+        let abs_scale = ui.obtain_topdata().map_or(1.0, |master| master.abs_scale);
+        let viewport = ui.obtain_data().map_or(Vec2::ZERO, |data| data.rectangle.size);
+        for child in children {
+            // If child matches and changed
+            let Ok((link, mut text, wrap, text_size, font_base)) = query.get_mut(*child) else { continue };
+            if !ui.is_changed() && !text.is_changed() && font_base.is_some() && !text_size.as_ref().is_some_and(|size| size.is_changed()) { continue }
+
+            // If node exists
+            let Ok(node) = ui.borrow_node(link.path.clone()) else { continue };
+            let Some(container) = node.obtain_data() else { continue };
+
+            // Sizes that were not written by Lunex are the new authored sizes
+            let current: Vec<f32> = text.sections.iter().map(|section| section.style.font_size).collect();
+            let mut base = font_base.as_deref().cloned().unwrap_or_default();
+            if base.applied != current { base.base = current.clone(); }
+
+            let factor = match text_size {
+                Some(text_size) if wrap => {
+                    let size = UiValue::from(text_size.size).evaluate(abs_scale, container.rectangle.size.y, viewport.y, container.computed_font_size);
+                    base.base.first().map_or(0.0, |first| if *first > 0.0 { size / first } else { 0.0 })
+                },
+                _ => container.computed_font_size / 16.0,
+            };
+            if factor <= 0.0 { continue }
+            base.applied = base.base.iter().map(|size| size * factor).collect();
+
+            if base.applied != current {
+                #[cfg(feature = "verbose")]
+                info!("{} {} - Linked {} fetched Text size data from node", "<-".bright_green(), link.path.yellow().bold(), "ENTITY".blue());
+                for (section, size) in text.sections.iter_mut().zip(&base.applied) {
+                    section.style.font_size = *size;
+                }
+            }
+            match font_base {
+                Some(mut font_base) => if *font_base != base { *font_base = base },
+                None => { commands.entity(*child).insert(base); },
+            }
+        }
+    }
+}
+
+/// This system computes the visible part of entities nested in [`UiScroll`] containers and overwrites querried [`UiClip`] data.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+/// * Generic `(N)` - Node data schema struct defining what can be stored in [`UiNode`]
+pub fn fetch_clip_from_node<T:Component, N:Default + Component>(
+    mut commands: Commands,
+    uis: Query<(&UiTree<T, N>, &Children), Changed<UiTree<T, N>>>,
+    scrolls: Query<&UiLink<T>, With<UiScroll>>,
+    mut query: Query<(&UiLink<T>, Has<Element>, Option<&mut UiClip>, Option<&mut Visibility>), With<Dimension>>,
+) {
+    for (ui, children) in &uis {
+
+        // Find the viewports of all scroll containers in this tree
+        let mut viewports = Vec::new();
+        for child in children {
+            let Ok(link) = scrolls.get(*child) else { continue };
+            let Ok(Some(container)) = ui.borrow_data(link.path.clone()) else { continue };
+            let rectangle = container.rectangle;
+            viewports.push((format!("{}/", link.path), *child, Rect::from_corners(rectangle.pos.truncate(), rectangle.pos.truncate() + rectangle.size)));
+        }
+
+        for child in children {
+            let Ok((link, is_element, clip, visibility)) = query.get_mut(*child) else { continue };
+
+            // Intersect all containers this entity is nested in, the innermost one has the longest path
+            let mut nested = viewports.iter().filter(|(path, ..)| link.path.starts_with(path.as_str())).peekable();
+            if nested.peek().is_none() {
+                if let Some(clip) = clip {
+                    // Restore the visibility hidden by the clipping
+                    if let (Some(hidden), Some(mut visibility)) = (clip.hidden, visibility) { *visibility = hidden; }
+                    commands.entity(*child).remove::<UiClip>();
+                }
+                continue;
+            }
+            let mut viewport = Rect::from_corners(Vec2::splat(f32::MIN), Vec2::splat(f32::MAX));
+            let mut innermost = (0, Entity::PLACEHOLDER);
+            for (path, entity, rect) in nested {
+                viewport = viewport.intersect(*rect);
+                if path.len() > innermost.0 { innermost = (path.len(), *entity); }
+            }
+
+            let Ok(Some(container)) = ui.borrow_data(link.path.clone()) else { continue };
+            let rectangle = container.rectangle;
+            let visible = viewport.intersect(Rect::from_corners(rectangle.pos.truncate(), rectangle.pos.truncate() + rectangle.size));
+
+            // Convert into the local space of the entity
+            let origin = if is_element { rectangle.pos.truncate() + rectangle.size / 2.0 } else { rectangle.pos.truncate() };
+            let rect = Rect {
+                min: Vec2::new(visible.min.x - origin.x, origin.y - visible.max.y),
+                max: Vec2::new(visible.max.x - origin.x, origin.y - visible.min.y),
+            };
+
+            match clip {
+                Some(mut clip) => if clip.rect != rect || clip.container != innermost.1 {
+                    clip.rect = rect;
+                    clip.container = innermost.1;
+                },
+                None => { commands.entity(*child).insert(UiClip { rect, container: innermost.1, cropped: false, hidden: None }); },
+            }
+        }
+    }
+}
+
 /// This system takes computed [`UiTree`] data and overwrites querried [`Transform`] + [`Element`] data in specific way.
 /// ## 📦 Types
 /// * Generic `(T)` - Marker component grouping entities into one widget type
@@ -357,9 +675,11 @@ pub fn element_fetch_transform_from_node<T:Component, N:Default + Component>(
                     if let Some(container) = node.obtain_data() {
                         #[cfg(feature = "verbose")]
                         info!("{} {} - Linked {} fetched Transform data", "<-".bright_green(), link.path.yellow().bold(), "ELEMENT".red());
-                        transform.translation = container.rectangle.pos.invert_y();
-                        transform.translation.x += container.rectangle.size.x /  2.0;
-                        transform.translation.y += container.rectangle.size.y / -2.0;
+                        let center = container.rectangle.pos + (container.rectangle.size / 2.0).extend(0.0);
+                        let translation = container.transform.transform_point3(center).invert_y();
+                        let rotation = container.transform.to_scale_rotation_translation().1.invert_y();
+                        if transform.as_ref().translation != translation { transform.translation = translation; }
+                        if transform.as_ref().rotation != rotation { transform.rotation = rotation; }
                     }
                 }
             }
@@ -380,6 +700,119 @@ pub fn element_sprite_size_from_dimension<T: Component>(
     }
 }
 
+/// This system takes updated [`UiClip`] data and crops querried [`Sprite`] data to the visible part.
+/// Sprites with manually set [`Sprite::rect`] are not cropped.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+pub fn element_sprite_clip<T: Component>(
+    mut query: Query<(&mut Sprite, &mut UiClip, &Handle<Image>, &Dimension), (With<UiLink<T>>, With<Element>, Or<(Changed<UiClip>, Changed<Dimension>)>)>,
+    images: Res<Assets<Image>>,
+) {
+    for (mut sprite, mut clip, handle, dimension) in &mut query {
+        if sprite.rect.is_some() && !clip.cropped { continue; }
+        let Some(image) = images.get(handle) else { continue };
+
+        #[cfg(feature = "verbose")]
+        info!("{} {} - Cropped sprite to UiClip", "--".yellow(), "ELEMENT".red());
+
+        let full = Rect::from_center_size(Vec2::ZERO, dimension.size);
+        let visible = clip.rect.intersect(full);
+        if visible == full || visible.is_empty() || dimension.size.cmple(Vec2::ZERO).any() {
+            sprite.rect = None;
+            sprite.anchor = bevy::sprite::Anchor::Center;
+            sprite.custom_size = Some(dimension.size);
+            clip.bypass_change_detection().cropped = false;
+            continue;
+        }
+
+        // Map the visible part into the texture, which has Y axis pointing down
+        let texture = image.size_f32();
+        let min = Vec2::new(visible.min.x - full.min.x, full.max.y - visible.max.y) / dimension.size;
+        let max = Vec2::new(visible.max.x - full.min.x, full.max.y - visible.min.y) / dimension.size;
+        sprite.rect = Some(Rect::from_corners(min * texture, max * texture));
+        sprite.custom_size = Some(visible.size());
+        sprite.anchor = bevy::sprite::Anchor::Custom(-visible.center() / visible.size());
+        clip.bypass_change_detection().cropped = true;
+    }
+}
+
+/// This system hides querried entities that are fully clipped by [`UiClip`]. Text can't be cropped,
+/// so it is hidden once any part of it is clipped. The previous [`Visibility`] is restored when it becomes visible again.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+pub fn element_clip_visibility<T: Component>(
+    mut query: Query<(&mut UiClip, &mut Visibility, &Dimension, Has<Element>, Has<Text>), (With<UiLink<T>>, Or<(Changed<UiClip>, Changed<Dimension>)>)>,
+) {
+    for (mut clip, mut visibility, dimension, is_element, is_text) in &mut query {
+        // Elements are centered, other entities are placed by their top-left corner
+        let full = if is_element { Rect::from_center_size(Vec2::ZERO, dimension.size) } else { Rect::new(0.0, -dimension.size.y, dimension.size.x, 0.0) };
+        let visible = clip.rect.intersect(full);
+        let hide = visible.is_empty() || (is_text && (full.size() - visible.size()).max_element() > 0.01);
+
+        match (hide, clip.hidden) {
+            (true, None) => {
+                #[cfg(feature = "verbose")]
+                info!("{} {} - Hidden by UiClip", "--".yellow(), "ELEMENT".red());
+                clip.bypass_change_detection().hidden = Some(*visibility);
+                *visibility = Visibility::Hidden;
+            },
+            (false, Some(hidden)) => {
+                clip.bypass_change_detection().hidden = None;
+                *visibility = hidden;
+            },
+            _ => {},
+        }
+    }
+}
+
+/// This system takes updated [`UiBorder`] and [`Dimension`] data and places a sprite on each side of the border.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+pub fn element_border_system<T: Component>(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut UiBorder, &Dimension, Has<Element>), (With<UiLink<T>>, Or<(Changed<UiBorder>, Changed<Dimension>)>)>,
+    mut sides: Query<(&mut Sprite, &mut Transform), Without<UiBorder>>,
+) {
+    for (entity, mut border, dimension, is_element) in &mut query {
+        let (size, width) = (dimension.size, border.width);
+
+        // Elements are centered, other entities are placed by their top-left corner
+        let origin = if is_element { Vec2::new(-size.x, size.y) / 2.0 } else { Vec2::ZERO };
+
+        // Left and right sides take the whole height, top and bottom fit between them
+        let inner = (size.x - width.x - width.z).max(0.0);
+        let rectangles = [
+            Rect::new(0.0, 0.0, width.x, size.y),
+            Rect::new(width.x, 0.0, width.x + inner, width.y),
+            Rect::new(size.x - width.z, 0.0, size.x, size.y),
+            Rect::new(width.x, size.y - width.w, width.x + inner, size.y),
+        ];
+
+        let mut spawned = border.sides.unwrap_or([Entity::PLACEHOLDER; 4]);
+        for (side, rectangle) in rectangles.iter().enumerate() {
+            let color = border.color[side];
+            let custom_size = Some(rectangle.size());
+            let translation = Vec3::new(origin.x + rectangle.min.x, origin.y - rectangle.min.y, 0.01);
+
+            match sides.get_mut(spawned[side]) {
+                Ok((mut sprite, mut transform)) => {
+                    if sprite.color != color { sprite.color = color; }
+                    if sprite.custom_size != custom_size { sprite.custom_size = custom_size; }
+                    if transform.translation != translation { transform.translation = translation; }
+                },
+                Err(_) => {
+                    spawned[side] = commands.spawn(SpriteBundle {
+                        sprite: Sprite { color, custom_size, anchor: bevy::sprite::Anchor::TopLeft, ..default() },
+                        transform: Transform::from_translation(translation),
+                        ..default()
+                    }).set_parent(entity).id();
+                },
+            }
+        }
+        if border.sides != Some(spawned) { border.bypass_change_detection().sides = Some(spawned); }
+    }
+}
+
 /// This system takes updated [`Dimension`] data and overwrites querried [`Handle<Image>`] data to fit.
 /// This is used to resize manually created render targets for secondary cameras, not textures.
 /// ## 📦 Types
@@ -397,23 +830,30 @@ pub fn element_image_size_from_dimension<T: Component>(
     }
 }
 
-/// This system takes updated [`Dimension`] data and reconstructs the mesh.
+/// This system takes updated [`Dimension`] data and reconstructs the mesh. If the entity has [`UiClip`], only the visible part is constructed.
+/// If the entity has [`UiSlice`], the mesh is nine-sliced. If the entity has [`UiShape`], the mesh is grown to fit its drop shadow.
 /// ## 📦 Types
 /// * Generic `(T)` - Marker component grouping entities into one widget type
 pub fn element_reconstruct_mesh<T: Component>(
     mut msh: ResMut<Assets<Mesh>>,
-    mut query: Query<(&Dimension, Option<&mut Handle<Mesh>>, Option<&mut Mesh2dHandle>, Option<&mut Aabb>), (With<UiLink<T>>, With<Element>, Or<(Changed<Dimension>, Added<Mesh2dHandle>)>)>,
+    mut query: Query<(&Dimension, Option<&UiClip>, Option<&UiSlice>, Option<&UiShape>, Option<&mut Handle<Mesh>>, Option<&mut Mesh2dHandle>, Option<&mut Aabb>), (With<UiLink<T>>, With<Element>, Or<(Changed<Dimension>, Added<Mesh2dHandle>, Changed<UiClip>, Changed<UiSlice>, Changed<UiShape>)>)>,
 ) {
-    for (dimension, mut mesh_option, mut mesh2d_option, mut aabb_option) in &mut query {
+    for (dimension, clip, slice, shape, mut mesh_option, mut mesh2d_option, mut aabb_option) in &mut query {
 
         #[cfg(feature = "verbose")]
         info!("{} {} - Reconstructed mesh size", "--".yellow(), "ELEMENT".red());
 
+        // Shapes draw their drop shadow outside of the node
+        let size = dimension.size + 2.0 * shape.map_or(0.0, |shape| shape.style.get_margin());
+        let full = Rect::from_center_size(Vec2::ZERO, size);
+        let visible = if let Some(clip) = clip { clip.rect.intersect(full) } else { full };
+        let (center, half_size) = (visible.center(), visible.half_size().max(Vec2::ZERO));
+
         if let Some(aabb) = aabb_option.as_mut() {
             // Create new culling boundary
             **aabb = Aabb {
-                center: Vec3A::ZERO,
-                half_extents: Vec3A::new(dimension.size.x/2.0, dimension.size.y/2.0, 1.0),
+                center: center.extend(0.0).into(),
+                half_extents: Vec3A::new(half_size.x, half_size.y, 1.0),
             };
         }
 
@@ -422,7 +862,7 @@ pub fn element_reconstruct_mesh<T: Component>(
             let _ = msh.remove(mesh.id());
 
             // Create new mesh
-            **mesh = msh.add(Rectangle {half_size: dimension.size / 2.0});
+            **mesh = msh.add(element_mesh(size, visible, slice));
         }
 
         if let Some(mesh2d) = mesh2d_option.as_mut() {
@@ -430,16 +870,74 @@ pub fn element_reconstruct_mesh<T: Component>(
             let _ = msh.remove(mesh2d.0.id());
 
             // Create new mesh
-            **mesh2d = Mesh2dHandle(msh.add(Rectangle {half_size: dimension.size / 2.0}));
+            **mesh2d = Mesh2dHandle(msh.add(element_mesh(size, visible, slice)));
+        }
+    }
+}
+
+/// Creates the element mesh covering the visible part of the node.
+fn element_mesh(size: Vec2, visible: Rect, slice: Option<&UiSlice>) -> Mesh {
+    match slice {
+        Some(slice) if !visible.is_empty() => sliced_rectangle(size, visible, slice.size, slice.texture),
+        _ => clipped_rectangle(size, visible.center(), visible.half_size().max(Vec2::ZERO)),
+    }
+}
+
+/// Creates a rectangle mesh covering only part of the node, with UVs matching the position within the whole node.
+fn clipped_rectangle(size: Vec2, center: Vec2, half_size: Vec2) -> Mesh {
+    let mut mesh = Mesh::from(Rectangle { half_size }).translated_by(center.extend(0.0));
+    if center == Vec2::ZERO && half_size == size / 2.0 { return mesh; }
+    if let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        let uvs: Vec<[f32; 2]> = positions.iter().map(|p| [p[0] / size.x + 0.5, 0.5 - p[1] / size.y]).collect();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    }
+    mesh
+}
+
+/// Creates a nine-sliced rectangle mesh covering only the visible part of the node. The frame is shrunk if it doesn't fit.
+fn sliced_rectangle(size: Vec2, visible: Rect, frame: Vec4, texture: Vec4) -> Mesh {
+    let fit = |a: f32, b: f32, max: f32| if a + b > max && a + b > 0.0 { (a * max / (a + b), b * max / (a + b)) } else { (a, b) };
+    let (left, right) = fit(frame.x, frame.z, size.x);
+    let (top, bottom) = fit(frame.y, frame.w, size.y);
+
+    // Grid lines of the slices from the top-left corner
+    let half = size / 2.0;
+    let xs = [-half.x, -half.x + left, half.x - right, half.x];
+    let ys = [half.y, half.y - top, -half.y + bottom, -half.y];
+    let us = [0.0, texture.x, 1.0 - texture.z, 1.0];
+    let vs = [0.0, texture.y, 1.0 - texture.w, 1.0];
+
+    let (mut positions, mut uvs, mut indices) = (Vec::new(), Vec::new(), Vec::new());
+    for row in 0..3 {
+        for column in 0..3 {
+            let cell = Rect::new(xs[column], ys[row + 1], xs[column + 1], ys[row]);
+            let clipped = cell.intersect(visible);
+            if clipped.is_empty() { continue }
+
+            // Vertices in the order top-left, top-right, bottom-right, bottom-left
+            let start = positions.len() as u32;
+            for corner in [Vec2::new(clipped.min.x, clipped.max.y), clipped.max, Vec2::new(clipped.max.x, clipped.min.y), clipped.min] {
+                let t = (corner - cell.min) / cell.size();
+                positions.push([corner.x, corner.y, 0.0]);
+                uvs.push([us[column].lerp(us[column + 1], t.x), vs[row + 1].lerp(vs[row], t.y)]);
+            }
+            indices.extend([start, start + 3, start + 2, start, start + 2, start + 1]);
         }
     }
+
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+    Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
 }
 
 /// This system takes updated [`TextLayoutInfo`] data and overwrites coresponding [`Layout`] data to match the text size.
 /// ## 📦 Types
 /// * Generic `(T)` - Marker component grouping entities into one widget type
 pub fn element_text_size_to_layout<T: Component>(
-    mut query: Query<(&mut UiLayout, &TextLayoutInfo, &Text, Option<&UiTextSize>), (With<UiLink<T>>, With<Element>, Changed<TextLayoutInfo>)>,
+    mut query: Query<(&mut UiLayout, &TextLayoutInfo, &Text, Option<&UiTextSize>), (With<UiLink<T>>, With<Element>, Without<UiTextWrap>, Changed<TextLayoutInfo>)>,
 ) {
     for (mut layout, text_info, text, optional_text_size) in &mut query {
         #[cfg(feature = "verbose")]
@@ -448,17 +946,7 @@ pub fn element_text_size_to_layout<T: Component>(
             Layout::Window(window) => {
                 let font_size = text.sections[0].style.font_size;
                 window.size = if let Some(text_size) = optional_text_size {
-                    match text_size.size {
-                        UiValueType::Ab(t) => Ab(text_info.logical_size/font_size * t.0).into(),
-                        UiValueType::Rl(t) => Rl(text_info.logical_size/font_size * t.0).into(),
-                        UiValueType::Rw(t) => Rw(text_info.logical_size/font_size * t.0).into(),
-                        UiValueType::Rh(t) => Rh(text_info.logical_size/font_size * t.0).into(),
-                        UiValueType::Em(t) => Em(text_info.logical_size/font_size * t.0).into(),
-                        UiValueType::Sp(t) => Sp(text_info.logical_size/font_size * t.0).into(),
-                        UiValueType::Vp(t) => Vp(text_info.logical_size/font_size * t.0).into(),
-                        UiValueType::Vw(t) => Vw(text_info.logical_size/font_size * t.0).into(),
-                        UiValueType::Vh(t) => Vh(text_info.logical_size/font_size * t.0).into(),
-                    }
+                    UiValue::from(text_size.size.map(|t| text_info.logical_size/font_size * t))
                 } else { Rh(text_info.logical_size).into() };
             },
             Layout::Solid(solid) => {solid.size = Ab(text_info.logical_size).into()},
@@ -467,7 +955,6 @@ pub fn element_text_size_to_layout<T: Component>(
     }
 }
 
-/// # WORK IN PROGRESS!!! DOES NOTHING CURRENTLY.
 /// This system takes updated [`TextLayoutInfo`] data and overwrites coresponding [`UiContent`] data to match the text size.
 /// ## 📦 Types
 /// * Generic `(T)` - Marker component grouping entities into one widget type
@@ -485,7 +972,7 @@ pub fn element_text_size_to_content<T: Component>(
 /// ## 📦 Types
 /// * Generic `(T)` - Marker component grouping entities into one widget type
 pub fn element_text_size_scale_fit_to_dimension<T: Component>(
-    mut query: Query<(&mut Transform, &Dimension, &TextLayoutInfo), (With<UiLink<T>>, With<Element>, Changed<Dimension>)>,
+    mut query: Query<(&mut Transform, &Dimension, &TextLayoutInfo), (With<UiLink<T>>, With<Element>, Without<UiTextWrap>, Changed<Dimension>)>,
 ) {
     for (mut transform, dimension, text_info) in &mut query {
         #[cfg(feature = "verbose")]
@@ -496,6 +983,109 @@ pub fn element_text_size_scale_fit_to_dimension<T: Component>(
     }
 }
 
+/// This system takes updated [`Dimension`] data and overwrites coresponding [`Text2dBounds`] data of [`UiTextWrap`] text to wrap at the node width.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+pub fn element_text_wrap_to_dimension<T: Component>(
+    mut query: Query<(&mut Text, &mut Text2dBounds, &mut Transform, &mut UiTextWrap, &Dimension), (With<UiLink<T>>, With<Element>, Or<(Changed<Dimension>, Changed<UiTextWrap>)>)>,
+) {
+    for (mut text, mut bounds, mut transform, mut wrap, dimension) in &mut query {
+        #[cfg(feature = "verbose")]
+        info!("{} {} - Wrapped text to fit into Dimension", "--".yellow(), "ELEMENT".red());
+
+        let size = Vec2::new(if dimension.size.x > 0.0 { dimension.size.x } else { f32::INFINITY }, f32::INFINITY);
+        if bounds.size != size { bounds.size = size; }
+        if transform.scale.truncate() != Vec2::ONE { transform.scale = Vec3::new(1.0, 1.0, transform.scale.z); }
+        if text.justify != wrap.justify { text.justify = wrap.justify; }
+
+        // More space can fit more lines, so the cut off text starts over from the full text
+        if dimension.size.x != wrap.size.x || dimension.size.y > wrap.size.y {
+            let untouched = text.sections.iter().map(|section| &section.value).eq(wrap.shown.iter());
+            if untouched && wrap.shown != wrap.source {
+                for (section, value) in text.sections.iter_mut().zip(&wrap.source) {
+                    section.value.clone_from(value);
+                }
+            }
+        }
+        if wrap.size != dimension.size { wrap.size = dimension.size; }
+    }
+}
+
+/// Returns the shown text of [`UiTextWrap`] cut at the first glyph past `max_lines` or below the height, or [`None`] if it fits.
+/// Lines are detected from the glyph positions, which are in physical pixels with Y axis pointing up.
+/// If the shown text is already cut and the ellipsis still doesn't fit, one more character is removed.
+fn wrap_cut_text(text_info: &TextLayoutInfo, sections: &[TextSection], wrap: &UiTextWrap, height: f32, scale: f32) -> Option<Vec<String>> {
+    let mut line = 0;
+    let mut previous: Option<Vec2> = None;
+    let cut = text_info.glyphs.iter().find(|glyph| {
+        if let Some(previous) = previous {
+            let font_size = sections.get(glyph.section_index).map_or(0.0, |section| section.style.font_size);
+            if glyph.position.x < previous.x || previous.y - glyph.position.y > font_size * scale { line += 1; }
+        }
+        previous = Some(glyph.position);
+        let below = line > 0 && text_info.logical_size.y - glyph.position.y / scale > height;
+        below || wrap.max_lines.is_some_and(|max| line >= max)
+    })?;
+
+    let mut values = wrap.shown.clone();
+    if !values.get(cut.section_index).is_some_and(|value| value.is_char_boundary(cut.byte_index)) { return None; }
+    values[cut.section_index].truncate(cut.byte_index);
+    for value in values.iter_mut().skip(cut.section_index + 1) { value.clear(); }
+
+    if wrap.overflow == UiTextOverflow::Ellipsis {
+        let last = values.iter().rposition(|value| !value.is_empty()).unwrap_or(0);
+        let trim = |value: &mut String| value.truncate(value.trim_end().len());
+        trim(&mut values[last]);
+        values[last].push('…');
+
+        // The ellipsis itself didn't fit, so one more character has to go
+        if values == wrap.shown {
+            values[last].pop();
+            values[last].pop();
+            trim(&mut values[last]);
+            values[last].push('…');
+        }
+    }
+    Some(values)
+}
+
+/// This system aligns the laid out [`UiTextWrap`] text inside of the node and cuts off the lines that don't fit.
+/// ## 📦 Types
+/// * Generic `(T)` - Marker component grouping entities into one widget type
+pub fn element_text_wrap_layout<T: Component>(
+    window: Query<&bevy::window::Window, With<PrimaryWindow>>,
+    mut query: Query<(&mut Text, &TextLayoutInfo, &Text2dBounds, &Dimension, &mut UiTextWrap, &mut Anchor), (With<UiLink<T>>, With<Element>, Or<(Changed<TextLayoutInfo>, Changed<Dimension>, Changed<UiTextWrap>)>)>,
+) {
+    let scale = if let Ok(window) = window.get_single() { window.resolution.scale_factor() } else { 1.0 };
+    for (mut text, text_info, bounds, dimension, mut wrap, mut anchor) in &mut query {
+
+        // Text changed by the user becomes the new full text
+        if !text.sections.iter().map(|section| &section.value).eq(wrap.shown.iter()) {
+            let values: Vec<String> = text.sections.iter().map(|section| section.value.clone()).collect();
+            wrap.source.clone_from(&values);
+            wrap.shown = values;
+        }
+
+        // Place the wrap bounds on the node, Text2d anchors the text block instead
+        let size = text_info.logical_size;
+        if size.x <= 0.0 || size.y <= 0.0 { continue; }
+        let width = if bounds.size.x.is_finite() { bounds.size.x } else { size.x };
+        let top = dimension.size.y * 0.5 - (dimension.size.y - size.y) * (wrap.align.0 + 1.0) * 0.5;
+        let custom = Anchor::Custom(Vec2::new(width / (2.0 * size.x) - 0.5, 0.5 - top / size.y));
+        if *anchor != custom { *anchor = custom; }
+
+        if wrap.overflow == UiTextOverflow::Visible { continue; }
+        let Some(values) = wrap_cut_text(text_info, &text.sections, &wrap, dimension.size.y, scale) else { continue };
+
+        if values != wrap.shown {
+            for (section, value) in text.sections.iter_mut().zip(&values) {
+                if section.value != *value { section.value.clone_from(value); }
+            }
+            wrap.shown = values;
+        }
+    }
+}
+
 
 // #===============#
 // #=== PLUGINS ===#
@@ -553,7 +1143,17 @@ impl <T:Component, N:Default + Component> UiCorePlugin<T, N> {
 }
 impl <T:Component, N:Default + Component> Plugin for UiCorePlugin<T, N> {
     fn build(&self, app: &mut App) {
+        #[cfg(feature = "serde")]
+        {
+            if !app.is_plugin_added::<UiAssetPlugin>() {
+                app.add_plugins(UiAssetPlugin);
+            }
+            app.add_systems(Update, ui_layout_source_system::<T, N>.in_set(UiSystems::Modify).before(UiSystems::Send));
+        }
+
         app
+            .add_event::<UiScrollEvent>()
+
             .add_systems(Update, (
                 element_text_size_to_layout::<T>,
                 element_text_size_to_content::<T>,
@@ -567,7 +1167,10 @@ impl <T:Component, N:Default + Component> Plugin for UiCorePlugin<T, N> {
                 send_content_size_to_node::<T, N>,
                 send_stack_to_node::<T, N>,
                 send_layout_control_to_node::<T, N>,
-                send_depth_bias_to_node::<T, N>
+                send_depth_bias_to_node::<T, N>,
+                send_layer_to_node::<T, N>,
+                send_font_size_to_node::<T, N>,
+                send_scroll_to_node::<T, N>,
             ).chain().in_set(UiSystems::Send).before(UiSystems::Compute))
 
             .add_systems(Update, (
@@ -578,14 +1181,24 @@ impl <T:Component, N:Default + Component> Plugin for UiCorePlugin<T, N> {
                 fetch_transform_from_node::<T, N>,
                 fetch_dimension_from_node::<T, N>,
                 element_fetch_transform_from_node::<T, N>,
+                fetch_scroll_from_node::<T, N>,
+                fetch_clip_from_node::<T, N>,
+                fetch_slice_from_node::<T, N>,
+                fetch_border_from_node::<T, N>,
+                fetch_text_size_from_node::<T, N>,
             ).in_set(UiSystems::Fetch).after(UiSystems::Compute))
 
             .add_systems(Update, (
-                element_sprite_size_from_dimension::<T>,
+                (element_sprite_size_from_dimension::<T>, element_sprite_clip::<T>).chain(),
                 element_image_size_from_dimension::<T>,
                 element_text_size_scale_fit_to_dimension::<T>,
+                element_text_wrap_to_dimension::<T>,
                 element_reconstruct_mesh::<T>,
+                element_border_system::<T>,
             ).in_set(UiSystems::Process).after(UiSystems::Fetch))
+
+            .add_systems(PostUpdate, element_clip_visibility::<T>.before(VisibilitySystems::VisibilityPropagate))
+            .add_systems(PostUpdate, element_text_wrap_layout::<T>.after(bevy::text::update_text2d_layout).before(bevy::text::calculate_bounds_text2d))
             ;
     }
 }
@@ -635,3 +1248,148 @@ impl <T:Component, N:Default + Component, G:GizmoConfigGroup> Plugin for UiDebug
             .add_systems(Update, debug_print_tree::<T, N>.after(UiSystems::Compute));
     }
 }
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy::text::{GlyphAtlasInfo, PositionedGlyph};
+
+    /// Returns the positions and uvs of the mesh vertices
+    fn mesh_vertices(mesh: &Mesh) -> Vec<(Vec2, Vec2)> {
+        let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else { panic!() };
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) else { panic!() };
+        positions.iter().zip(uvs).map(|(p, uv)| (Vec2::new(p[0], p[1]), Vec2::from(*uv))).collect()
+    }
+
+    fn quad(top_left: (f32, f32), bottom_right: (f32, f32), uv_min: (f32, f32), uv_max: (f32, f32)) -> Vec<(Vec2, Vec2)> {
+        let (tl, br, uv_min, uv_max) = (Vec2::from(top_left), Vec2::from(bottom_right), Vec2::from(uv_min), Vec2::from(uv_max));
+        vec![
+            (tl, uv_min),
+            (Vec2::new(br.x, tl.y), Vec2::new(uv_max.x, uv_min.y)),
+            (br, uv_max),
+            (Vec2::new(tl.x, br.y), Vec2::new(uv_min.x, uv_max.y)),
+        ]
+    }
+
+    /// Lays out the sections with 10 pixel wide glyphs and 20 pixel high lines, starting a new line at the breaks
+    fn text_layout(sections: &[&str], breaks: &[(usize, usize)]) -> TextLayoutInfo {
+        let mut positions = Vec::new();
+        let (mut line, mut column) = (0, 0);
+        for (section_index, value) in sections.iter().enumerate() {
+            for (byte_index, _) in value.char_indices() {
+                if breaks.contains(&(section_index, byte_index)) { (line, column) = (line + 1, 0); }
+                positions.push((line, column, section_index, byte_index));
+                column += 1;
+            }
+        }
+        let height = 20.0 * (line + 1) as f32;
+        let glyphs = positions.into_iter().map(|(line, column, section_index, byte_index)| PositionedGlyph {
+            position: Vec2::new(column as f32 * 10.0 + 5.0, height - line as f32 * 20.0 - 10.0),
+            size: Vec2::new(10.0, 16.0),
+            atlas_info: GlyphAtlasInfo { texture_atlas: Handle::default(), texture: Handle::default(), glyph_index: 0 },
+            section_index,
+            byte_index,
+        }).collect();
+        TextLayoutInfo { glyphs, logical_size: Vec2::new(100.0, height) }
+    }
+
+    fn text_wrap(wrap: UiTextWrap, shown: &[&str]) -> (UiTextWrap, Vec<TextSection>) {
+        let shown: Vec<String> = shown.iter().map(|value| value.to_string()).collect();
+        let sections = shown.iter().map(|value| TextSection::new(value, TextStyle { font_size: 16.0, ..default() })).collect();
+        (UiTextWrap { source: shown.clone(), shown, ..wrap }, sections)
+    }
+
+    #[test]
+    fn wrap_max_lines () {
+        let layout = text_layout(&["one two three"], &[(0, 4), (0, 8)]);
+        let (wrap, sections) = text_wrap(UiTextWrap::new().overflow(UiTextOverflow::Clip).max_lines(2), &["one two three"]);
+        assert_eq!(wrap_cut_text(&layout, &sections, &wrap, 1000.0, 1.0), Some(vec!["one two ".to_string()]));
+
+        let (wrap, sections) = text_wrap(UiTextWrap::new().overflow(UiTextOverflow::Clip).max_lines(3), &["one two three"]);
+        assert_eq!(wrap_cut_text(&layout, &sections, &wrap, 1000.0, 1.0), None);
+
+        // Sections after the cut are cleared
+        let layout = text_layout(&["Hello ", "world"], &[(1, 0)]);
+        let (wrap, sections) = text_wrap(UiTextWrap::new().overflow(UiTextOverflow::Ellipsis).max_lines(1), &["Hello ", "world"]);
+        assert_eq!(wrap_cut_text(&layout, &sections, &wrap, 1000.0, 1.0), Some(vec!["Hello…".to_string(), String::new()]));
+    }
+
+    #[test]
+    fn wrap_height () {
+        let layout = text_layout(&["one two three"], &[(0, 4), (0, 8)]);
+        let (wrap, sections) = text_wrap(UiTextWrap::new().overflow(UiTextOverflow::Clip), &["one two three"]);
+        assert_eq!(wrap_cut_text(&layout, &sections, &wrap, 60.0, 1.0), None);
+        assert_eq!(wrap_cut_text(&layout, &sections, &wrap, 45.0, 1.0), Some(vec!["one two ".to_string()]));
+        assert_eq!(wrap_cut_text(&layout, &sections, &wrap, 25.0, 1.0), Some(vec!["one ".to_string()]));
+
+        // The first line is always shown
+        assert_eq!(wrap_cut_text(&layout, &sections, &wrap, 5.0, 1.0), Some(vec!["one ".to_string()]));
+
+        // Glyph positions are in physical pixels
+        let mut layout = layout;
+        for glyph in &mut layout.glyphs { glyph.position *= 2.0; }
+        layout.logical_size.y = 60.0;
+        assert_eq!(wrap_cut_text(&layout, &sections, &wrap, 45.0, 2.0), Some(vec!["one two ".to_string()]));
+    }
+
+    #[test]
+    fn wrap_ellipsis () {
+        let layout = text_layout(&["one twoo three"], &[(0, 9)]);
+        let (wrap, sections) = text_wrap(UiTextWrap::new().overflow(UiTextOverflow::Ellipsis).max_lines(1), &["one twoo three"]);
+        let shown = wrap_cut_text(&layout, &sections, &wrap, 1000.0, 1.0).unwrap();
+        assert_eq!(shown, vec!["one twoo…".to_string()]);
+
+        // The ellipsis wrapped to the next line, so one more character is removed
+        let layout = text_layout(&["one twoo…"], &[(0, 8)]);
+        let wrap = UiTextWrap { shown, ..wrap };
+        assert_eq!(wrap_cut_text(&layout, &sections, &wrap, 1000.0, 1.0), Some(vec!["one two…".to_string()]));
+
+        // Once it fits, nothing changes
+        let layout = text_layout(&["one two…"], &[]);
+        let wrap = UiTextWrap { shown: vec!["one two…".to_string()], ..wrap };
+        assert_eq!(wrap_cut_text(&layout, &sections, &wrap, 1000.0, 1.0), None);
+    }
+
+    #[test]
+    fn sliced_full () {
+        let size = Vec2::splat(100.0);
+        let mesh = sliced_rectangle(size, Rect::from_center_size(Vec2::ZERO, size), Vec4::splat(10.0), Vec4::splat(0.25));
+        let vertices = mesh_vertices(&mesh);
+        assert_eq!(vertices.len(), 36);
+        assert_eq!(mesh.indices().unwrap().len(), 54);
+
+        // Corners keep their size, the centre is stretched
+        assert_eq!(vertices[0..4], quad((-50.0, 50.0), (-40.0, 40.0), (0.0, 0.0), (0.25, 0.25)));
+        assert_eq!(vertices[16..20], quad((-40.0, 40.0), (40.0, -40.0), (0.25, 0.25), (0.75, 0.75)));
+        assert_eq!(vertices[32..36], quad((40.0, -40.0), (50.0, -50.0), (0.75, 0.75), (1.0, 1.0)));
+    }
+
+    #[test]
+    fn sliced_fit () {
+        // The frame is shrunk proportionally and the empty middle slices are skipped
+        let size = Vec2::new(100.0, 40.0);
+        let mesh = sliced_rectangle(size, Rect::from_center_size(Vec2::ZERO, size), Vec4::new(60.0, 30.0, 60.0, 30.0), Vec4::splat(0.25));
+        let vertices = mesh_vertices(&mesh);
+        assert_eq!(vertices.len(), 16);
+        assert_eq!(vertices[0..4], quad((-50.0, 20.0), (0.0, 0.0), (0.0, 0.0), (0.25, 0.25)));
+        assert_eq!(vertices[12..16], quad((0.0, 0.0), (50.0, -20.0), (0.75, 0.75), (1.0, 1.0)));
+    }
+
+    #[test]
+    fn sliced_clipped () {
+        // Half of the top-left corner and the bottom row are hidden
+        let mesh = sliced_rectangle(Vec2::splat(100.0), Rect::new(-40.0, -15.0, 50.0, 50.0), Vec4::splat(20.0), Vec4::splat(0.25));
+        let vertices = mesh_vertices(&mesh);
+        assert_eq!(vertices.len(), 24);
+        assert_eq!(vertices[0..4], quad((-40.0, 50.0), (-30.0, 30.0), (0.125, 0.0), (0.25, 0.25)));
+        assert_eq!(vertices[12..16], quad((-40.0, 30.0), (-30.0, -15.0), (0.125, 0.25), (0.25, 0.625)));
+
+        // Nothing is visible
+        let mesh = sliced_rectangle(Vec2::splat(100.0), Rect::new(60.0, 60.0, 70.0, 70.0), Vec4::splat(20.0), Vec4::splat(0.25));
+        assert!(mesh_vertices(&mesh).is_empty());
+    }
+}
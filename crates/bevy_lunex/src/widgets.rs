@@ -0,0 +1,800 @@
+use crate::*;
+use bevy::window::PrimaryWindow;
+
+
+// #====================#
+// #=== WIDGET VALUE ===#
+
+/// Typed value of a widget. Changing it updates the widget visuals.
+///
+/// Widgets also send [`UiChangeEvent`] with the value formatted as string, but only when the user changes it.
+/// ## 🛠️ Example
+/// ```
+/// fn volume(query: Query<&UiWidgetValue<f32>, (With<VolumeSlider>, Changed<UiWidgetValue<f32>>)>) {
+///     for volume in &query {
+///         info!("Volume: {}", volume.value);
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct UiWidgetValue<V> {
+    /// The value
+    pub value: V,
+}
+impl <V> UiWidgetValue<V> {
+    /// Creates new struct
+    pub fn new(value: V) -> Self {
+        UiWidgetValue { value }
+    }
+}
+
+/// Makes the widget focusable with navigation and clickable through [`UiClickEvent`], unless the user already did.
+/// The navigation axes used by the widget are captured. Widgets driving their own selection get [`UiManualSelect`].
+fn widget_setup(commands: &mut Commands, entity: Entity, focusable: Option<Mut<UiFocusable>>, has_emitter: bool, capture: BVec2, manual_select: bool) {
+    match focusable {
+        Some(mut focusable) => focusable.capture |= capture,
+        None => { commands.entity(entity).insert(UiFocusable { capture, ..UiFocusable::new() }); },
+    }
+    if !has_emitter { commands.entity(entity).insert(UiClickEmitter::SELF); }
+    if manual_select { commands.entity(entity).insert(UiManualSelect); }
+}
+
+
+// #==============#
+// #=== SLIDER ===#
+
+/// Slider selecting a number from range by dragging the track or the handle.
+/// The value is stored in [`UiWidgetValue<f32>`].
+///
+/// The handle and the fill are nodes with window layout. The slider moves the handle position and resizes the fill
+/// along its axis in [`Rl`] units, so anchor the fill to the start of the track.
+/// When focused, the slider is adjusted by directional navigation along its axis.
+/// ## 🛠️ Example
+/// ```
+/// let handle = ui.spawn((
+///     UiLink::<MainUi>::path("Volume/Handle"),
+///     UiLayout::window().pos(Rl((0.0, 50.0))).anchor(Anchor::Center).size(Rh((100.0, 100.0))).pack::<Base>(),
+///     UiImage2dBundle::from(assets.load("handle.png")),
+/// )).id();
+///
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Volume"),
+///     UiLayout::window().pos(Rl((10.0, 40.0))).size(Rl((30.0, 4.0))).pack::<Base>(),
+///     UiImage2dBundle::from(assets.load("track.png")),
+///     UiSlider::new(0.0, 100.0).step(5.0).handle(handle),
+///     UiWidgetValue::new(50.0),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct UiSlider {
+    /// The lowest value
+    pub min: f32,
+    /// The highest value
+    pub max: f32,
+    /// If set, the value is rounded to multiples of this step
+    pub step: Option<f32>,
+    /// If the slider goes from bottom to top instead of left to right
+    pub vertical: bool,
+    /// Node moved to the position of the value
+    pub handle: Option<Entity>,
+    /// Node resized to the position of the value
+    pub fill: Option<Entity>,
+}
+impl UiSlider {
+    /// Creates new slider with the range.
+    pub fn new(min: f32, max: f32) -> Self {
+        UiSlider {
+            min,
+            max,
+            step: None,
+            vertical: false,
+            handle: None,
+            fill: None,
+        }
+    }
+    /// Replaces the step with a new value.
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = Some(step);
+        self
+    }
+    /// Makes the slider go from bottom to top.
+    pub fn vertical(mut self) -> Self {
+        self.vertical = true;
+        self
+    }
+    /// Replaces the handle with a new entity.
+    pub fn handle(mut self, handle: Entity) -> Self {
+        self.handle = Some(handle);
+        self
+    }
+    /// Replaces the fill with a new entity.
+    pub fn fill(mut self, fill: Entity) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+    /// Clamps the value to the range and rounds it to the step.
+    pub fn snap(&self, value: f32) -> f32 {
+        let value = match self.step {
+            Some(step) if step > 0.0 => self.min + ((value - self.min) / step).round() * step,
+            _ => value,
+        };
+        value.clamp(self.min.min(self.max), self.max.max(self.min))
+    }
+    /// Returns the position of the value within the range, ranging from `0.0` to `1.0`.
+    pub fn get_fraction(&self, value: f32) -> f32 {
+        if self.max == self.min { return 0.0; }
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+    /// Returns the amount the value changes by with navigation.
+    fn get_increment(&self) -> f32 {
+        self.step.unwrap_or((self.max - self.min) / 20.0)
+    }
+}
+
+/// Marks the handle of [`UiSlider`]. It is inserted automatically.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiSliderHandle {
+    /// The slider entity
+    pub slider: Entity,
+}
+
+fn ui_slider_setup_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &UiSlider, Option<&mut UiFocusable>, Has<UiWidgetValue<f32>>), Added<UiSlider>>,
+) {
+    for (entity, slider, focusable, has_value) in &mut query {
+        widget_setup(&mut commands, entity, focusable, true, BVec2::new(!slider.vertical, slider.vertical), false);
+        if !has_value { commands.entity(entity).insert(UiWidgetValue::new(slider.min)); }
+        if let Some(handle) = slider.handle { commands.entity(handle).insert(UiSliderHandle { slider: entity }); }
+    }
+}
+
+/// Sets the value from the pointer position when the track or the handle is pressed or dragged.
+fn ui_slider_pointer_system(
+    mut downs: EventReader<Pointer<Down>>,
+    mut drags: EventReader<Pointer<Drag>>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, &OrthographicProjection)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    handles: Query<&UiSliderHandle>,
+    mut query: Query<(&UiSlider, &mut UiWidgetValue<f32>, &Dimension, &GlobalTransform, Has<Element>)>,
+    mut events: EventWriter<UiChangeEvent>,
+) {
+    let downs = downs.read().map(|event| (event.target, event.button, &event.pointer_location));
+    let drags = drags.read().map(|event| (event.target, event.button, &event.pointer_location));
+    for (target, button, location) in downs.chain(drags) {
+        if button != PointerButton::Primary { continue }
+        let target = handles.get(target).map_or(target, |handle| handle.slider);
+        let Ok((slider, mut value, dimension, transform, is_element)) = query.get_mut(target) else { continue };
+        let Some((_, position)) = pointer_world_position(location, &cameras, &primary_window) else { continue };
+
+        let rect = node_rect(dimension, is_element);
+        let local = node_local_position(position, transform);
+        let fraction = if slider.vertical { (local.y - rect.min.y) / rect.height() } else { (local.x - rect.min.x) / rect.width() };
+        let new = slider.snap(slider.min + fraction.clamp(0.0, 1.0) * (slider.max - slider.min));
+        if value.value != new {
+            value.value = new;
+            events.send(UiChangeEvent { target, value: new.to_string() });
+        }
+    }
+}
+
+fn ui_slider_visual_system(
+    query: Query<(&UiSlider, &UiWidgetValue<f32>), Or<(Changed<UiWidgetValue<f32>>, Changed<UiSlider>)>>,
+    mut layouts: Query<&mut UiLayout, Without<UiSlider>>,
+) {
+    for (slider, value) in &query {
+        let fraction = slider.get_fraction(value.value);
+        if let Some(Ok(mut layout)) = slider.handle.map(|handle| layouts.get_mut(handle)) {
+            if let Layout::Window(window) = &mut layout.layout {
+                if slider.vertical { window.pos.set_y(Rl(100.0 - fraction * 100.0)) } else { window.pos.set_x(Rl(fraction * 100.0)) }
+            }
+        }
+        if let Some(Ok(mut layout)) = slider.fill.map(|fill| layouts.get_mut(fill)) {
+            if let Layout::Window(window) = &mut layout.layout {
+                if slider.vertical { window.size.set_y(Rl(fraction * 100.0)) } else { window.size.set_x(Rl(fraction * 100.0)) }
+            }
+        }
+    }
+}
+
+
+// #================#
+// #=== CHECKBOX ===#
+
+/// Checkbox toggled on click. The value is stored in [`UiWidgetValue<bool>`]
+/// and drives [`UiAnimator<Selected>`] of the entity, if present.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Settings/Vsync"),
+///     UiLayout::window().pos(Rl((10.0, 40.0))).size(Rh((5.0, 5.0))).pack::<Base>(),
+///     UiImage2dBundle::from(assets.load("checkbox.png")),
+///     UiLayout::window().size(Rh((6.0, 6.0))).pack::<Selected>(),
+///     UiAnimator::<Selected>::new(),
+///     UiCheckbox,
+///     UiWidgetValue::new(true),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UiCheckbox;
+
+fn ui_checkbox_setup_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, Option<&mut UiFocusable>, Has<UiClickEmitter>, Has<UiWidgetValue<bool>>), Added<UiCheckbox>>,
+) {
+    for (entity, focusable, has_emitter, has_value) in &mut query {
+        widget_setup(&mut commands, entity, focusable, has_emitter, BVec2::FALSE, true);
+        if !has_value { commands.entity(entity).insert(UiWidgetValue::new(false)); }
+    }
+}
+
+fn ui_checkbox_click_system(
+    mut clicks: EventReader<UiClickEvent>,
+    mut query: Query<&mut UiWidgetValue<bool>, With<UiCheckbox>>,
+    mut events: EventWriter<UiChangeEvent>,
+) {
+    for event in clicks.read() {
+        let Ok(mut value) = query.get_mut(event.target) else { continue };
+        value.value = !value.value;
+        events.send(UiChangeEvent { target: event.target, value: value.value.to_string() });
+    }
+}
+
+fn ui_checkbox_visual_system(mut query: Query<(&UiWidgetValue<bool>, &mut UiAnimator<Selected>), (With<UiCheckbox>, Changed<UiWidgetValue<bool>>)>) {
+    for (value, mut animator) in &mut query {
+        if animator.is_forward() != value.value { animator.set_forward(value.value); }
+    }
+}
+
+
+// #=============#
+// #=== RADIO ===#
+
+/// Group of [`UiRadio`] buttons. The index of the selected button is stored in [`UiWidgetValue<usize>`].
+/// The group can be any entity, for example a node containing the buttons.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UiRadioGroup;
+
+/// Button of [`UiRadioGroup`] selected on click. Only one button in the group can be selected,
+/// which drives its [`UiAnimator<Selected>`], if present.
+/// ## 🛠️ Example
+/// ```
+/// let group = ui.spawn((
+///     UiLink::<MainUi>::path("Difficulty"),
+///     UiLayout::window().pos(Rl((10.0, 40.0))).size(Rl((40.0, 10.0))).pack::<Base>(),
+///     UiRadioGroup,
+/// )).id();
+///
+/// for (index, name) in ["Easy", "Normal", "Hard"].into_iter().enumerate() {
+///     ui.spawn((
+///         UiLink::<MainUi>::path(format!("Difficulty/{name}")),
+///         UiLayout::window().x(Rl(index as f32 * 33.3)).size(Rl((30.0, 100.0))).pack::<Base>(),
+///         UiImage2dBundle::from(assets.load("radio.png")),
+///         UiColor::<Base>::new(Color::WHITE),
+///         UiColor::<Selected>::new(Color::srgb(1.0, 0.8, 0.2)),
+///         UiAnimator::<Selected>::new(),
+///         UiRadio::new(group, index),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiRadio {
+    /// The group entity
+    pub group: Entity,
+    /// Index of this button in the group
+    pub index: usize,
+}
+impl UiRadio {
+    /// Creates new struct
+    pub fn new(group: Entity, index: usize) -> Self {
+        UiRadio { group, index }
+    }
+}
+
+fn ui_radio_setup_system(
+    mut commands: Commands,
+    mut radios: Query<(Entity, Option<&mut UiFocusable>, Has<UiClickEmitter>), Added<UiRadio>>,
+    groups: Query<Entity, (Added<UiRadioGroup>, Without<UiWidgetValue<usize>>)>,
+) {
+    for (entity, focusable, has_emitter) in &mut radios {
+        widget_setup(&mut commands, entity, focusable, has_emitter, BVec2::FALSE, true);
+    }
+    for entity in &groups {
+        commands.entity(entity).insert(UiWidgetValue::<usize>::new(0));
+    }
+}
+
+fn ui_radio_click_system(
+    mut clicks: EventReader<UiClickEvent>,
+    radios: Query<&UiRadio>,
+    mut groups: Query<&mut UiWidgetValue<usize>, With<UiRadioGroup>>,
+    mut events: EventWriter<UiChangeEvent>,
+) {
+    for event in clicks.read() {
+        let Ok(radio) = radios.get(event.target) else { continue };
+        let Ok(mut value) = groups.get_mut(radio.group) else { continue };
+        if value.value != radio.index {
+            value.value = radio.index;
+            events.send(UiChangeEvent { target: radio.group, value: radio.index.to_string() });
+        }
+    }
+}
+
+fn ui_radio_visual_system(
+    groups: Query<Ref<UiWidgetValue<usize>>, With<UiRadioGroup>>,
+    mut radios: Query<(Ref<UiRadio>, &mut UiAnimator<Selected>)>,
+) {
+    for (radio, mut animator) in &mut radios {
+        let Ok(value) = groups.get(radio.group) else { continue };
+        if !value.is_changed() && !radio.is_changed() { continue }
+        let selected = value.value == radio.index;
+        if animator.is_forward() != selected { animator.set_forward(selected); }
+    }
+}
+
+
+// #===============#
+// #=== SPINBOX ===#
+
+/// Spinbox changing a number by steps. The value is stored in [`UiWidgetValue<f32>`].
+///
+/// Clicking the increment and decrement buttons changes the value, so does horizontal navigation when focused.
+/// The formatted value is written into the text entity, if set.
+/// ## 🛠️ Example
+/// ```
+/// ui.spawn((
+///     UiLink::<MainUi>::path("Players"),
+///     UiLayout::window().pos(Rl((10.0, 40.0))).size(Rl((20.0, 6.0))).pack::<Base>(),
+///     UiSpinbox::new(1.0, 8.0).increment(plus).decrement(minus).text(label),
+///     UiWidgetValue::new(2.0),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct UiSpinbox {
+    /// The lowest value
+    pub min: f32,
+    /// The highest value
+    pub max: f32,
+    /// The amount the value changes by
+    pub step: f32,
+    /// If the value wraps around when going past the range
+    pub wrap: bool,
+    /// Number of decimal places of the displayed value
+    pub precision: usize,
+    /// Button increasing the value
+    pub increment: Option<Entity>,
+    /// Button decreasing the value
+    pub decrement: Option<Entity>,
+    /// Text entity displaying the value
+    pub text: Option<Entity>,
+}
+impl UiSpinbox {
+    /// Creates new spinbox with the range.
+    pub fn new(min: f32, max: f32) -> Self {
+        UiSpinbox {
+            min,
+            max,
+            step: 1.0,
+            wrap: false,
+            precision: 0,
+            increment: None,
+            decrement: None,
+            text: None,
+        }
+    }
+    /// Replaces the step with a new value.
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+    /// Makes the value wrap around when going past the range.
+    pub fn wrap(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+    /// Replaces the displayed decimal places with a new value.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+    /// Replaces the increment button with a new entity.
+    pub fn increment(mut self, button: Entity) -> Self {
+        self.increment = Some(button);
+        self
+    }
+    /// Replaces the decrement button with a new entity.
+    pub fn decrement(mut self, button: Entity) -> Self {
+        self.decrement = Some(button);
+        self
+    }
+    /// Replaces the text entity with a new entity.
+    pub fn text(mut self, text: Entity) -> Self {
+        self.text = Some(text);
+        self
+    }
+    /// Returns the value moved by the number of steps, clamped or wrapped to the range.
+    pub fn spin(&self, value: f32, steps: f32) -> f32 {
+        let (low, high) = (self.min.min(self.max), self.max.max(self.min));
+        let value = value + self.step * steps;
+        if self.wrap && value > high { return low; }
+        if self.wrap && value < low { return high; }
+        value.clamp(low, high)
+    }
+    /// Returns the value formatted with the precision. Values rounding to zero are shown without the minus sign.
+    pub fn format(&self, value: f32) -> String {
+        let value = if value.abs() < 0.5 * 10f32.powi(-(self.precision as i32)) { 0.0 } else { value };
+        format!("{:.*}", self.precision, value)
+    }
+}
+
+/// Marks the increment and decrement buttons of [`UiSpinbox`]. It is inserted automatically.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct UiSpinboxButton {
+    /// The spinbox entity
+    pub spinbox: Entity,
+    /// Number of steps the button moves the value by
+    pub steps: f32,
+}
+
+fn ui_spinbox_setup_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &UiSpinbox, Option<&mut UiFocusable>, Has<UiWidgetValue<f32>>), Added<UiSpinbox>>,
+    emitters: Query<(), With<UiClickEmitter>>,
+) {
+    for (entity, spinbox, focusable, has_value) in &mut query {
+        widget_setup(&mut commands, entity, focusable, true, BVec2::new(true, false), false);
+        if !has_value { commands.entity(entity).insert(UiWidgetValue::new(spinbox.min)); }
+        for (button, steps) in [(spinbox.increment, 1.0), (spinbox.decrement, -1.0)] {
+            let Some(button) = button else { continue };
+            commands.entity(button).insert(UiSpinboxButton { spinbox: entity, steps });
+            if !emitters.contains(button) { commands.entity(button).insert(UiClickEmitter::SELF); }
+        }
+    }
+}
+
+fn ui_spinbox_click_system(
+    mut clicks: EventReader<UiClickEvent>,
+    buttons: Query<&UiSpinboxButton>,
+    mut query: Query<(&UiSpinbox, &mut UiWidgetValue<f32>)>,
+    mut events: EventWriter<UiChangeEvent>,
+) {
+    for event in clicks.read() {
+        let Ok(button) = buttons.get(event.target) else { continue };
+        let Ok((spinbox, mut value)) = query.get_mut(button.spinbox) else { continue };
+        let new = spinbox.spin(value.value, button.steps);
+        if value.value != new {
+            value.value = new;
+            events.send(UiChangeEvent { target: button.spinbox, value: spinbox.format(new) });
+        }
+    }
+}
+
+fn ui_spinbox_visual_system(
+    query: Query<(&UiSpinbox, &UiWidgetValue<f32>), Or<(Changed<UiWidgetValue<f32>>, Changed<UiSpinbox>)>>,
+    mut texts: Query<&mut Text>,
+) {
+    for (spinbox, value) in &query {
+        let Some(Ok(mut text)) = spinbox.text.map(|text| texts.get_mut(text)) else { continue };
+        if let Some(section) = text.sections.first_mut() {
+            section.value = spinbox.format(value.value);
+        }
+    }
+}
+
+
+// #================#
+// #=== DROPDOWN ===#
+
+/// Dropdown selecting one of the options. The index of the selected option is stored in [`UiWidgetValue<usize>`].
+///
+/// Clicking the dropdown shows the list node, clicking one of its [`UiDropdownOption`]s selects it
+/// and clicking anywhere else hides the list. The open state drives [`UiAnimator<Selected>`] of the dropdown
+/// and the selected option drives [`UiAnimator<Selected>`] of the option, if present.
+/// The label of the selected option is written into the text entity, if set.
+/// If the list has [`UiPopover`], it is opened and closed through it instead.
+/// ## 🛠️ Example
+/// ```
+/// let dropdown = ui.spawn((
+///     UiLink::<MainUi>::path("Quality"),
+///     UiLayout::window().pos(Rl((10.0, 40.0))).size(Rl((20.0, 6.0))).pack::<Base>(),
+///     UiImage2dBundle::from(assets.load("dropdown.png")),
+///     UiDropdown::new(["Low", "Medium", "High"]).list(list).text(label),
+/// )).id();
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct UiDropdown {
+    /// Labels of the options
+    pub options: Vec<String>,
+    /// Node containing the options, shown while open
+    pub list: Option<Entity>,
+    /// Text entity displaying the selected option
+    pub text: Option<Entity>,
+    /// If the list is shown
+    open: bool,
+}
+impl UiDropdown {
+    /// Creates new dropdown with the option labels.
+    pub fn new(options: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        UiDropdown {
+            options: options.into_iter().map(Into::into).collect(),
+            list: None,
+            text: None,
+            open: false,
+        }
+    }
+    /// Replaces the list with a new entity.
+    pub fn list(mut self, list: Entity) -> Self {
+        self.list = Some(list);
+        self
+    }
+    /// Replaces the text entity with a new entity.
+    pub fn text(mut self, text: Entity) -> Self {
+        self.text = Some(text);
+        self
+    }
+    /// Checks if the list is shown
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+    /// Shows or hides the list.
+    pub fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+    /// Returns the label of the option.
+    pub fn get_label(&self, index: usize) -> Option<&str> {
+        self.options.get(index).map(String::as_str)
+    }
+}
+
+/// Option of [`UiDropdown`], selected on click.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiDropdownOption {
+    /// The dropdown entity
+    pub dropdown: Entity,
+    /// Index of the option in the dropdown
+    pub index: usize,
+}
+impl UiDropdownOption {
+    /// Creates new struct
+    pub fn new(dropdown: Entity, index: usize) -> Self {
+        UiDropdownOption { dropdown, index }
+    }
+}
+
+fn ui_dropdown_setup_system(
+    mut commands: Commands,
+    mut dropdowns: Query<(Entity, Option<&mut UiFocusable>, Has<UiClickEmitter>, Has<UiWidgetValue<usize>>), Added<UiDropdown>>,
+    mut options: Query<(Entity, Option<&mut UiFocusable>, Has<UiClickEmitter>), (Added<UiDropdownOption>, Without<UiDropdown>)>,
+) {
+    for (entity, focusable, has_emitter, has_value) in &mut dropdowns {
+        widget_setup(&mut commands, entity, focusable, has_emitter, BVec2::FALSE, true);
+        if !has_value { commands.entity(entity).insert(UiWidgetValue::<usize>::new(0)); }
+    }
+    for (entity, focusable, has_emitter) in &mut options {
+        widget_setup(&mut commands, entity, focusable, has_emitter, BVec2::FALSE, true);
+    }
+}
+
+fn ui_dropdown_click_system(
+    mut downs: EventReader<Pointer<Down>>,
+    mut clicks: EventReader<UiClickEvent>,
+    mut focus: ResMut<UiFocus>,
+    emitters: Query<&UiClickEmitter>,
+    options: Query<&UiDropdownOption>,
+    mut query: Query<(Entity, &mut UiDropdown, &mut UiWidgetValue<usize>)>,
+    mut events: EventWriter<UiChangeEvent>,
+) {
+    // Pressing anything outside of the dropdown closes it
+    for event in downs.read() {
+        let target = emitters.get(event.target).ok().and_then(|emitter| emitter.target).unwrap_or(event.target);
+        let owner = options.get(target).map_or(target, |option| option.dropdown);
+        for (entity, mut dropdown, _) in &mut query {
+            if dropdown.open && entity != owner { dropdown.open = false; }
+        }
+    }
+
+    for event in clicks.read() {
+        if let Ok((_, mut dropdown, _)) = query.get_mut(event.target) {
+            dropdown.open = !dropdown.open;
+            continue;
+        }
+
+        let Ok(option) = options.get(event.target) else { continue };
+        let Ok((entity, mut dropdown, mut value)) = query.get_mut(option.dropdown) else { continue };
+        dropdown.open = false;
+        if focus.get() == Some(event.target) { focus.focus(entity); }
+        if value.value != option.index {
+            value.value = option.index;
+            events.send(UiChangeEvent { target: entity, value: dropdown.get_label(option.index).map_or_else(|| option.index.to_string(), Into::into) });
+        }
+    }
+}
+
+fn ui_dropdown_visual_system(
+    query: Query<(Entity, Ref<UiDropdown>, Ref<UiWidgetValue<usize>>)>,
+    mut options: Query<(&UiDropdownOption, &mut UiAnimator<Selected>)>,
+    mut animators: Query<&mut UiAnimator<Selected>, Without<UiDropdownOption>>,
+    mut popovers: Query<&mut UiPopover>,
+    mut visibility: Query<&mut Visibility>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, dropdown, value) in &query {
+        if !dropdown.is_changed() && !value.is_changed() { continue }
+
+        // Lists with popover are positioned and animated by it
+        if let Some(list) = dropdown.list {
+            if let Ok(mut popover) = popovers.get_mut(list) {
+                if popover.is_open() != dropdown.open { popover.set_open(dropdown.open); }
+            } else if let Ok(mut visibility) = visibility.get_mut(list) {
+                let new = if dropdown.open { Visibility::Inherited } else { Visibility::Hidden };
+                if *visibility != new { *visibility = new; }
+            }
+        }
+        if let Ok(mut animator) = animators.get_mut(entity) {
+            if animator.is_forward() != dropdown.open { animator.set_forward(dropdown.open); }
+        }
+        if let (Some(Ok(mut text)), Some(label)) = (dropdown.text.map(|text| texts.get_mut(text)), dropdown.get_label(value.value)) {
+            if let Some(section) = text.sections.first_mut() {
+                if section.value != label { section.value = label.to_string(); }
+            }
+        }
+        for (option, mut animator) in &mut options {
+            if option.dropdown != entity { continue }
+            let selected = option.index == value.value;
+            if animator.is_forward() != selected { animator.set_forward(selected); }
+        }
+    }
+}
+
+
+// #==================#
+// #=== NAVIGATION ===#
+
+/// Changes the value of the focused slider or spinbox with directional navigation.
+fn ui_widget_navigation_system(
+    mut navigation: EventReader<UiNavigationEvent>,
+    focus: Res<UiFocus>,
+    mut sliders: Query<(&UiSlider, &mut UiWidgetValue<f32>), Without<UiSpinbox>>,
+    mut spinboxes: Query<(&UiSpinbox, &mut UiWidgetValue<f32>), Without<UiSlider>>,
+    mut events: EventWriter<UiChangeEvent>,
+) {
+    let Some(target) = focus.get() else {
+        navigation.clear();
+        return;
+    };
+    for event in navigation.read() {
+        let (horizontal, vertical) = match event {
+            UiNavigationEvent::Left => (-1.0, 0.0),
+            UiNavigationEvent::Right => (1.0, 0.0),
+            UiNavigationEvent::Down => (0.0, -1.0),
+            UiNavigationEvent::Up => (0.0, 1.0),
+            _ => continue,
+        };
+
+        if let Ok((slider, mut value)) = sliders.get_mut(target) {
+            let steps = if slider.vertical { vertical } else { horizontal };
+            let new = slider.snap(value.value + slider.get_increment() * steps * (slider.max - slider.min).signum());
+            if steps != 0.0 && value.value != new {
+                value.value = new;
+                events.send(UiChangeEvent { target, value: new.to_string() });
+            }
+        }
+        if let Ok((spinbox, mut value)) = spinboxes.get_mut(target) {
+            let new = spinbox.spin(value.value, horizontal);
+            if horizontal != 0.0 && value.value != new {
+                value.value = new;
+                events.send(UiChangeEvent { target, value: spinbox.format(new) });
+            }
+        }
+    }
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// Plugin adding logic for all widgets, see [`UiSlider`], [`UiCheckbox`], [`UiRadio`], [`UiSpinbox`] and [`UiDropdown`]
+pub struct UiWidgetsPlugin;
+impl Plugin for UiWidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                ui_slider_setup_system,
+                ui_checkbox_setup_system,
+                ui_radio_setup_system,
+                ui_spinbox_setup_system,
+                ui_dropdown_setup_system,
+            ).in_set(UiSystems::Modify).before(UiSystems::Send))
+
+            .add_systems(Update, (
+                (
+                    ui_slider_pointer_system,
+                    ui_checkbox_click_system,
+                    ui_radio_click_system,
+                    ui_spinbox_click_system,
+                    ui_dropdown_click_system,
+                    ui_widget_navigation_system,
+                ),
+                (
+                    ui_slider_visual_system,
+                    ui_checkbox_visual_system,
+                    ui_radio_visual_system,
+                    ui_spinbox_visual_system,
+                    ui_dropdown_visual_system,
+                ),
+            ).chain().in_set(UiSystems::Modify).before(UiSystems::Send));
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn slider_snap () {
+        let slider = UiSlider::new(0.0, 100.0).step(5.0);
+        assert_eq!(slider.snap(12.0), 10.0);
+        assert_eq!(slider.snap(12.5), 15.0);
+        assert_eq!(slider.snap(-20.0), 0.0);
+        assert_eq!(slider.snap(140.0), 100.0);
+
+        // Steps are counted from the minimum
+        let slider = UiSlider::new(1.0, 10.0).step(3.0);
+        assert_eq!(slider.snap(5.0), 4.0);
+        assert_eq!(slider.snap(9.9), 10.0);
+        assert_eq!(UiSlider::new(0.0, 1.0).snap(0.37), 0.37);
+        assert_eq!(UiSlider::new(0.0, 1.0).step(0.0).snap(0.37), 0.37);
+
+        // Reversed range
+        let slider = UiSlider::new(100.0, 0.0).step(10.0);
+        assert_eq!(slider.snap(44.0), 40.0);
+        assert_eq!(slider.snap(-5.0), 0.0);
+        assert_eq!(slider.snap(120.0), 100.0);
+    }
+
+    #[test]
+    fn slider_fraction () {
+        let slider = UiSlider::new(-50.0, 50.0);
+        assert_eq!(slider.get_fraction(-50.0), 0.0);
+        assert_eq!(slider.get_fraction(0.0), 0.5);
+        assert_eq!(slider.get_fraction(75.0), 1.0);
+
+        let slider = UiSlider::new(100.0, 0.0);
+        assert_eq!(slider.get_fraction(25.0), 0.75);
+        assert_eq!(UiSlider::new(5.0, 5.0).get_fraction(5.0), 0.0);
+    }
+
+    #[test]
+    fn spinbox_spin () {
+        let spinbox = UiSpinbox::new(1.0, 8.0);
+        assert_eq!(spinbox.spin(2.0, 1.0), 3.0);
+        assert_eq!(spinbox.spin(8.0, 1.0), 8.0);
+        assert_eq!(spinbox.spin(1.0, -1.0), 1.0);
+        assert_eq!(spinbox.spin(2.0, 10.0), 8.0);
+
+        // Wraps around past the ends
+        let spinbox = UiSpinbox::new(1.0, 8.0).wrap();
+        assert_eq!(spinbox.spin(8.0, 1.0), 1.0);
+        assert_eq!(spinbox.spin(1.0, -1.0), 8.0);
+        assert_eq!(spinbox.spin(7.0, 1.0), 8.0);
+
+        // Reversed range
+        let spinbox = UiSpinbox::new(10.0, 0.0).step(4.0);
+        assert_eq!(spinbox.spin(8.0, 1.0), 10.0);
+        assert_eq!(spinbox.spin(2.0, -1.0), 0.0);
+        assert_eq!(spinbox.clone().wrap().spin(8.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn spinbox_format () {
+        assert_eq!(UiSpinbox::new(0.0, 10.0).format(2.0), "2");
+        assert_eq!(UiSpinbox::new(0.0, 10.0).format(2.6), "3");
+        assert_eq!(UiSpinbox::new(0.0, 1.0).step(0.1).precision(2).format(0.1 + 0.2), "0.30");
+        assert_eq!(UiSpinbox::new(-1.0, 1.0).precision(1).format(-0.04), "0.0");
+        assert_eq!(UiSpinbox::new(-1.0, 1.0).precision(1).format(-0.06), "-0.1");
+    }
+}
@@ -0,0 +1,50 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use lunex_engine::*;
+
+/// Creates a tree with 20 panels, each containing 100 buttons with base and hover layout.
+fn build_tree() -> UiTree {
+    let mut tree: UiTree = UiTree::new2d("Bench");
+    for p in 0..20 {
+        let panel = format!("Panel {p}");
+        tree.borrow_or_create_ui_node_mut(panel.as_str()).unwrap().obtain_data_mut().unwrap()
+            .layout.insert(0, Layout::window().pos(Rl((5.0 * (p % 4) as f32, 20.0 * (p / 4) as f32))).size(Rl(20.0)).into());
+
+        for b in 0..100 {
+            let data = tree.borrow_or_create_ui_node_mut(format!("{panel}/Button {b}")).unwrap().obtain_data_mut().unwrap();
+            data.layout.insert(0, Layout::window().pos(Rl((0.0, b as f32))).size(Rl((100.0, 1.0))).into());
+            data.layout.insert(1, Layout::window().pos(Rl((2.0, b as f32))).size(Rl((100.0, 1.0))).into());
+        }
+    }
+    tree
+}
+
+fn compute(c: &mut Criterion) {
+    let mut tree = build_tree();
+    let mut width = 800.0;
+    c.bench_function("compute_full", |b| b.iter(|| {
+        // Changing the parent size forces all nodes to recompute
+        width = if width == 800.0 { 801.0 } else { 800.0 };
+        tree.compute(black_box(Rectangle2D::new().with_size((width, 600.0)).into()));
+    }));
+
+    let mut tree = build_tree();
+    tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+    let mut tween = 0.0;
+    c.bench_function("compute_single_tween", |b| b.iter(|| {
+        tween = (tween + 0.1) % 1.0;
+        let data = tree.borrow_data_mut("Panel 10/Button 50").unwrap().unwrap();
+        data.layout_index = [0, 1];
+        data.layout_tween = tween;
+        data.mark_dirty();
+        tree.compute(black_box(Rectangle2D::new().with_size((800.0, 600.0)).into()));
+    }));
+
+    let mut tree = build_tree();
+    tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+    c.bench_function("compute_clean", |b| b.iter(|| {
+        tree.compute(black_box(Rectangle2D::new().with_size((800.0, 600.0)).into()));
+    }));
+}
+
+criterion_group!(benches, compute);
+criterion_main!(benches);
@@ -1,16 +1,27 @@
 use bevy::ecs::component::Component;
 
+use bevy::math::{Affine3A, EulerRot, Quat, Vec2Swizzles, Vec4Swizzles, VectorSpace};
+
 use crate::import::*;
 use crate::NodeGeneralTrait;
 use crate::NodeTopDataTrait;
 use crate::UiNode;
 use crate::UiTree;
+use crate::NodeData;
+use crate::Rectangle2D;
 use crate::Rectangle3D;
 use crate::Layout;
+use crate::Div;
+use crate::StackDirection;
+use crate::StackMargin;
+use crate::UiStack;
+use crate::UiValueEvaluate;
+use crate::YInvert;
 
 /// Trait with [`UiTree`] layout computation methods.
 pub trait UiNodeTreeComputeTrait {
-    /// Compute the layout of the [`UiTree`].
+    /// Compute the layout of the [`UiTree`]. Only dirty nodes and nodes depending on them are recomputed,
+    /// unless the parent rectangle, absolute scale or font size changed since the last compute.
     fn compute(&mut self, parent: Rectangle3D);
 }
 impl <T, N: Default + Component> UiNodeTreeComputeTrait for UiTree<T, N> {
@@ -18,317 +29,777 @@ impl <T, N: Default + Component> UiNodeTreeComputeTrait for UiTree<T, N> {
 
         let mut abs_scale = 1.0;
         let mut font_size = 16.0;
+        let mut force = true;
 
-        if let Some(master_data) = self.obtain_topdata() {
+        if let Some(master_data) = self.obtain_topdata_mut() {
             abs_scale = master_data.abs_scale;
             font_size = master_data.font_size;
+
+            // Recompute all nodes only if the input changed
+            let input = Some((parent, abs_scale, font_size));
+            force = master_data.computed_with != input;
+            master_data.computed_with = input;
         }
 
-        self.node.compute_all(parent, abs_scale, parent.size, font_size);
+        self.node.propagate_dirty();
+        self.node.compute_all(parent, Affine3A::IDENTITY, parent.size, abs_scale, parent.size, font_size, 0.0, force);
     }
 }
 
 
 /// Trait with [`UiNode`] layout computation methods. Includes private methods.
 trait UiNodeComputeTrait {
-    fn compute_all(&mut self, parent: Rectangle3D, absolute_scale: f32, viewport_size: Vec2, font_size: f32);
-    //fn compute_content(&mut self, ancestor_size: Vec2, ancestor_padding: Vec4, abs_scale: f32, font_size: f32) -> Vec2;
-    //fn compute_stack(&mut self, ancestor_size: Vec2, ancestor_padding: Vec4, abs_scale: f32, font_size: f32, horizontal: bool) -> Vec2;
-    //fn align_stack(&mut self, ancestor_position: Vec2);
+    fn propagate_dirty(&mut self) -> bool;
+    #[allow(clippy::too_many_arguments)]
+    fn compute_all(&mut self, parent: Rectangle3D, parent_transform: Affine3A, ancestor_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32, layer_bias: f32, force: bool);
+    fn compute_content(&mut self, ancestor_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Vec2;
+    fn align_stack(&mut self, content: Rectangle2D, ancestor_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32);
+    fn compute_scroll_size(&mut self);
 }
 impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> { 
-    /// Triggers the recursion in the right manner.
-    fn compute_all(&mut self, parent: Rectangle3D, absolute_scale: f32, viewport_size: Vec2, mut font_size: f32) {
+    /// Marks nodes as dirty if the size of their parametric content could have changed or their subnodes
+    /// were added or removed. Returns `true` if this node is a dirty Div or a dirty node with content size.
+    fn propagate_dirty(&mut self) -> bool {
+        let mut content_dirty = std::mem::take(&mut self.changed);
+        for subnode in self.nodes.values_mut() {
+            content_dirty |= subnode.propagate_dirty();
+        }
+
+        let Some(node_data) = &mut self.data else { return false; };
+        node_data.dirty |= content_dirty;
+        node_data.dirty && (get_divs(node_data).is_some() || node_data.content_size != Vec2::ZERO)
+    }
+    /// Triggers the recursion in the right manner. Clean nodes are skipped unless forced.
+    fn compute_all(&mut self, parent: Rectangle3D, parent_transform: Affine3A, mut ancestor_size: Vec2, absolute_scale: f32, viewport_size: Vec2, mut font_size: f32, mut layer_bias: f32, force: bool) {
 
         // Get depth before mutating self
         let depth = self.get_depth();
-        
-        let skip = true;
-        let is_parametric = false;
-
-        // Check here if computation is required for partial recalculation
 
         // Compute my layout and return computed rectangle for recursion
-        let my_rectangle = if let Some(node_data) = &mut self.data {
+        let Some(node_data) = &mut self.data else { return; };
+
+        // Overwrite passed style with font size
+        font_size = get_font_size(node_data, ancestor_size, absolute_scale, viewport_size, font_size);
+        node_data.computed_font_size = font_size;
+
+        // Layer bias is inherited by all subnodes
+        layer_bias += node_data.layer_bias;
+
+        let dirty = node_data.dirty;
+        node_data.dirty = false;
+        let divs = get_divs(node_data);
+
+        // Skip the computation, but keep looking for dirty subnodes
+        if !force && !dirty {
+            let (my_rectangle, my_transform) = (node_data.rectangle, node_data.transform);
+            let scrolled = Rectangle3D { pos: my_rectangle.pos - node_data.scroll.extend(0.0), ..my_rectangle };
+            if divs.is_none() { ancestor_size = my_rectangle.size }
+            for subnode in self.nodes.values_mut() {
+                subnode.compute_all(scrolled, my_transform, ancestor_size, absolute_scale, viewport_size, font_size, layer_bias, false);
+            }
+            self.compute_scroll_size();
+            return;
+        }
+        let (previous_rectangle, previous_transform) = (node_data.rectangle, node_data.transform);
 
-            // Overwrite passed style with font size
-            if let Some(fnt) = node_data.font_size { font_size = fnt }
+        // Parametric nodes already received their rectangle from the parent stack
+        if divs.is_none() {
 
             // Compute node layout
-
             let layout_0 = node_data.layout.get(&node_data.layout_index[0]).unwrap_or(node_data.layout.get(&0).unwrap());
-            let layout_0: Option<Rectangle3D> = match layout_0 {
-                Layout::Div(_) => {
-                    None
-                },
-                Layout::Boundary(l) => {
-                    Some(l.compute(parent.into(), absolute_scale, viewport_size, font_size).into())
-                },
-                Layout::Window(l) => {
-                    Some(l.compute(parent.into(), absolute_scale, viewport_size, font_size).into())
-                },
-                Layout::Solid(l)  => {
-                    Some(l.compute(parent.into(), absolute_scale, viewport_size, font_size).into())
-                },
-            };
+            let layout_0 = compute_declarative(layout_0, parent, absolute_scale, viewport_size, font_size);
 
             let layout_1 = node_data.layout.get(&node_data.layout_index[1]).unwrap_or(node_data.layout.get(&0).unwrap());
-            let layout_1: Option<Rectangle3D> = match layout_1 {
-                Layout::Div(_) => {
-                    None
-                },
-                Layout::Boundary(l) => {
-                    Some(l.compute(parent.into(), absolute_scale, viewport_size, font_size).into())
-                },
-                Layout::Window(l) => {
-                    Some(l.compute(parent.into(), absolute_scale, viewport_size, font_size).into())
-                },
-                Layout::Solid(l)  => {
-                    Some(l.compute(parent.into(), absolute_scale, viewport_size, font_size).into())
-                },
-            };
-
-            /* match &node_data.layout {
-                Layout::Div(_) => {
-                    is_parametric = true;
-                },
-                Layout::Boundary(l) => {
-                    skip = false;
-                },
-                Layout::Window(l) => {
-                    skip = false;
-                },
-                Layout::Solid(l)  => {
-                    skip = false;
-                },
-            } */
+            let layout_1 = compute_declarative(layout_1, parent, absolute_scale, viewport_size, font_size);
 
             if let Some(l0) = layout_0 {
-                if let Some(l1) = layout_1 {
-                    node_data.rectangle = l0.lerp(l1, node_data.layout_tween);
-                };
-            };
+                let mut rectangle = if let Some(l1) = layout_1 { l0.lerp(l1, node_data.layout_tween) } else { l0 };
 
-            // Adding depth
-            node_data.rectangle.pos.z = (depth + node_data.depth_bias)*absolute_scale;
-            node_data.rectangle
+                // Blend the chained layouts on top
+                for (index, tween) in &node_data.layout_chain {
+                    let Some(layout) = node_data.layout.get(index) else { continue };
+                    if let Some(l) = compute_declarative(layout, parent, absolute_scale, viewport_size, font_size) { rectangle = rectangle.lerp(l, *tween) }
+                }
+                node_data.rectangle = rectangle;
+            };
+        }
 
-        } else { return; };
+        // Parametric nodes only get their rotation
+        if let Some(divs) = &divs {
+            node_data.rectangle.roll = divs.blend(|div| div.roll);
+            node_data.rectangle.yaw = divs.blend(|div| div.yaw);
+            node_data.rectangle.tilt = divs.blend(|div| div.tilt);
+        }
 
-        if !skip {
-            if is_parametric {
-                //compute divs with inherited scale
-                //self.compute_content(parent.size, Vec4::ZERO, absolute_scale, font_size);
-            } else {
-                //compute divs with my rectangle scale
-                //self.compute_content(my_rectangle.size, Vec4::ZERO, absolute_scale, font_size);
+        // Adding depth
+        node_data.rectangle.pos.z = (depth + layer_bias + node_data.depth_bias)*absolute_scale;
+        let my_rectangle = node_data.rectangle;
+
+        // Rotate around the center on top of the inherited transform
+        let rotation = Quat::from_euler(EulerRot::YXZ, my_rectangle.yaw, my_rectangle.tilt, my_rectangle.roll).invert_y();
+        let center = my_rectangle.pos + (my_rectangle.size / 2.0).extend(0.0);
+        node_data.transform = parent_transform * Affine3A::from_rotation_translation(rotation, center) * Affine3A::from_translation(-center);
+        let my_transform = node_data.transform;
+
+        // Subnodes are placed into the rectangle moved by the scroll offset
+        let scrolled = Rectangle3D { pos: my_rectangle.pos - node_data.scroll.extend(0.0), ..my_rectangle };
+
+        // Get the area where the subnodes are stacked
+        let content = if let Some(divs) = &divs {
+            // Compute divs with inherited scale
+            node_data.border = divs.blend(|div| div.compute_border(ancestor_size, absolute_scale, viewport_size, font_size));
+            let offset = divs.blend(|div| div.compute_padding(ancestor_size, absolute_scale, viewport_size, font_size)) + node_data.border;
+            Rectangle2D {
+                pos: scrolled.pos.truncate() + offset.xy(),
+                size: my_rectangle.size - offset.xy() - offset.zw(),
             }
-        }
+        } else {
+            // Only parametric nodes have a border
+            node_data.border = Vec4::ZERO;
+
+            // Compute divs with my rectangle scale
+            ancestor_size = my_rectangle.size;
+            self.compute_content(ancestor_size, absolute_scale, viewport_size, font_size);
+            scrolled.into()
+        };
+        self.align_stack(content, ancestor_size, absolute_scale, viewport_size, font_size);
+
+        // Subnodes have to be recomputed if they could have been affected
+        let force = force || dirty || divs.is_some() || my_rectangle != previous_rectangle || my_transform != previous_transform;
 
         // Enter recursion
         for (_, subnode) in &mut self.nodes {
-            subnode.compute_all(my_rectangle, absolute_scale, viewport_size, font_size);
+            let is_div = subnode.data.as_ref().is_some_and(|data| get_divs(data).is_some());
+            subnode.compute_all(scrolled, my_transform, ancestor_size, absolute_scale, viewport_size, font_size, layer_bias, force || is_div);
+        }
+        self.compute_scroll_size();
+    }
+    /// Computes the size of all parametric subnodes and returns the size of the content they take up.
+    /// Empty space is not distributed here, because the size of the content area is not known yet.
+    fn compute_content(&mut self, ancestor_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Vec2 {
+        let Some(node_data) = &self.data else { return Vec2::ZERO; };
+        let (gap, stack_margin) = compute_stack_spacing(node_data, ancestor_size, absolute_scale, viewport_size, font_size);
+        let stack = &node_data.stack;
+
+        let mut computed_divs = Vec::new();
+        for subnode in self.nodes.values_mut() {
+            let Some(subnode_data) = &subnode.data else { continue; };
+            if get_divs(subnode_data).is_none() { continue; }
+            let font_size = get_font_size(subnode_data, ancestor_size, absolute_scale, viewport_size, font_size);
+
+            // Enter recursion to get the right content size
+            let potential_content = subnode.compute_content(ancestor_size, absolute_scale, viewport_size, font_size);
+
+            // Content of declarative subnodes, like a text label filling the div
+            let declarative_content = subnode.nodes.values()
+                .filter_map(|node| node.data.as_ref())
+                .filter(|data| get_divs(data).is_none())
+                .fold(Vec2::ZERO, |content, data| content.max(data.content_size));
+
+            // Fetch data again, because they were modified
+            let Some(subnode_data) = &mut subnode.data else { continue; };
+            let Some(divs) = get_divs(subnode_data) else { continue; };
+
+            // Overwrite subnode content if div contains no subdivs
+            let content = if potential_content != Vec2::ZERO { potential_content } else { subnode_data.content_size };
+            let content = content.max(declarative_content);
+
+            // Compute the size of all layouts and tween them
+            subnode_data.rectangle.size = divs.blend(|div| div.compute_size(content, ancestor_size, absolute_scale, viewport_size, font_size));
+
+            if let Some(div) = ComputedDiv::new(subnode_data, stack_margin, ancestor_size, absolute_scale, viewport_size, font_size) {
+                computed_divs.push(div);
+            }
         }
+
+        compute_stack(&computed_divs, stack, gap, Vec2::ZERO).1
     }
-    /* /// Computes the content only.
-    fn compute_content(&mut self, ancestor_size: Vec2, ancestor_padding: Vec4, abs_scale: f32, font_size: f32) -> Vec2 {
+    /// Positions all parametric subnodes within the given content area and distributes the empty space. Sizes must be computed before.
+    fn align_stack(&mut self, content: Rectangle2D, ancestor_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32) {
+        let Some(node_data) = &self.data else { return; };
+        let (gap, stack_margin) = compute_stack_spacing(node_data, ancestor_size, absolute_scale, viewport_size, font_size);
+        let stack = &node_data.stack;
+
+        let mut computed_divs = Vec::new();
+        for subnode in self.nodes.values() {
+            let Some(subnode_data) = &subnode.data else { continue; };
+            let font_size = get_font_size(subnode_data, ancestor_size, absolute_scale, viewport_size, font_size);
+            if let Some(div) = ComputedDiv::new(subnode_data, stack_margin, ancestor_size, absolute_scale, viewport_size, font_size) {
+                computed_divs.push(div);
+            }
+        }
+        if computed_divs.is_empty() { return; }
 
-        let stack_options = self.data.as_ref().unwrap().stack;
+        let (rectangles, _) = compute_stack(&computed_divs, stack, gap, content.size);
 
-        match stack_options.direction {
-            StackDirection::Horizontal => self.compute_stack(ancestor_size, ancestor_padding, abs_scale, font_size, true),
-            StackDirection::Vertical => self.compute_stack(ancestor_size, ancestor_padding, abs_scale, font_size, false),
+        let mut rectangles = rectangles.into_iter();
+        for subnode in self.nodes.values_mut() {
+            let Some(subnode_data) = &mut subnode.data else { continue; };
+            if get_divs(subnode_data).is_none() { continue; }
+            let Some(mut rectangle) = rectangles.next() else { break; };
+
+            // Mirror the position within the content area
+            if stack.inverted { rectangle.pos.x = content.size.x - rectangle.pos.x - rectangle.size.x }
+            if stack.flipped { rectangle.pos.y = content.size.y - rectangle.pos.y - rectangle.size.y }
+
+            subnode_data.rectangle.pos.x = content.pos.x + rectangle.pos.x;
+            subnode_data.rectangle.pos.y = content.pos.y + rectangle.pos.y;
+            subnode_data.rectangle.size = rectangle.size;
         }
     }
-    /// This will compute the stack and position nodes ONLY locally as if every matrix starts at 0,0.
-    /// Secondary pass after alignment of parent nodes is required.
-    fn compute_stack(&mut self, ancestor_size: Vec2, ancestor_padding: Vec4, abs_scale: f32, font_size: f32, horizontal: bool) -> Vec2 {
+    /// Measures the area covered by the subnodes, so scroll containers know how far they can scroll.
+    fn compute_scroll_size(&mut self) {
+        let Some(node_data) = &self.data else { return; };
+        let origin = node_data.rectangle.pos.truncate() - node_data.scroll;
+
+        let mut size = Vec2::ZERO;
+        for subnode in self.nodes.values() {
+            let Some(subnode_data) = &subnode.data else { continue; };
+            size = size.max(subnode_data.rectangle.pos.truncate() + subnode_data.rectangle.size - origin);
+        }
 
-        let mut matrix: Vec<Vec<&mut Node<NodeData<N>>>> = Vec::new();
-        let mut content_size = Vec2::ZERO;
+        if let Some(node_data) = &mut self.data { node_data.scroll_size = size; }
+    }
+}
 
-        // Sort mutable pointers into matrix
-        let mut i = 0;
-        matrix.push(Vec::new());
-        for (_, subnode) in &mut self.nodes {
-            if let Some(subnode_data) = &subnode.data {
-                if let Layout::Div(layout) = &subnode_data.layout {
-                    let br = layout.force_break;
-                    matrix[i].push(subnode);
-                    if br {
-                        i += 1;
-                        matrix.push(Vec::new());
-                    }
-                }
+/// Computes the declarative layout. Returns [`None`] if the layout is parametric.
+fn compute_declarative(layout: &Layout, parent: Rectangle3D, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Option<Rectangle3D> {
+    match layout {
+        Layout::Div(_) => None,
+        Layout::Boundary(l) => Some(Rectangle3D { roll: l.roll, yaw: l.yaw, tilt: l.tilt, ..l.compute(parent.into(), absolute_scale, viewport_size, font_size).into() }),
+        Layout::Window(l) => Some(Rectangle3D { roll: l.roll, yaw: l.yaw, tilt: l.tilt, ..l.compute(parent.into(), absolute_scale, viewport_size, font_size).into() }),
+        Layout::Solid(l) => Some(Rectangle3D { roll: l.roll, yaw: l.yaw, tilt: l.tilt, ..l.compute(parent.into(), absolute_scale, viewport_size, font_size).into() }),
+    }
+}
+
+/// Returns the [`Div`] layouts the node is tweening between together with the tween values.
+/// Returns [`None`] if the node is not parametric. If only the first layout is [`Div`], it is used for both.
+fn get_divs<N:Default + Component>(node_data: &NodeData<N>) -> Option<DivBlend> {
+    let layout_0 = node_data.layout.get(&node_data.layout_index[0]).or(node_data.layout.get(&0))?;
+    let layout_1 = node_data.layout.get(&node_data.layout_index[1]).or(node_data.layout.get(&0))?;
+    let Layout::Div(div_0) = *layout_0 else { return None; };
+    let div_1 = if let Layout::Div(div_1) = *layout_1 { div_1 } else { div_0 };
+    let chain = node_data.layout_chain.iter().filter_map(|(index, tween)| match node_data.layout.get(index) {
+        Some(Layout::Div(div)) => Some((*div, *tween)),
+        _ => None,
+    }).collect();
+    Some(DivBlend { div_0, div_1, tween: node_data.layout_tween, chain })
+}
+
+/// Parametric layouts of the node with the tween values they are blended with.
+struct DivBlend {
+    div_0: Div,
+    div_1: Div,
+    tween: f32,
+    chain: Vec<(Div, f32)>,
+}
+impl DivBlend {
+    /// Computes the value for each layout and blends them together
+    fn blend<V: VectorSpace>(&self, value: impl Fn(&Div) -> V) -> V {
+        let blended = value(&self.div_0).lerp(value(&self.div_1), self.tween);
+        self.chain.iter().fold(blended, |blended, (div, tween)| blended.lerp(value(div), *tween))
+    }
+}
+
+/// Returns the font size of the node. The [`crate::Em`] unit of the node font size is relative to the inherited `font_size`.
+fn get_font_size<N:Default + Component>(node_data: &NodeData<N>, ancestor_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> f32 {
+    node_data.font_size.map_or(font_size, |size| size.evaluate(absolute_scale, ancestor_size.y, viewport_size.y, font_size))
+}
+
+/// Computes the gap between subnodes and the margin subnodes inherit from the [`crate::UiStack`] of the node.
+fn compute_stack_spacing<N:Default + Component>(node_data: &NodeData<N>, ancestor_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> (Vec2, Vec4) {
+    let gap = node_data.stack.gap.evaluate(Vec2::splat(absolute_scale), ancestor_size, viewport_size, Vec2::splat(font_size));
+    let margin = match &node_data.stack.margin {
+        StackMargin::Manual(margin) => margin.evaluate(Vec4::splat(absolute_scale), ancestor_size.xyxy(), viewport_size.xyxy(), Vec4::splat(font_size)),
+        _ => Vec4::ZERO,
+    };
+    (gap, margin)
+}
+
+/// Computes the local rectangle of each div in the stack, starting from `(0, 0)`.
+/// The `available` space left in each line is distributed between [`crate::Sp`] values in proportion.
+/// Space that a div can't take because of its max size is distributed between the rest.
+/// Returns the rectangles in the same order and the size of the whole content without the distributed space.
+fn compute_stack(divs: &[ComputedDiv], stack: &UiStack, gap: Vec2, available: Vec2) -> (Vec<Rectangle2D>, Vec2) {
+
+    // Compute everything as horizontal and swap the axis for vertical stack
+    let horizontal = stack.direction == StackDirection::Horizontal;
+    let swap2 = |v: Vec2| if horizontal { v } else { v.yx() };
+    let swap4 = |v: Vec4| if horizontal { v } else { v.yxwz() };
+    let gap = swap2(gap);
+    let gap_sp = swap2(stack.gap.get_sp());
+    let available = swap2(available);
+
+    // Lines end after a div that forces a line break
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    while line_start < divs.len() {
+        let line_end = divs[line_start..].iter().position(|div| div.br).map_or(divs.len(), |i| line_start + i + 1);
+        lines.push(&divs[line_start..line_end]);
+        line_start = line_end;
+    }
+
+    // Compute how thick each line is
+    let thickness: Vec<f32> = lines.iter().map(|line| line.iter().map(|div| {
+        let margin = swap4(div.margin);
+        margin.y + swap2(div.size).y + margin.w
+    }).fold(0.0, f32::max)).collect();
+
+    // Distribute the empty space between lines
+    let line_gaps = lines.len().saturating_sub(1) as f32;
+    let used = thickness.iter().sum::<f32>() + gap.y * line_gaps;
+    let line_gap = gap.y + gap_sp.y * space_unit(available.y - used, gap_sp.y * line_gaps);
+
+    let mut rectangles = Vec::with_capacity(divs.len());
+    let mut content_size = Vec2::ZERO;
+    let mut line_cursor = 0.0;
+
+    for (l, line) in lines.iter().enumerate() {
+        if l != 0 { line_cursor += line_gap }
+
+        // Get the space of each div together with the space inherited from the stack
+        let spaces: Vec<Vec4> = line.iter().enumerate().map(|(i, div)| swap4(div.margin_sp) + stack_margin_sp(&stack.margin, i, line.len())).collect();
+
+        // Distribute the empty space within the line
+        let item_gaps = (line.len() - 1) as f32;
+        let used = line.iter().map(|div| {
+            let margin = swap4(div.margin);
+            margin.x + swap2(div.size).x + margin.z
+        }).sum::<f32>() + gap.x * item_gaps;
+        let mut space = line.iter().zip(&spaces).map(|(div, sp)| sp.x + swap2(div.size_sp).x + sp.z).sum::<f32>() + gap_sp.x * item_gaps;
+        let mut free = available.x - used;
+
+        // Divs reaching their max size are frozen and the space they can't take is shared again
+        let mut frozen = vec![false; line.len()];
+        let unit = loop {
+            let unit = space_unit(free, space);
+            let mut clamped = false;
+            for (div, frozen) in line.iter().zip(&mut frozen) {
+                let (size, size_sp, max_size) = (swap2(div.size).x, swap2(div.size_sp).x, swap2(div.max_size).x);
+                if *frozen || size_sp <= 0.0 || size + size_sp * unit <= max_size { continue; }
+                *frozen = true;
+                clamped = true;
+                free -= (max_size - size).max(0.0);
+                space -= size_sp;
+            }
+            if !clamped { break unit; }
+        };
+
+        let mut cursor = 0.0;
+        let mut used_length = 0.0;
+        for (i, (div, sp)) in line.iter().zip(&spaces).enumerate() {
+            let mut size = swap2(div.size);
+            let size_sp = swap2(div.size_sp);
+            let max_size = swap2(div.max_size);
+            let margin = swap4(div.margin);
+
+            // Distribute the empty space left in the line thickness
+            let cross_unit = space_unit(thickness[l] - margin.y - size.y - margin.w, sp.y + size_sp.y + sp.w);
+            size.x = (size.x + size_sp.x * unit).min(max_size.x).max(size.x);
+            size.y = (size.y + size_sp.y * cross_unit).min(max_size.y).max(size.y);
+
+            if i != 0 {
+                cursor += gap.x + gap_sp.x * unit;
+                used_length += gap.x;
             }
+            cursor += margin.x + sp.x * unit;
+            rectangles.push(Rectangle2D {
+                pos: swap2(Vec2::new(cursor, line_cursor + margin.y + sp.y * cross_unit)),
+                size: swap2(size),
+            });
+            cursor += size.x + margin.z + sp.z * unit;
+            used_length += margin.x + swap2(div.size).x + margin.z;
         }
 
+        content_size.x = content_size.x.max(used_length);
+        line_cursor += thickness[l];
+    }
+    content_size.y = thickness.iter().sum::<f32>() + gap.y * line_gaps;
 
-        // INSIDE MATRIX =================================================================
+    (rectangles, swap2(content_size))
+}
 
-        let gap = self.data.as_ref().unwrap().stack.gap.evaluate(abs_scale, ancestor_size, font_size);
-        let align = self.data.as_ref().unwrap().stack.node_alignment.0;
+/// Returns the size of one [`crate::Sp`] unit, when `free` space is distributed between `space` units.
+fn space_unit(free: f32, space: f32) -> f32 {
+    if free > 0.0 && space > 0.0 { free / space } else { 0.0 }
+}
 
+/// Returns the [`crate::Sp`] margin the div at index `i` inherits from the [`StackMargin`] preset. The `x` and `z`
+/// values are the sides facing the start and the end of the line with `n` divs.
+fn stack_margin_sp(margin: &StackMargin, i: usize, n: usize) -> Vec4 {
+    let first = if i == 0 { 1.0 } else { 0.0 };
+    let last = if i + 1 == n { 1.0 } else { 0.0 };
+    match margin {
+        StackMargin::Start | StackMargin::Manual(_) => Vec4::ZERO,
+        StackMargin::Center => Vec4::new(first, 0.0, last, 0.0),
+        StackMargin::End => Vec4::new(first, 0.0, 0.0, 0.0),
+        StackMargin::Between => Vec4::new(1.0 - first, 0.0, 0.0, 0.0),
+        StackMargin::Evenly => Vec4::new(1.0, 0.0, last, 0.0),
+        StackMargin::Around => Vec4::new(1.0, 0.0, 1.0, 0.0),
+    }
+}
 
-        let mut line_cursor = if horizontal { ancestor_padding.y } else { ancestor_padding.x };
+/// Computed size, margin and space of a parametric node within a stack.
+#[derive(Debug, Clone, Copy)]
+struct ComputedDiv {
+    size: Vec2,
+    max_size: Vec2,
+    margin: Vec4,
+    size_sp: Vec2,
+    margin_sp: Vec4,
+    br: bool,
+}
+impl ComputedDiv {
+    /// Computes the div from already computed node size. Returns [`None`] if the node is not parametric.
+    fn new<N:Default + Component>(node_data: &NodeData<N>, stack_margin: Vec4, ancestor_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Option<Self> {
+        let divs = get_divs(node_data)?;
+        Some(ComputedDiv {
+            size: node_data.rectangle.size,
+            max_size: divs.blend(|div| div.compute_max_size(ancestor_size, absolute_scale, viewport_size, font_size)),
+            margin: divs.blend(|div| div.compute_margin(ancestor_size, absolute_scale, viewport_size, font_size)) + stack_margin,
+            size_sp: divs.blend(|div| div.min_size.map_or(Vec2::ZERO, |size| size.get_sp())),
+            margin_sp: divs.blend(|div| div.margin.get_sp()),
+            br: divs.div_0.br,
+        })
+    }
+}
 
-        //--------------------------//
-        let mut _i = 0;             //
-        let _i_max = matrix.len();  //
-        for line in &mut matrix {   //
-            // INSIDE LINE =================================================================
 
-            // Register that is shared between the two passes
-            let mut comline = ComputedLine {
-                divs: Vec::new(),
-                line_length: 0.0,
-            };
+// #=============#
+// #=== TESTS ===#
 
-            // First pass to compute sizes--//
-            let mut _ii = 0;                //
-            let _ii_max = line.len();       //
-            for subnode in &mut *line {     //
-                // INSIDE SUBNODE =================================================================
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use bevy::math::{Vec2, Vec4};
 
-                // Fetch data
-                let subnode_data = subnode.data.as_ref().unwrap();
-                let layout = if let Layout::Div(layout) = subnode_data.layout { layout } else { unreachable!() };
+    fn add_div(tree: &mut UiTree, path: &str, div: Div, content_size: Vec2) {
+        let node = tree.borrow_or_create_ui_node_mut(path).unwrap();
+        let data = node.obtain_data_mut().unwrap();
+        data.layout.insert(0, div.into());
+        data.content_size = content_size;
+    }
 
-                // Get padding & margin => compute range of motion
-                let padding = layout.compute_padding(ancestor_size, abs_scale, font_size);
-                let margin = layout.compute_margin(ancestor_size, abs_scale, font_size);
-                let border = layout.compute_border(ancestor_size, abs_scale, font_size);
+    fn rectangle(tree: &UiTree, path: &str) -> Rectangle3D {
+        tree.borrow_data(path).unwrap().unwrap().rectangle
+    }
 
-                // Enter recursion to get the right content size
-                let potential_content = subnode.compute_content(ancestor_size, padding, abs_scale, font_size);
+    #[test]
+    fn div_flow () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        tree.borrow_or_create_ui_node_mut("Root").unwrap();
+        tree.borrow_data_mut("Root").unwrap().unwrap().stack = UiStack::new().gap(Ab(10.0));
 
-                // Fetch data again, because they were modified
-                let subnode_data = subnode.data.as_mut().unwrap();
-                let mut subnode_content = subnode_data.content_size;
+        add_div(&mut tree, "Root/A", Div::new().pad(Ab(5.0)), Vec2::new(100.0, 20.0));
+        add_div(&mut tree, "Root/B", Div::new().margin_l(Ab(4.0)).br(), Vec2::new(50.0, 50.0));
+        add_div(&mut tree, "Root/C", Div::new(), Vec2::new(30.0, 30.0));
 
-                // Overwrite subnode content if div contains no subdivs
-                if potential_content != Vec2::ZERO { subnode_content = potential_content }
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
 
-                // Compute size and (line_length)
-                let size = layout.compute_size(subnode_content, padding, border);
-                let line_length = if horizontal { margin.y + size.y + margin.w } else { margin.x + size.x + margin.z };
+        let a = rectangle(&tree, "Root/A");
+        assert_eq!(a.pos.truncate(), Vec2::new(0.0, 0.0));
+        assert_eq!(a.size, Vec2::new(110.0, 30.0));
 
-                // Push into register
-                comline.line_length = f32::max(comline.line_length, line_length);
-                comline.divs.push(ComputedDiv { size, margin });
+        let b = rectangle(&tree, "Root/B");
+        assert_eq!(b.pos.truncate(), Vec2::new(124.0, 0.0));
+        assert_eq!(b.size, Vec2::new(50.0, 50.0));
 
+        let c = rectangle(&tree, "Root/C");
+        assert_eq!(c.pos.truncate(), Vec2::new(0.0, 60.0));
+    }
 
-                // END OF INSIDE SUBNODE =================================================================
-                _ii += 1;
-            }
+    #[test]
+    fn div_min () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        tree.borrow_or_create_ui_node_mut("Root").unwrap();
 
-            if _i != 0 { line_cursor += if horizontal { gap.x } else { gap.y } }
-            let mut cursor = if horizontal { ancestor_padding.x } else { ancestor_padding.y };
+        add_div(&mut tree, "Root/A", Div::new().min(Ab((50.0, 40.0))), Vec2::new(20.0, 10.0));
+        add_div(&mut tree, "Root/B", Div::new().min(Ab(50.0)), Vec2::new(100.0, 100.0));
+        add_div(&mut tree, "Root/C", Div::new().width(Sizing::Min).min(Ab((50.0, 0.0))), Vec2::new(100.0, 20.0));
 
-            // Second pass to align them----//
-            let mut _ii = 0;                //
-            let _ii_max = line.len();       //
-            for subnode in &mut *line {     //
-                // INSIDE SUBNODE =================================================================
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
 
-                // Fetch data
-                let subnode_data = subnode.data.as_ref().unwrap();
-                let layout = if let Layout::Div(layout) = subnode_data.layout { layout } else { unreachable!() };
-                
-                let margin = comline.divs[_ii].margin;
-                let size = comline.divs[_ii].size;
+        assert_eq!(rectangle(&tree, "Root/A").size, Vec2::new(50.0, 40.0));
+        assert_eq!(rectangle(&tree, "Root/B").size, Vec2::new(100.0, 100.0));
+        assert_eq!(rectangle(&tree, "Root/C").size, Vec2::new(50.0, 20.0));
+    }
 
-                let possible_size = if horizontal {comline.line_length - margin.y - margin.w } else {comline.line_length - margin.x - margin.z };
+    #[test]
+    fn div_max () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        tree.borrow_or_create_ui_node_mut("Root").unwrap();
 
-                let mut my_align = align;
-                let my_offset;
-                
-                if horizontal {
-                    if let Some(align) = layout.align_y { my_align = align.0 }
+        add_div(&mut tree, "Root/A", Div::new().max(Ab((50.0, 40.0))), Vec2::new(100.0, 100.0));
+        add_div(&mut tree, "Root/B", Div::new().max(Ab(200.0)), Vec2::new(20.0, 10.0));
+        add_div(&mut tree, "Root/C", Div::new().width(Sizing::Max).max(Ab((300.0, 200.0))), Vec2::new(20.0, 10.0));
 
-                    if _ii != 0 { cursor += gap.x }
-                    cursor += margin.x;
-                    let off = margin.y + possible_size/2.0 - size.y/2.0;
-                    my_offset = Vec2::new(cursor, off + (off - margin.x) * my_align);
-                    cursor += size.x;
-                    cursor += margin.z;
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
 
-                } else {
-                    if let Some(align) = layout.align_x { my_align = align.0 }
+        assert_eq!(rectangle(&tree, "Root/A").size, Vec2::new(50.0, 40.0));
+        assert_eq!(rectangle(&tree, "Root/B").size, Vec2::new(20.0, 10.0));
+        assert_eq!(rectangle(&tree, "Root/C").size, Vec2::new(300.0, 10.0));
+    }
 
-                    if _ii != 0 { cursor += gap.y }
-                    cursor += margin.y;
-                    let off = margin.x + possible_size/2.0 - size.x/2.0;
-                    my_offset = Vec2::new(off + (off - margin.x) * my_align, cursor);
-                    cursor += size.y;
-                    cursor += margin.w;
-                };
-                
+    #[test]
+    fn div_label () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        tree.borrow_or_create_ui_node_mut("Root").unwrap();
+        add_div(&mut tree, "Root/Button", Div::new().pad(Ab(5.0)), Vec2::ZERO);
+        add_div(&mut tree, "Root/Next", Div::new(), Vec2::new(10.0, 10.0));
+
+        // Declarative label with the size of its text
+        let label = tree.borrow_or_create_ui_node_mut("Root/Button/Label").unwrap().obtain_data_mut().unwrap();
+        label.layout.insert(0, Window::full().into());
+        label.content_size = Vec2::new(60.0, 20.0);
+
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Root/Button").size, Vec2::new(70.0, 30.0));
+        assert_eq!(rectangle(&tree, "Root/Button/Label").size, Vec2::new(70.0, 30.0));
+
+        // Button grows with its label
+        let label = tree.borrow_data_mut("Root/Button/Label").unwrap().unwrap();
+        label.content_size = Vec2::new(100.0, 20.0);
+        label.mark_dirty();
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Root/Button").size, Vec2::new(110.0, 30.0));
+        assert_eq!(rectangle(&tree, "Root/Next").pos.x, 110.0);
+    }
 
-                // Fetch data again, because they were modified
-                let subnode_data = subnode.data.as_mut().unwrap();
+    #[test]
+    fn layout_chain () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        let root = tree.borrow_or_create_ui_node_mut("Root").unwrap().obtain_data_mut().unwrap();
+        root.layout.insert(0, Window::new().size(Ab(100.0)).into());
+        root.layout.insert(1, Window::new().size(Ab(200.0)).into());
+        root.layout.insert(2, Window::new().size(Ab(400.0)).into());
+        root.layout_index = [0, 1];
+        root.layout_tween = 0.5;
+        root.layout_chain = vec![(2, 0.5)];
+        add_div(&mut tree, "Root/A", Div::new().pad(Ab(0.0)), Vec2::new(10.0, 10.0));
+        let a = tree.borrow_data_mut("Root/A").unwrap().unwrap();
+        a.layout.insert(1, Div::new().pad(Ab(10.0)).into());
+        a.layout.insert(2, Div::new().pad(Ab(20.0)).into());
+        a.layout_index = [0, 1];
+        a.layout_tween = 1.0;
+        a.layout_chain = vec![(2, 0.5)];
+
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+
+        // The chained layout is blended on top of the tweened pair
+        assert_eq!(rectangle(&tree, "Root").size, Vec2::splat(275.0));
+        assert_eq!(rectangle(&tree, "Root/A").size, Vec2::splat(40.0));
+
+        // Finished transitions end on the chained layout
+        tree.borrow_data_mut("Root").unwrap().unwrap().layout_chain = vec![(2, 1.0)];
+        tree.borrow_data_mut("Root").unwrap().unwrap().mark_dirty();
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Root").size, Vec2::splat(400.0));
+    }
 
-                let _xxx = my_offset + if horizontal { Vec2::new(0.0, line_cursor) } else { Vec2::new(line_cursor, 0.0) };
-                subnode_data.rectangle.pos.x = _xxx.x;
-                subnode_data.rectangle.pos.y = _xxx.y;
-                subnode_data.rectangle.size = size;
+    #[test]
+    fn div_nested () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        tree.borrow_or_create_ui_node_mut("Root").unwrap();
+        tree.borrow_data_mut("Root").unwrap().unwrap().stack = UiStack::new().direction(StackDirection::Vertical).flipped(true);
 
+        add_div(&mut tree, "Root/List", Div::new().pad(Ab(10.0)).border(Ab(2.0)), Vec2::ZERO);
+        tree.borrow_data_mut("Root/List").unwrap().unwrap().stack = UiStack::new().direction(StackDirection::Vertical);
+        add_div(&mut tree, "Root/List/A", Div::new(), Vec2::new(100.0, 20.0));
+        add_div(&mut tree, "Root/List/B", Div::new().max(Ab(40.0)), Vec2::new(80.0, 80.0));
 
-                let subnode_data = subnode.data.as_ref().unwrap();
-                subnode.align_stack(subnode_data.rectangle.pos.xy());
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
 
+        let list = rectangle(&tree, "Root/List");
+        assert_eq!(list.size, Vec2::new(124.0, 84.0));
+        assert_eq!(list.pos.truncate(), Vec2::new(0.0, 516.0));
+        assert_eq!(tree.borrow_data("Root/List").unwrap().unwrap().border, Vec4::splat(2.0));
 
-                // END OF INSIDE SUBNODE =================================================================
-                _ii += 1;
-            }
+        let b = rectangle(&tree, "Root/List/B");
+        assert_eq!(b.size, Vec2::new(40.0, 40.0));
+        assert_eq!(b.pos.truncate(), Vec2::new(12.0, 548.0));
+    }
 
-            // Set content size
-            if horizontal {
-                content_size.x = f32::max(content_size.x, cursor - ancestor_padding.x)
-            } else {
-                content_size.y = f32::max(content_size.y, cursor - ancestor_padding.y)
-            }
+    #[test]
+    fn div_space () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        tree.borrow_or_create_ui_node_mut("Root").unwrap();
+        tree.borrow_data_mut("Root").unwrap().unwrap().stack = UiStack::new().margin(StackMargin::Between);
+
+        add_div(&mut tree, "Root/A", Div::new(), Vec2::new(100.0, 20.0));
+        add_div(&mut tree, "Root/B", Div::new().margin_y(Sp(1.0)), Vec2::new(100.0, 20.0));
+        add_div(&mut tree, "Root/C", Div::new().br(), Vec2::new(100.0, 60.0));
+        add_div(&mut tree, "Root/D", Div::new().min(Sp((1.0, 0.0))).max(Ab(200.0)), Vec2::new(100.0, 20.0));
+        add_div(&mut tree, "Root/E", Div::new().min(Sp((3.0, 0.0))), Vec2::new(100.0, 20.0));
+
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+
+        assert_eq!(rectangle(&tree, "Root/A").pos.truncate(), Vec2::new(0.0, 0.0));
+        assert_eq!(rectangle(&tree, "Root/B").pos.truncate(), Vec2::new(350.0, 20.0));
+        assert_eq!(rectangle(&tree, "Root/C").pos.truncate(), Vec2::new(700.0, 0.0));
+
+        assert_eq!(rectangle(&tree, "Root/D").size, Vec2::new(200.0, 20.0));
+        // The space D can't take goes to E, so the line fills the whole width
+        let e = rectangle(&tree, "Root/E");
+        assert_eq!(e.size, Vec2::new(475.0, 20.0));
+        assert_eq!(e.pos.truncate(), Vec2::new(325.0, 60.0));
+        assert_eq!(e.pos.x + e.size.x, 800.0);
+    }
 
-            line_cursor += comline.line_length;
+    #[test]
+    fn dirty_recompute () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        tree.borrow_or_create_ui_node_mut("Root").unwrap();
+        add_div(&mut tree, "Root/A", Div::new(), Vec2::new(100.0, 20.0));
+        add_div(&mut tree, "Root/B", Div::new(), Vec2::new(50.0, 20.0));
+        tree.borrow_or_create_ui_node_mut("Root/B/Window").unwrap();
+
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert!(!tree.borrow_data("Root/A").unwrap().unwrap().is_dirty());
+
+        // Clean nodes are not recomputed
+        tree.borrow_data_mut("Root/A").unwrap().unwrap().content_size = Vec2::new(200.0, 20.0);
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Root/A").size, Vec2::new(100.0, 20.0));
+
+        // Dirty Div also moves its siblings and their subnodes
+        tree.borrow_data_mut("Root/A").unwrap().unwrap().mark_dirty();
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Root/A").size, Vec2::new(200.0, 20.0));
+        assert_eq!(rectangle(&tree, "Root/B").pos.x, 200.0);
+        assert_eq!(rectangle(&tree, "Root/B/Window").pos.x, 200.0);
+    }
 
-            // END OF INSIDE LINE =================================================================
-            _i += 1;
-        }
+    #[test]
+    fn remove_recompute () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        tree.borrow_or_create_ui_node_mut("Root").unwrap();
+        add_div(&mut tree, "Root/A", Div::new().br(), Vec2::new(100.0, 20.0));
+        add_div(&mut tree, "Root/B", Div::new().br(), Vec2::new(100.0, 30.0));
+        add_div(&mut tree, "Root/C", Div::new().br(), Vec2::new(100.0, 40.0));
+
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Root/C").pos.y, 50.0);
+
+        // Removing a Div moves its siblings up
+        tree.remove_node("Root/B").unwrap();
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Root/C").pos.y, 20.0);
+
+        // Moving a Div restacks both parents
+        tree.borrow_or_create_ui_node_mut("Other").unwrap();
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        tree.move_node("Root/A", "Other/A").unwrap();
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Root/C").pos.y, 0.0);
+        assert_eq!(rectangle(&tree, "Other/A").size, Vec2::new(100.0, 20.0));
+    }
 
-        // Set content size
-        if horizontal {
-            content_size.y = line_cursor - ancestor_padding.y
-        } else {
-            content_size.x = line_cursor - ancestor_padding.x
+    #[test]
+    fn scale_recompute () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        for (path, window) in [("Root", Window::full()), ("Root/Panel", Window::new().size(Rl(50.0))), ("Root/Panel/Icon", Window::new().pos(Ab(5.0)).size(Ab(20.0)))] {
+            tree.borrow_or_create_ui_node_mut(path).unwrap().obtain_data_mut().unwrap()
+                .layout.insert(0, window.into());
         }
-        
-        // END OF INSIDE MATRIX =========================================================
-        content_size
+
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Root/Panel/Icon").size, Vec2::splat(20.0));
+
+        // Clean nodes with unchanged rectangles still pass the new scale down
+        tree.obtain_topdata_mut().unwrap().abs_scale = 2.0;
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Root/Panel").size, Vec2::new(400.0, 300.0));
+        assert_eq!(rectangle(&tree, "Root/Panel/Icon").pos.truncate(), Vec2::splat(10.0));
+        assert_eq!(rectangle(&tree, "Root/Panel/Icon").size, Vec2::splat(40.0));
     }
-    /// This is the secondary pass to align the nodes.
-    fn align_stack(&mut self, ancestor_position: Vec2) {
 
-        for (_, subnode) in &mut self.nodes {
-            if let Some(subnode_data) = &mut subnode.data {
-                if let Layout::Div(_) = &subnode_data.layout {
+    #[test]
+    fn rotation_inherit () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        tree.borrow_or_create_ui_node_mut("Card").unwrap().obtain_data_mut().unwrap()
+            .layout.insert(0, Window::new().size(Ab(100.0)).roll(std::f32::consts::FRAC_PI_2).into());
+        tree.borrow_or_create_ui_node_mut("Card/Dot").unwrap().obtain_data_mut().unwrap()
+            .layout.insert(0, Window::new().size(Rl(10.0)).into());
 
-                    subnode_data.rectangle.pos.x += ancestor_position.x;
-                    subnode_data.rectangle.pos.y += ancestor_position.y;
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
 
-                }
-                subnode.align_stack(ancestor_position);
-            }
+        // Top-left corner of the dot is rotated counterclockwise around the card center
+        let dot = tree.borrow_data("Card/Dot").unwrap().unwrap();
+        let corner = dot.transform.transform_point3(dot.rectangle.pos).truncate();
+        assert!(corner.abs_diff_eq(Vec2::new(0.0, 100.0), 0.001));
+    }
+
+    #[test]
+    fn scroll_offset () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        tree.borrow_or_create_ui_node_mut("List").unwrap().obtain_data_mut().unwrap()
+            .layout.insert(0, Window::new().pos(Ab(100.0)).size(Ab(200.0)).into());
+        tree.borrow_data_mut("List").unwrap().unwrap().stack = UiStack::new().direction(StackDirection::Vertical);
+        for i in 0..5 {
+            add_div(&mut tree, &format!("List/{i}"), Div::new(), Vec2::new(150.0, 100.0));
         }
+        tree.borrow_or_create_ui_node_mut("List/Header").unwrap().obtain_data_mut().unwrap()
+            .layout.insert(0, Window::new().size(Ab((200.0, 20.0))).into());
+
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(tree.borrow_data("List").unwrap().unwrap().scroll_size, Vec2::new(200.0, 500.0));
+        assert_eq!(rectangle(&tree, "List/4").pos.truncate(), Vec2::new(100.0, 500.0));
+
+        // Both parametric and declarative subnodes are moved
+        let list = tree.borrow_data_mut("List").unwrap().unwrap();
+        list.scroll = Vec2::new(0.0, 300.0);
+        list.mark_dirty();
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "List/4").pos.truncate(), Vec2::new(100.0, 200.0));
+        assert_eq!(rectangle(&tree, "List/Header").pos.truncate(), Vec2::new(100.0, -200.0));
+        assert_eq!(tree.borrow_data("List").unwrap().unwrap().scroll_size, Vec2::new(200.0, 500.0));
+    }
 
-    } */
-}
-/* 
-struct ComputedDiv {
-    size: Vec2,
-    margin: Vec4,
-}
-struct ComputedLine {
-    divs: Vec<ComputedDiv>,
-    line_length: f32,
+    #[test]
+    fn depth_bias () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        for path in ["Hud", "Hud/Bar", "Modal", "Modal/Button"] {
+            tree.borrow_or_create_ui_node_mut(path).unwrap().obtain_data_mut().unwrap()
+                .layout.insert(0, Window::full().into());
+        }
+        tree.borrow_data_mut("Hud").unwrap().unwrap().depth_bias = 10.0;
+        tree.borrow_data_mut("Modal").unwrap().unwrap().layer_bias = 100.0;
+        tree.borrow_data_mut("Modal/Button").unwrap().unwrap().depth_bias = 1.0;
+
+        // Depth bias moves only the node, layer bias the whole branch
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Hud").pos.z, 11.0);
+        assert_eq!(rectangle(&tree, "Hud/Bar").pos.z, 2.0);
+        assert_eq!(rectangle(&tree, "Modal").pos.z, 101.0);
+        assert_eq!(rectangle(&tree, "Modal/Button").pos.z, 103.0);
+
+        tree.borrow_data_mut("Modal").unwrap().unwrap().layer_bias = 0.0;
+        tree.borrow_data_mut("Modal").unwrap().unwrap().mark_dirty();
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Modal/Button").pos.z, 3.0);
+    }
+
+    #[test]
+    fn font_size () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        for path in ["Panel", "Panel/Label", "Panel/Label/Icon"] {
+            tree.borrow_or_create_ui_node_mut(path).unwrap().obtain_data_mut().unwrap()
+                .layout.insert(0, Window::new().size(Em(2.0)).into());
+        }
+        tree.borrow_data_mut("Panel").unwrap().unwrap().font_size = Some(Em(1.5).into());
+        // Plain pixels from before font size became a value still convert
+        tree.borrow_data_mut("Panel/Label/Icon").unwrap().unwrap().font_size = Some(10.0.into());
+
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(tree.borrow_data("Panel").unwrap().unwrap().computed_font_size, 24.0);
+        assert_eq!(rectangle(&tree, "Panel").size, Vec2::splat(48.0));
+        assert_eq!(rectangle(&tree, "Panel/Label").size, Vec2::splat(48.0));
+        assert_eq!(rectangle(&tree, "Panel/Label/Icon").size, Vec2::splat(20.0));
+
+        // Changing the root font size scales the whole tree
+        tree.obtain_topdata_mut().unwrap().font_size = 20.0;
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(rectangle(&tree, "Panel/Label").size, Vec2::splat(60.0));
+        assert_eq!(rectangle(&tree, "Panel/Label/Icon").size, Vec2::splat(20.0));
+    }
 }
- */
\ No newline at end of file
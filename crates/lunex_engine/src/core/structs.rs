@@ -1,8 +1,8 @@
 use std::marker::PhantomData;
 
-use crate::{import::*, NiceDisplay, UiStack};
+use crate::{import::*, NiceDisplay, UiStack, UiValue};
 use bevy::ecs::component::Component;
-use bevy::math::FloatExt;
+use bevy::math::{Affine3A, FloatExt};
 use colored::Colorize;
 
 use crate::nodes::prelude::*;
@@ -177,23 +177,31 @@ pub type UiNode<N = NoData> = Node<NodeData<N>>;
 // #=== DIFFERENT DATA TYPE GENERICS ===#
 
 #[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MainUi;
 
 /// Empty type to tell the compiler that there is no data stored in the node.
 #[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoData;
 
 
 /// A struct holding all data appended to [`UiTree`]. Responsible for storing settings, scaling, theme, etc.
 /// Every [`UiTree`] needs to have this to work properly.
 #[derive(Component, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MasterData<T> {
     /// Marker for query filtering
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub marker: PhantomData<T>,
     /// Scale of the [`crate::Abs`] unit.
     pub abs_scale: f32,
     /// Default font size for all subnodes to use (Rem unit scaling).
     pub font_size: f32,
+    /// Parent rectangle, absolute scale and font size of the last compute. If they change, all nodes are recomputed.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) computed_with: Option<(Rectangle3D, f32, f32)>,
 }
 impl <T> Default for MasterData<T> {
     fn default() -> Self {
@@ -201,6 +209,7 @@ impl <T> Default for MasterData<T> {
             marker: PhantomData,
             abs_scale: 1.0,
             font_size: 16.0,
+            computed_with: None,
         }
     }
 }
@@ -214,37 +223,77 @@ impl <T> NiceDisplay for MasterData<T> {
 /// A struct holding all data appended to [`UiNode`]. Responsible for storing layout, custom data, cache, etc.
 /// Every [`UiNode`] needs to have this to work properly.
 #[derive(Component, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct NodeData<N:Default + Component> {
     /// Optional data the user can append.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub data: Option<N>,
     /// Calculated rectangle from layout.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub rectangle: Rectangle3D,
+    /// Calculated transform of the node, including the rotation inherited from the parent nodes.
+    /// Maps the unrotated [`NodeData::rectangle`] into its final placement.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub transform: Affine3A,
+    /// Calculated border widths of the [`crate::Div`] layout. `x-left`, `y-top`, `z-right`, `w-bottom`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub border: Vec4,
     /// Layouts of this node.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
     pub layout: HashMap<usize, Layout>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub layout_index: [usize; 2],
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub layout_tween: f32,
+    /// Layouts blended on top of the tweened pair in this order, each with its own tween value.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub layout_chain: Vec<(usize, f32)>,
 
     /// Layout of subnodes and how to stack them.
     pub stack: UiStack,
-    /// Optional font size to overwrite the inherited master font size.
-    pub font_size: Option<f32>,
+    /// Optional font size to overwrite the inherited font size. The [`crate::Em`] unit is relative to the inherited font size.
+    /// Plain `f32` pixels used before still work with `.into()`, for example `Some(20.0.into())`.
+    pub font_size: Option<UiValue<f32>>,
+    /// Calculated font size of the node, used to resolve the [`crate::Em`] unit of its layout.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub computed_font_size: f32,
     /// Value that will be relatively applied to Z after layout compute.
     pub depth_bias: f32,
+    /// Value that will be relatively applied to Z after layout compute. Unlike [`NodeData::depth_bias`], it is inherited by all subnodes.
+    pub layer_bias: f32,
     /// Size of the content to wrap around. Affects this node's size only if the layout is parametric (Div).
     pub content_size: Vec2,
+    /// Offset of all subnodes, used by scroll containers. Positive values move the subnodes up and left.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub scroll: Vec2,
+    /// Computed size of the area covered by the subnodes, measured from the top-left corner of this node without the scroll offset.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub scroll_size: Vec2,
+    /// If the node and its subnodes need to be recomputed.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) dirty: bool,
 }
 impl <N:Default + Component> Default for NodeData<N> {
     fn default() -> Self {
         NodeData {
             data: Default::default(),
             rectangle: Default::default(),
+            transform: Affine3A::IDENTITY,
+            border: Vec4::ZERO,
             layout: HashMap::from([(0, Layout::default())]),
             layout_index: Default::default(),
             layout_tween: Default::default(),
+            layout_chain: Default::default(),
             stack: Default::default(),
             font_size: Default::default(),
+            computed_font_size: 16.0,
             depth_bias: Default::default(),
+            layer_bias: Default::default(),
             content_size: Default::default(),
+            scroll: Default::default(),
+            scroll_size: Default::default(),
+            dirty: true,
         }
     }
 }
@@ -252,6 +301,20 @@ impl <N:Default + Component> NodeData<N> {
     pub fn new() -> NodeData<N> {
         NodeData::default()
     }
+    /// Marks the node to be recomputed together with its subnodes. Needs to be called
+    /// after modifying any data the layout depends on, otherwise the change is ignored.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+    /// Returns if the node will be recomputed during the next compute.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
+/// Serializes the layouts sorted by index, so the output is stable
+#[cfg(feature = "serde")]
+fn serialize_sorted<S: serde::Serializer>(map: &HashMap<usize, Layout>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(map.iter().collect::<std::collections::BTreeMap<_, _>>())
 }
 impl <N:Default + Component> NiceDisplay for NodeData<N> {
     fn to_nicestr(&self) -> String {
@@ -262,4 +325,33 @@ impl <N:Default + Component> NiceDisplay for NodeData<N> {
         }
         format!("{} {} {}", st, "|||".black(), self.rectangle.to_nicestr())
     }
-}
\ No newline at end of file
+}
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn serde_roundtrip () {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        let data = tree.borrow_or_create_ui_node_mut("Root").unwrap().obtain_data_mut().unwrap();
+        data.layout.insert(0, Layout::window().pos(Rl(10.0)).size(Ab(200.0) + Em(2.0)).into());
+        data.layout.insert(1, Layout::window().pos(Rl(12.0)).size(Ab(200.0)).roll(0.5).into());
+        data.stack = UiStack::new().gap(Ab(10.0)).margin(StackMargin::Manual(Box::new(Sp(1.0).into())));
+        data.depth_bias = 5.0;
+        data.font_size = Some(Em(1.25).into());
+
+        let data = tree.borrow_or_create_ui_node_mut("Root/Item").unwrap().obtain_data_mut().unwrap();
+        data.layout.insert(0, Layout::div().pad(Ab(5.0)).min(Sp((1.0, 0.0))).br().into());
+        data.content_size = (100.0, 20.0).into();
+
+        let text = ron::ser::to_string_pretty(&tree, Default::default()).unwrap();
+        let loaded: UiTree = ron::from_str(&text).unwrap();
+        assert_eq!(loaded, tree);
+        assert_eq!(loaded.borrow_node("Root/Item").unwrap().get_path(), "Root/Item");
+        assert_eq!(ron::ser::to_string_pretty(&loaded, Default::default()).unwrap(), text);
+    }
+}
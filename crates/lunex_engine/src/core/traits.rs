@@ -5,6 +5,7 @@ use bevy::ecs::component::Component;
 use crate::nodes::prelude::*;
 //use crate::layout;
 use crate::MasterData;
+use bevy::math::Quat;
 use crate::import::*;
 
 use super::{UiNode, UiTree, NodeData};
@@ -44,6 +45,12 @@ impl YInvert for Vec4 {
         self
     }
 }
+impl YInvert for Quat {
+    /// Mirrors the rotation by the y axis, for converting rotations between y-up and y-down space.
+    fn invert_y(self) -> Self {
+        Quat::from_xyzw(-self.x, self.y, -self.z, self.w)
+    }
+}
 
 
 // #==========================#
@@ -33,8 +33,10 @@ macro_rules! uivalue_declare {
         /// let d: UiValue<Vec2> = (Ab(20.0), Em(2.0)).into() // -> [20px, 2em]
         /// ```
         #[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub struct UiValue<T> {
             $(
+                #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
                 $ufield: Option<T>,
             )*
         }
@@ -563,6 +565,7 @@ macro_rules! unit_cross_operations {
 /// let b: Ab<f32> = Ab(4.0) * 2.0;     // -> 8px
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ab<T>(pub T);
 
 /// **Relative** - Represents scalable unit `0% to 100%`. `120%` is allowed.
@@ -573,6 +576,7 @@ pub struct Ab<T>(pub T);
 /// let b: Rl<f32> = Rl(25.0) * 3.0;      // -> 75%
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rl<T>(pub T);
 
 /// **Relative width** - Represents scalable unit `0% to 100%`. `120%` is allowed.
@@ -584,6 +588,7 @@ pub struct Rl<T>(pub T);
 /// let b: Rw<f32> = Rw(25.0) * 3.0;      // -> 75%
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rw<T>(pub T);
 
 /// **Relative height** - Represents scalable unit `0% to 100%`. `120%` is allowed.
@@ -595,6 +600,7 @@ pub struct Rw<T>(pub T);
 /// let b: Rh<f32> = Rh(25.0) * 3.0;      // -> 75%
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rh<T>(pub T);
 
 /// **Size of M** - Represents unit that is the size of the symbol `M`. Which is `16px` with `font size 16px` and so on.
@@ -604,6 +610,7 @@ pub struct Rh<T>(pub T);
 /// let a: Em<f32> = Em(1.0) + Em(2.0); // -> 3em == 48px with font size 16px
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Em<T>(pub T);
 
 /// **Space** - Represents proportional empty space left in the parent container. Requires to know space unit of surrounding
@@ -617,6 +624,7 @@ pub struct Em<T>(pub T);
 /// ```
 /// If container `a` and `b` were next to each other, they would split remaining space in **3:6** ratio.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sp<T>(pub T);
 
 /// **Viewport** - Represents scalable unit `0% to 100%` of the root container. `120%` is allowed.
@@ -627,6 +635,7 @@ pub struct Sp<T>(pub T);
 /// let b: Vp<f32> = Vp(25.0) * 3.0;      // -> 75%
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vp<T>(pub T);
 
 /// **Viewport width** - Represents scalable unit `0% to 100%` of the root container. `120%` is allowed.
@@ -638,6 +647,7 @@ pub struct Vp<T>(pub T);
 /// let b: Vw<f32> = Vw(25.0) * 3.0;      // -> 75%
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vw<T>(pub T);
 
 /// **Viewport Height** - Represents scalable unit `0% to 100%` of the root container. `120%` is allowed.
@@ -649,10 +659,12 @@ pub struct Vw<T>(pub T);
 /// let b: Vh<f32> = Vh(25.0) * 3.0;      // -> 75%
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vh<T>(pub T);
 
 /// **Unit type** - Enum with all possible ui unit types.
 #[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UiValueType<T> {
     Ab(Ab<T>),
     Rl(Rl<T>),
@@ -757,6 +769,39 @@ unit_cross_operations!((Vh, vh), (Vw, vw));
 // #==============================#
 // #=== CUSTOM IMPLEMENTATIONS ===#
 
+// # Impl UiValueType => UiValue
+impl <T> From<UiValueType<T>> for UiValue<T> {
+    fn from(val: UiValueType<T>) -> Self {
+        match val {
+            UiValueType::Ab(v) => v.into(),
+            UiValueType::Rl(v) => v.into(),
+            UiValueType::Rw(v) => v.into(),
+            UiValueType::Rh(v) => v.into(),
+            UiValueType::Em(v) => v.into(),
+            UiValueType::Sp(v) => v.into(),
+            UiValueType::Vp(v) => v.into(),
+            UiValueType::Vw(v) => v.into(),
+            UiValueType::Vh(v) => v.into(),
+        }
+    }
+}
+impl <T> UiValueType<T> {
+    /// Maps the inner value while keeping the unit type.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> UiValueType<U> {
+        match self {
+            UiValueType::Ab(v) => UiValueType::Ab(Ab(f(v.0))),
+            UiValueType::Rl(v) => UiValueType::Rl(Rl(f(v.0))),
+            UiValueType::Rw(v) => UiValueType::Rw(Rw(f(v.0))),
+            UiValueType::Rh(v) => UiValueType::Rh(Rh(f(v.0))),
+            UiValueType::Em(v) => UiValueType::Em(Em(f(v.0))),
+            UiValueType::Sp(v) => UiValueType::Sp(Sp(f(v.0))),
+            UiValueType::Vp(v) => UiValueType::Vp(Vp(f(v.0))),
+            UiValueType::Vw(v) => UiValueType::Vw(Vw(f(v.0))),
+            UiValueType::Vh(v) => UiValueType::Vh(Vh(f(v.0))),
+        }
+    }
+}
+
 // # Impl (A, B) => UiValue(Vec2)
 impl <A, B> From<(A, B)> for UiValue<Vec2> where 
     A: Into<UiValue<f32>>, 
@@ -1028,6 +1073,14 @@ impl UiValueEvaluate<Vec4> for UiValue<Vec4> {
     }
 }
 
+impl <T: Default + Copy> UiValue<T> {
+    /// Returns the amount of [`Sp`] units. They are not resolved by [`UiValueEvaluate::evaluate`],
+    /// because the empty space is known only after all stacked siblings are computed.
+    pub fn get_sp(&self) -> T {
+        self.sp.unwrap_or_default()
+    }
+}
+
 
 impl NiceDisplay for UiValue<f32> {
     fn to_nicestr(&self) -> String {
@@ -1499,7 +1552,7 @@ impl NiceDisplay for UiValue<Vec4> {
 mod test {
     use crate::NiceDisplay;
 
-    use super::{Ab, Rl, Rw, Rh, Em, Sp, UiValue, Vec2};
+    use super::{Ab, Rl, Rw, Rh, Em, Sp, UiValue, UiValueType, Vec2};
     #[test]
     fn all () {
         let _: UiValue<f32> = Ab(5.0) + Rl(5.0);
@@ -1509,5 +1562,11 @@ mod test {
         let size: UiValue<Vec2> = Ab(Vec2::splat(5.0)) + Rl(Vec2::splat(5.0));
         println!("{}", size.to_nicestr());
     }
+    #[test]
+    fn value_type () {
+        let value: UiValue<f32> = UiValueType::Em(Em(2.0)).into();
+        assert_eq!(value, Em(2.0).into());
+        let value: UiValue<Vec2> = UiValueType::Rh(Rh(2.0)).map(|t| Vec2::new(10.0, 5.0) * t).into();
+        assert_eq!(value, Rh(Vec2::new(20.0, 10.0)).into());
+    }
 }
-
@@ -1,3 +1,4 @@
+use bevy::math::{Vec2Swizzles, Vec4Swizzles};
 use crate::{import::*, YInvert};
 use crate::{NiceDisplay, Rectangle2D, UiValue, UiValueEvaluate, Ab, Rl};
 
@@ -16,6 +17,7 @@ use crate::{NiceDisplay, Rectangle2D, UiValue, UiValueEvaluate, Ab, Rl};
 /// The expected range is `-1.0` to `1.0`, but you can extrapolate.
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Layout {
     Boundary(Boundary),
     Window(Window),
@@ -160,6 +162,7 @@ impl NiceDisplay for Layout {
 
 /// **Anchor** - A type used to define where should Window node layout be anchored at.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Anchor {
     Center,
     BottomLeft,
@@ -234,6 +237,7 @@ impl From<bevy::sprite::Anchor> for Anchor {
 /// ```
 /// The expected range is `-1.0` to `1.0`, but you can extrapolate.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Align (pub f32);
 impl Align {
     pub const START: Align = Align(-1.0);
@@ -265,6 +269,7 @@ impl NiceDisplay for Align {
 /// let scaling: Scaling = Scaling::Fill; // -> always cover all
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Scaling {
     /// Node layout should always cover the horizontal axis of the parent node.
     HorFill,
@@ -296,12 +301,13 @@ impl NiceDisplay for Scaling {
 /// let scaling: Sizing = Sizing::Max;   // -> Tries to reach maximum size limit
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Sizing {
-    /// Div node layout should be as small as possible.
+    /// Div node layout should be as small as possible. Uses the minimum size if set.
     Min,
-    /// Div node layout should 
+    /// Div node layout should be as big as its content, clamped by the minimum & maximum size.
     #[default] Basic,
-    /// Div node layout should be as big as possible.
+    /// Div node layout should be as big as possible. Uses the maximum size if set.
     Max,
 }
 impl NiceDisplay for Sizing {
@@ -326,11 +332,19 @@ impl NiceDisplay for Sizing {
 /// let layout: UiLayout = Boundary::new().pos1(Rl(20.0)).pos2(Rl(80.0)).pack();
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Boundary {
     /// Position of the top-left corner.
     pub pos1: UiValue<Vec2>,
     /// Position of the bottom-right corner.
     pub pos2: UiValue<Vec2>,
+    /// Rotation of the node around the z axis in radians, counterclockwise.
+    pub roll: f32,
+    /// Rotation of the node around the y axis in radians.
+    pub yaw: f32,
+    /// Rotation of the node around the x axis in radians.
+    pub tilt: f32,
 }
 impl Boundary {
     /// Creates new empty Boundary node layout.
@@ -338,6 +352,9 @@ impl Boundary {
         Boundary {
             pos1 : UiValue::new(),
             pos2: UiValue::new(),
+            roll: 0.0,
+            yaw: 0.0,
+            tilt: 0.0,
         }
     }
     /// Replaces the position of the top-left corner with a new value.
@@ -370,6 +387,21 @@ impl Boundary {
         self.pos2.set_y(y);
         self
     }
+    /// Replaces the roll with a new value.
+    pub fn roll(mut self, roll: f32) -> Self {
+        self.roll = roll;
+        self
+    }
+    /// Replaces the yaw with a new value.
+    pub fn yaw(mut self, yaw: f32) -> Self {
+        self.yaw = yaw;
+        self
+    }
+    /// Replaces the tilt with a new value.
+    pub fn tilt(mut self, tilt: f32) -> Self {
+        self.tilt = tilt;
+        self
+    }
     /// Sets the position of the top-left corner to a new value.
     pub fn set_pos1(&mut self, pos: impl Into<UiValue<Vec2>>) {
         self.pos1 = pos.into();
@@ -394,6 +426,18 @@ impl Boundary {
     pub fn set_y2(&mut self, y: impl Into<UiValue<f32>>) {
         self.pos2.set_y(y);
     }
+    /// Sets the roll to a new value.
+    pub fn set_roll(&mut self, roll: f32) {
+        self.roll = roll;
+    }
+    /// Sets the yaw to a new value.
+    pub fn set_yaw(&mut self, yaw: f32) {
+        self.yaw = yaw;
+    }
+    /// Sets the tilt to a new value.
+    pub fn set_tilt(&mut self, tilt: f32) {
+        self.tilt = tilt;
+    }
 
     /// Computes the layout based on given parameters.
     pub(crate) fn compute(&self, parent: Rectangle2D, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Rectangle2D {
@@ -430,6 +474,8 @@ impl NiceDisplay for Boundary {
 /// let layout: UiLayout = Window::new().pos(Ab(100.0)).size(Rl(50.0)).pack();
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Window {
     /// Position of the node.
     pub pos : UiValue<Vec2>,
@@ -437,6 +483,12 @@ pub struct Window {
     pub anchor: Anchor,
     /// Size of the node layout.
     pub size: UiValue<Vec2>,
+    /// Rotation of the node around the z axis in radians, counterclockwise.
+    pub roll: f32,
+    /// Rotation of the node around the y axis in radians.
+    pub yaw: f32,
+    /// Rotation of the node around the x axis in radians.
+    pub tilt: f32,
 }
 impl Window {
     /// Creates new empty Window node layout.
@@ -445,6 +497,9 @@ impl Window {
             pos : UiValue::new(),
             anchor: Anchor::TopLeft,
             size: UiValue::new(),
+            roll: 0.0,
+            yaw: 0.0,
+            tilt: 0.0,
         }
     }
     /// Creates new full Window node layout.
@@ -453,6 +508,9 @@ impl Window {
             pos : UiValue::new(),
             anchor: Anchor::TopLeft,
             size: Rl(100.0).into(),
+            roll: 0.0,
+            yaw: 0.0,
+            tilt: 0.0,
         }
     }
     /// Replaces the position with a new value.
@@ -490,6 +548,21 @@ impl Window {
         self.anchor = anchor.into();
         self
     }
+    /// Replaces the roll with a new value.
+    pub fn roll(mut self, roll: f32) -> Self {
+        self.roll = roll;
+        self
+    }
+    /// Replaces the yaw with a new value.
+    pub fn yaw(mut self, yaw: f32) -> Self {
+        self.yaw = yaw;
+        self
+    }
+    /// Replaces the tilt with a new value.
+    pub fn tilt(mut self, tilt: f32) -> Self {
+        self.tilt = tilt;
+        self
+    }
     /// Sets the position to a new value.
     pub fn set_pos(&mut self, pos: impl Into<UiValue<Vec2>>){
         self.pos = pos.into();
@@ -518,6 +591,18 @@ impl Window {
     pub fn set_anchor(&mut self, anchor: impl Into<Anchor>){
         self.anchor = anchor.into();
     }
+    /// Sets the roll to a new value.
+    pub fn set_roll(&mut self, roll: f32) {
+        self.roll = roll;
+    }
+    /// Sets the yaw to a new value.
+    pub fn set_yaw(&mut self, yaw: f32) {
+        self.yaw = yaw;
+    }
+    /// Sets the tilt to a new value.
+    pub fn set_tilt(&mut self, tilt: f32) {
+        self.tilt = tilt;
+    }
 
     /// Computes the layout based on given parameters.
     pub(crate) fn compute(&self, parent: Rectangle2D, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Rectangle2D {
@@ -555,6 +640,8 @@ impl NiceDisplay for Window {
 /// let layout: UiLayout = Solid::new().size((4.0, 3.0)).align_x(-0.8).pack();
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Solid {
     /// Aspect ratio of the width and height. `1:1 == 10:10 == 100:100`.
     pub size: UiValue<Vec2>,
@@ -564,6 +651,12 @@ pub struct Solid {
     pub align_y: Align,
     /// Specifies container scaling.
     pub scaling: Scaling,
+    /// Rotation of the node around the z axis in radians, counterclockwise.
+    pub roll: f32,
+    /// Rotation of the node around the y axis in radians.
+    pub yaw: f32,
+    /// Rotation of the node around the x axis in radians.
+    pub tilt: f32,
 }
 impl Solid {
     /// Creates new empty Solid node layout.
@@ -573,6 +666,9 @@ impl Solid {
             align_x: Align::CENTER,
             align_y: Align::CENTER,
             scaling: Scaling::Fit,
+            roll: 0.0,
+            yaw: 0.0,
+            tilt: 0.0,
         }
     }
     /// Replaces the size with a new value.
@@ -605,6 +701,21 @@ impl Solid {
         self.scaling = scaling;
         self
     }
+    /// Replaces the roll with a new value.
+    pub fn roll(mut self, roll: f32) -> Self {
+        self.roll = roll;
+        self
+    }
+    /// Replaces the yaw with a new value.
+    pub fn yaw(mut self, yaw: f32) -> Self {
+        self.yaw = yaw;
+        self
+    }
+    /// Replaces the tilt with a new value.
+    pub fn tilt(mut self, tilt: f32) -> Self {
+        self.tilt = tilt;
+        self
+    }
     /// Sets the size to a new value.
     pub fn set_size(&mut self, size: impl Into<UiValue<Vec2>>) {
         self.size = size.into();
@@ -629,6 +740,18 @@ impl Solid {
     pub fn set_scaling(&mut self, scaling: Scaling) {
         self.scaling = scaling;
     }
+    /// Sets the roll to a new value.
+    pub fn set_roll(&mut self, roll: f32) {
+        self.roll = roll;
+    }
+    /// Sets the yaw to a new value.
+    pub fn set_yaw(&mut self, yaw: f32) {
+        self.yaw = yaw;
+    }
+    /// Sets the tilt to a new value.
+    pub fn set_tilt(&mut self, tilt: f32) {
+        self.tilt = tilt;
+    }
 
     /// Computes the layout based on given parameters.
     pub(crate) fn compute(&self, parent: Rectangle2D, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Rectangle2D {
@@ -683,6 +806,8 @@ impl NiceDisplay for Solid {
 /// let layout: UiLayout = Div::new().pad_x(2.0).margin_y(Sp(1.0)).br().pack();
 /// ```
 #[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Div {
     /// Describes how width should size itself.
     pub width: Sizing,
@@ -700,6 +825,12 @@ pub struct Div {
     pub margin: UiValue<Vec4>,
     /// Force a line break in the ui flow after this node.
     pub br: bool,
+    /// Rotation of the node around the z axis in radians, counterclockwise.
+    pub roll: f32,
+    /// Rotation of the node around the y axis in radians.
+    pub yaw: f32,
+    /// Rotation of the node around the x axis in radians.
+    pub tilt: f32,
 }
 impl Div {
     /// Creates new empty Div node layout.
@@ -713,6 +844,9 @@ impl Div {
             border: UiValue::new(),
             margin: UiValue::new(),
             br: false,
+            roll: 0.0,
+            yaw: 0.0,
+            tilt: 0.0,
         }
     }
     /// Replaces the width with a new value.
@@ -899,6 +1033,21 @@ impl Div {
         self.br = true;
         self
     }
+    /// Replaces the roll with a new value.
+    pub fn roll(mut self, roll: f32) -> Self {
+        self.roll = roll;
+        self
+    }
+    /// Replaces the yaw with a new value.
+    pub fn yaw(mut self, yaw: f32) -> Self {
+        self.yaw = yaw;
+        self
+    }
+    /// Replaces the tilt with a new value.
+    pub fn tilt(mut self, tilt: f32) -> Self {
+        self.tilt = tilt;
+        self
+    }
     /// Sets the width to a new value.
     pub fn set_width(&mut self, sizing: Sizing) {
         self.width = sizing;
@@ -1049,6 +1198,63 @@ impl Div {
     pub fn set_margin_b(&mut self, margin: impl Into<UiValue<f32>>) {
         self.margin.set_w(margin);
     }
+    /// Sets the roll to a new value.
+    pub fn set_roll(&mut self, roll: f32) {
+        self.roll = roll;
+    }
+    /// Sets the yaw to a new value.
+    pub fn set_yaw(&mut self, yaw: f32) {
+        self.yaw = yaw;
+    }
+    /// Sets the tilt to a new value.
+    pub fn set_tilt(&mut self, tilt: f32) {
+        self.tilt = tilt;
+    }
+
+    /// Computes the padding based on given parameters.
+    pub(crate) fn compute_padding(&self, parent_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Vec4 {
+        self.padding.evaluate(Vec4::splat(absolute_scale), parent_size.xyxy(), viewport_size.xyxy(), Vec4::splat(font_size))
+    }
+    /// Computes the border based on given parameters.
+    pub(crate) fn compute_border(&self, parent_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Vec4 {
+        self.border.evaluate(Vec4::splat(absolute_scale), parent_size.xyxy(), viewport_size.xyxy(), Vec4::splat(font_size))
+    }
+    /// Computes the margin based on given parameters.
+    pub(crate) fn compute_margin(&self, parent_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Vec4 {
+        self.margin.evaluate(Vec4::splat(absolute_scale), parent_size.xyxy(), viewport_size.xyxy(), Vec4::splat(font_size))
+    }
+    /// Computes the size of the node from its content, padding and border. The size is then resized
+    /// by [`Sizing`] of each axis and clamped by the min & max size.
+    pub(crate) fn compute_size(&self, content_size: Vec2, parent_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Vec2 {
+        let padding = self.compute_padding(parent_size, absolute_scale, viewport_size, font_size);
+        let border = self.compute_border(parent_size, absolute_scale, viewport_size, font_size);
+        let basic = content_size + padding.xy() + padding.zw() + border.xy() + border.zw();
+
+        let min = self.min_size.map(|min| min.evaluate(Vec2::splat(absolute_scale), parent_size, viewport_size, Vec2::splat(font_size)));
+        let max = self.max_size.map(|max| max.evaluate(Vec2::splat(absolute_scale), parent_size, viewport_size, Vec2::splat(font_size)));
+
+        // Pick the size each axis tries to reach
+        let sizing = |sizing: Sizing, basic: f32, min: Option<f32>, max: Option<f32>| match sizing {
+            Sizing::Min => min.unwrap_or(basic),
+            Sizing::Basic => basic,
+            Sizing::Max => max.unwrap_or(basic),
+        };
+        let mut size = Vec2::new(
+            sizing(self.width, basic.x, min.map(|v| v.x), max.map(|v| v.x)),
+            sizing(self.height, basic.y, min.map(|v| v.y), max.map(|v| v.y)),
+        );
+
+        if let Some(max) = max { size = size.min(max) }
+        if let Some(min) = min { size = size.max(min) }
+        size
+    }
+    /// Computes the max size. Returns [`f32::MAX`] if the size is not limited.
+    pub(crate) fn compute_max_size(&self, parent_size: Vec2, absolute_scale: f32, viewport_size: Vec2, font_size: f32) -> Vec2 {
+        match self.max_size {
+            Some(max) => max.evaluate(Vec2::splat(absolute_scale), parent_size, viewport_size, Vec2::splat(font_size)),
+            None => Vec2::MAX,
+        }
+    }
 
     /// Packs the struct into Layout
     pub fn package(self) -> Layout {
@@ -7,6 +7,7 @@ use crate::UiValue;
 
 /// **Stack direction** - A type used to define in which direction should the subnodes be placed.
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StackDirection {
     #[default]
     Horizontal,
@@ -15,6 +16,7 @@ pub enum StackDirection {
 
 
 /// **Stack margin** - A special type to define margin subnodes should inherit. Contains a set of presets.
+/// The presets are applied to each line of the stack separately, with sides relative to the stack direction.
 /// ## 🛠️ Example
 /// ```
 /// # use lunex_engine::StackMargin;
@@ -22,10 +24,11 @@ pub enum StackDirection {
 /// let margin = StackMargin::Center; // -> Subnodes on sides will inherit 1sp on sides facing out
 /// let margin = StackMargin::End;    // -> First subnode will inherit 1sp on left side
 /// let margin = StackMargin::Between;// -> All subnodes except 1st will inherit 1sp on left side
-/// let margin = StackMargin::Evenly; // -> All subnodes will inherit 1sp on both sides except 1st
+/// let margin = StackMargin::Evenly; // -> All subnodes will inherit 1sp on left side, last one also on right side
 /// let margin = StackMargin::Around; // -> All subnodes will inherit 1sp on both sides
 /// ```
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StackMargin {
     /// Default, does nothing.
     #[default]
@@ -36,7 +39,7 @@ pub enum StackMargin {
     End,
     /// All subnodes except 1st will inherit 1sp on left side.
     Between,
-    /// All subnodes will inherit 1sp on both sides except 1st (only left).
+    /// All subnodes will inherit 1sp on left side, last one also on right side.
     Evenly,
     /// All subnodes will inherit 1sp on both sides.
     Around,
@@ -55,6 +58,8 @@ pub enum StackMargin {
 /// let stack: UiStack = UiStack::new().flipped(true);
 /// ```
 #[derive(Debug, Default, Clone, PartialEq, Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct UiStack {
     /// Populating direction
     pub direction: StackDirection,
@@ -66,6 +66,7 @@ pub enum NodeError {
 /// ## ⚠️ Warning
 /// Please refrain from manually using `".||#:0"`, `".||#:1"`, `".||#:2"`, ... as names or [`NodeGeneralTrait::add_node`] will return errors.
 #[derive(Component, Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize), serde(from = "NodeTreeSerde<D, T>", bound(deserialize = "D: serde::Deserialize<'de>, T: serde::Deserialize<'de>")))]
 pub struct NodeTree<D, T> {
     /// ## Top-level data
     /// This top-level data is meant to be shared for every node. Example usage is storing `theme` and other surface data.
@@ -123,6 +124,10 @@ impl <D, T> NodeGeneralTrait<T> for NodeTree<D, T> {
         self.node.remove_node(path)
     }
 
+    fn move_node(&mut self, path: impl Borrow<str>, new_path: impl Borrow<str>) -> Result<String, NodeError> {
+        self.node.move_node(path, new_path)
+    }
+
     fn obtain_node(&self, name: impl Borrow<str>) -> Result<&Node<T>, NodeError> {
         self.node.obtain_node(name)
     }
@@ -233,31 +238,93 @@ impl <D, T> From<NodeTree<D, T>> for Node<T>{
 }
 
 
+// #=============#
+// #=== SERDE ===#
+
+/// Serialized form of [`NodeTree`]. Names of subnodes are stored as keys, the cached paths are rebuilt after loading.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(bound(deserialize = "D: serde::Deserialize<'de>, T: serde::Deserialize<'de>"))]
+struct NodeTreeSerde<D, T> {
+    name: String,
+    #[serde(default)]
+    data: Option<D>,
+    node: Node<T>,
+}
+#[cfg(feature = "serde")]
+impl <D, T> From<NodeTreeSerde<D, T>> for NodeTree<D, T> {
+    fn from(value: NodeTreeSerde<D, T>) -> Self {
+        let mut node = value.node;
+        node.name = value.name;
+        node.path = "".into();
+        node.depth = 0.0;
+        node.refresh_cache();
+        NodeTree { data: value.data, node }
+    }
+}
+#[cfg(feature = "serde")]
+impl <D: serde::Serialize, T: serde::Serialize> serde::Serialize for NodeTree<D, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("NodeTree", 3)?;
+        state.serialize_field("name", &self.node.name)?;
+        state.serialize_field("data", &self.data)?;
+        state.serialize_field("node", &self.node)?;
+        state.end()
+    }
+}
+
+
 // #============#
 // #=== NODE ===#
 
 /// A struct representing organized data in [`NodeTree`].
-#[derive(Component, Debug, Default, Clone, PartialEq)]
+#[derive(Component, Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound(deserialize = "T: serde::Deserialize<'de>")))]
 pub struct Node<T> {
     /// ## Name
     /// Name of the node. `Cached` & `Read-only`.
+    #[cfg_attr(feature = "serde", serde(skip))]
     name: String,
     /// ## Path
     /// Full path without the name. `Cached` & `Read-only`.
+    #[cfg_attr(feature = "serde", serde(skip))]
     path: String,
     /// ## Depth
     /// Depth within the hierarchy. `Cached` & `Read-only`.
+    #[cfg_attr(feature = "serde", serde(skip))]
     depth: f32,
 
     /// ## Data
     /// Optional data this node can have. Example usage is storing `node layout` and other specific data.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub data: Option<T>,
     /// ## Nodes
     /// All subnodes this node contains. Treat is as `Read-only` unless you know what you are doing.
     /// Use the struct methods to manipulate the values inside.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "IndexMap::is_empty"))]
     pub nodes: IndexMap<String, Node<T>>,
+    /// ## Changed
+    /// If subnodes were added or removed since the flag was last cleared. `Read-only`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) changed: bool,
+}
+impl <T: PartialEq> PartialEq for Node<T> {
+    /// The change flag is ignored, it only tracks pending work
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.path == other.path && self.depth == other.depth && self.data == other.data && self.nodes == other.nodes
+    }
 }
 impl <T> Node<T> {
+    /// Recursively sets the cached name, path and depth of all subnodes
+    fn refresh_cache(&mut self) {
+        for (name, node) in &mut self.nodes {
+            node.name = name.to_owned();
+            node.path = if self.path.is_empty() { name.to_owned() } else { self.path.to_owned() + "/" + name };
+            node.depth = self.depth + 1.0;
+            node.refresh_cache();
+        }
+    }
     /// Generate overview of the inner tree and write the mapped output to the given string with data formatted to a certain level depth
     pub(crate) fn cascade_tree(&self, mut string: String, level: u32, param: &str) -> String {
         for (name, node) in &self.nodes {
@@ -300,6 +367,7 @@ impl <T> NodeInitTrait for Node<T> {
 
             data: None,
             nodes: IndexMap::new(),
+            changed: false,
         }
     }
 }
@@ -313,6 +381,7 @@ impl <T> NodeGeneralTrait<T> for Node<T> {
                 node.path = if self.path.is_empty() { name.borrow().to_string() } else { self.path.to_string() + "/" + name.borrow() };
                 node.depth = self.depth + 1.0;
                 self.nodes.insert(name.borrow().to_owned(), node);
+                self.changed = true;
                 Ok(name.borrow().to_owned())
             } else {
                 Err(NodeError::NameInUse(name.borrow().to_owned()))
@@ -329,6 +398,7 @@ impl <T> NodeGeneralTrait<T> for Node<T> {
             node.path = if self.path.is_empty() { generated_name.to_owned() } else { self.path.to_owned() + "/" + &generated_name };
             node.depth = self.depth + 1.0;
             self.nodes.insert(generated_name.to_owned(), node);
+            self.changed = true;
             Ok(generated_name)
         }
     }
@@ -342,7 +412,10 @@ impl <T> NodeGeneralTrait<T> for Node<T> {
 
     fn take_node(&mut self, name: impl Borrow<str>) -> Result<Node<T>, NodeError> {
         match self.nodes.shift_remove(name.borrow()) {
-            Some(node) => Ok(node),
+            Some(node) => {
+                self.changed = true;
+                Ok(node)
+            },
             None => Err(NodeError::NoNode(name.borrow().to_owned())),
         }
     }
@@ -354,6 +427,18 @@ impl <T> NodeGeneralTrait<T> for Node<T> {
         }
     }
 
+    fn move_node(&mut self, path: impl Borrow<str>, new_path: impl Borrow<str>) -> Result<String, NodeError> {
+        let (path, new_path) = (path.borrow(), new_path.borrow());
+        if new_path.starts_with(&format!("{path}/")) { return Err(NodeError::InvalidPath(new_path.to_owned())); }
+        if self.borrow_node(new_path).is_ok() { return Err(NodeError::NameInUse(new_path.to_owned())); }
+        if let Some((parent, _)) = new_path.rsplit_once('/') { self.borrow_node(parent)?; }
+
+        let node = self.remove_node(path)?;
+        let name = self.insert_node(new_path, node)?;
+        self.borrow_node_mut(new_path)?.refresh_cache();
+        Ok(name)
+    }
+
     fn obtain_node(&self, name: impl Borrow<str>) -> Result<&Node<T>, NodeError> {
         if !name.borrow().is_empty() {
             if name.borrow() == "." { return Ok(self) }
@@ -446,6 +531,7 @@ impl <T> NodeCreationTrait<T> for Node<T> {
                 node.path = if self.path.is_empty() { name.borrow().to_string() } else { self.path.to_string() + "/" + name.borrow() };
                 node.depth = self.depth + 1.0;
                 self.nodes.insert(name.borrow().to_owned(), node);
+                self.changed = true;
                 Ok(name.borrow().to_owned())
             } else {
                 Err(NodeError::NameInUse(name.borrow().to_owned()))
@@ -463,6 +549,7 @@ impl <T> NodeCreationTrait<T> for Node<T> {
             node.path = if self.path.is_empty() { generated_name.to_owned() } else { self.path.to_owned() + "/" + &generated_name };
             node.depth = self.depth + 1.0;
             self.nodes.insert(generated_name.to_owned(), node);
+            self.changed = true;
             Ok(generated_name)
         }
     }
@@ -539,3 +626,47 @@ impl <T:NiceDisplay> NodeDisplayTrait<T> for Node<T> {
         )
     }
 }
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    fn tree() -> UiTree {
+        let mut tree: UiTree = UiTree::new2d("Test");
+        for path in ["Menu", "Menu/Button", "Menu/Button/Icon", "Hud"] {
+            tree.borrow_or_create_ui_node_mut(path).unwrap();
+        }
+        tree
+    }
+
+    #[test]
+    fn move_node () {
+        let mut tree = tree();
+        assert_eq!(tree.move_node("Menu/Button", "Hud/Play"), Ok("Play".to_string()));
+        assert_eq!(tree.borrow_node("Menu/Button").unwrap_err(), NodeError::NoNode("Button".to_string()));
+
+        // Subnodes are moved too and know their new path
+        let icon = tree.borrow_node("Hud/Play/Icon").unwrap();
+        assert_eq!(icon.get_path(), "Hud/Play/Icon");
+        assert_eq!(icon.get_depth(), 3.0);
+    }
+
+    #[test]
+    fn move_node_into_subtree () {
+        let mut tree = tree();
+        assert_eq!(tree.move_node("Menu", "Menu/Button/Menu"), Err(NodeError::InvalidPath("Menu/Button/Menu".to_string())));
+        assert!(tree.borrow_node("Menu/Button/Icon").is_ok());
+    }
+
+    #[test]
+    fn move_node_name_in_use () {
+        let mut tree = tree();
+        assert_eq!(tree.move_node("Hud", "Menu/Button"), Err(NodeError::NameInUse("Menu/Button".to_string())));
+        assert!(tree.borrow_node("Hud").is_ok());
+        assert!(tree.borrow_node("Menu/Button/Icon").is_ok());
+    }
+}
@@ -24,7 +24,14 @@ pub trait NodeGeneralTrait<T> {
     /// Removes subnode from this node or any other subnode and returns it.
     /// ## 📌 Note
     /// * Use [`NodeGeneralTrait::take_node`] for direct retrieval on this node `(no recursion)`
+    /// * The parent is flagged as changed, so [`UiTree`](crate::UiTree) restacks the remaining subnodes on the next compute.
     fn remove_node(&mut self, path: impl Borrow<str>) -> Result<Node<T>, NodeError>;
+    /// ## 🚸 Recursive
+    /// Moves subnode from the path to the new path together with all of its subnodes and returns the new subnodes' name.
+    /// ## 📌 Note
+    /// * The parent of the new path must already exist and the new path must not be in use.
+    /// * Both parents are flagged as changed, so [`UiTree`](crate::UiTree) restacks their subnodes on the next compute.
+    fn move_node(&mut self, path: impl Borrow<str>, new_path: impl Borrow<str>) -> Result<String, NodeError>;
     /// Borrows subnode from this node.
     /// ## 📌 Note
     /// * Use [`NodeGeneralTrait::borrow_node`] for hierarchy retrieval `(supports path recursion)`